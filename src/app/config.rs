@@ -1,9 +1,13 @@
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::events::Action;
 
 /// Worktreeパステンプレート
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum WorktreePathStyle {
     /// リポジトリと並列に配置: {repo_parent}/{repo}__{branch}
     /// 例: ~/work/config__feature-branch
@@ -23,44 +27,120 @@ impl Default for WorktreePathStyle {
     }
 }
 
+/// worktree を作ろうとしたブランチが既に別の worktree にある場合の動作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExistingWorktreeBehavior {
+    /// 既存の worktree をツリーで選択する
+    #[default]
+    Select,
+    /// 既存の worktree を選択して開く（Enter と同じ）
+    Open,
+    /// エラーとして表示する
+    Error,
+}
+
+/// ワークスペース内のセッションの並び順
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionSortOrder {
+    /// 登録順
+    #[default]
+    Registration,
+    /// 最近アクティブなものを先頭に
+    RecentActivity,
+    /// 状態の優先度順（NeedsInput > Error > Working > Success > Idle）
+    Status,
+}
+
+/// パスの表示方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PathDisplay {
+    /// 絶対パスのまま
+    Absolute,
+    /// ホームディレクトリを ~ に短縮
+    #[default]
+    Tilde,
+    /// 最も近い search_paths からの相対パス（例: github.com/owner/repo）
+    RelativeToRoot,
+}
+
 /// Worktree設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorktreeConfig {
     /// パス生成スタイル
+    #[serde(default)]
     pub path_style: WorktreePathStyle,
     /// ghq root（Ghqスタイル使用時）
+    #[serde(default = "default_ghq_root")]
     pub ghq_root: Option<PathBuf>,
     /// デフォルトのリモート
+    #[serde(default = "default_remote")]
     pub default_remote: String,
     /// リモートブランチの最大表示数（0で無制限）
     #[serde(default = "default_max_remote_branches")]
     pub max_remote_branches: usize,
+    /// worktree作成後に新しいworktreeで実行するコマンド（{path}/{branch}をシェルクォートして置換）
+    /// 例: "cp ../.env . && direnv allow && pnpm install"
+    #[serde(default)]
+    pub post_create_command: Option<String>,
+    /// リポジトリ名ごとの post_create_command 上書き
+    #[serde(default)]
+    pub post_create_commands: HashMap<String, String>,
+    /// worktree作成時に元リポジトリからコピーするファイル（glob、リポジトリルートからの相対パス）
+    /// 例: [".env*", ".envrc", "config/local.yml"]
+    #[serde(default)]
+    pub copy_into_worktree: Vec<String>,
+    /// 作成しようとしたブランチが既に別の worktree にチェックアウトされている場合の動作
+    #[serde(default)]
+    pub on_existing_worktree: ExistingWorktreeBehavior,
 }
 
 fn default_max_remote_branches() -> usize {
     100
 }
 
+/// ghq rootを自動検出
+fn default_ghq_root() -> Option<PathBuf> {
+    std::env::var("GHQ_ROOT")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| {
+            directories::BaseDirs::new().map(|d| d.home_dir().join("ghq"))
+        })
+}
+
+fn default_remote() -> String {
+    "origin".to_string()
+}
+
 impl Default for WorktreeConfig {
     fn default() -> Self {
-        // ghq rootを自動検出
-        let ghq_root = std::env::var("GHQ_ROOT")
-            .ok()
-            .map(PathBuf::from)
-            .or_else(|| {
-                directories::BaseDirs::new().map(|d| d.home_dir().join("ghq"))
-            });
-
         Self {
             path_style: WorktreePathStyle::Parallel,
-            ghq_root,
-            default_remote: "origin".to_string(),
+            ghq_root: default_ghq_root(),
+            default_remote: default_remote(),
             max_remote_branches: default_max_remote_branches(),
+            post_create_command: None,
+            post_create_commands: HashMap::new(),
+            copy_into_worktree: Vec::new(),
+            on_existing_worktree: ExistingWorktreeBehavior::default(),
         }
     }
 }
 
 impl WorktreeConfig {
+    /// リポジトリに適用する post_create_command を取得（リポジトリ別 > 共通）
+    pub fn post_create_command_for(&self, repo_path: &std::path::Path) -> Option<&str> {
+        repo_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|name| self.post_create_commands.get(name))
+            .or(self.post_create_command.as_ref())
+            .map(|s| s.as_str())
+    }
+
     /// worktreeのパスを生成
     pub fn generate_worktree_path(
         &self,
@@ -114,7 +194,7 @@ impl WorktreeConfig {
 }
 
 /// Git URLをパース (host, owner, repo)
-fn parse_git_url(url: &str) -> Option<(String, String, String)> {
+pub fn parse_git_url(url: &str) -> Option<(String, String, String)> {
     // SSH形式: git@github.com:owner/repo.git
     if let Some(rest) = url.strip_prefix("git@") {
         let parts: Vec<&str> = rest.splitn(2, ':').collect();
@@ -149,20 +229,37 @@ fn parse_git_url(url: &str) -> Option<(String, String, String)> {
 }
 
 /// アプリケーション設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
     /// 検索対象ディレクトリ
+    #[serde(default = "default_search_paths")]
     pub search_paths: Vec<PathBuf>,
     /// 最大検索深度
+    #[serde(default = "default_max_scan_depth")]
     pub max_scan_depth: usize,
     /// MCPサーバーソケットパス
+    #[serde(default = "default_socket_path")]
     pub socket_path: PathBuf,
     /// ログレベル
+    #[serde(default = "default_log_level")]
     pub log_level: String,
     /// エディタコマンド（code, cursor, vim など）
+    /// 見つからなければ開くときに $VISUAL → $EDITOR → code / cursor / zed / subl → nvim / vim / vi の順に探す
     #[serde(default = "default_editor")]
     pub editor: String,
+    /// 端末内で動くエディタ（マルチプレクサの新しいペインで開く）。"emacs -nw" のように引数も指定可
+    #[serde(default = "default_terminal_editors")]
+    pub terminal_editors: Vec<String>,
+    /// `w` で開くファイル（ワークスペースからの相対パス）。セッションが最後に触ったファイルが優先、未設定時は README
+    #[serde(default)]
+    pub default_open_file: Option<String>,
+    /// 差分表示コマンド（{base}/{path}をシェルクォートして置換し `sh -c` で実行）
+    /// 未設定時はマルチプレクサのペインで `git diff {base}...HEAD` を表示
+    /// 例: "git difftool --dir-diff --tool=vscode {base}...HEAD"
+    #[serde(default)]
+    pub diff_command: Option<String>,
     /// Zellij連携設定（後方互換）
+    #[serde(default)]
     pub zellij: ZellijConfig,
     /// マルチプレクサ設定（[multiplexer] が優先、未設定時は [zellij] にフォールバック）
     #[serde(default)]
@@ -170,7 +267,92 @@ pub struct Config {
     /// Nerd Fontアイコンを使用するか
     #[serde(default = "default_use_nerd_font")]
     pub use_nerd_font: bool,
+    /// コミットアクティビティの集計期間（日数、0で非表示）
+    #[serde(default = "default_activity_days")]
+    pub activity_days: u32,
+    /// 更新が途絶えたセッションを切断扱いにするまでの秒数（0で無効）
+    #[serde(default)]
+    pub session_timeout_secs: u64,
+    /// ポーリングで報告された Error を表示するまでに必要な連続回数（1 で即時）
+    #[serde(default = "default_error_confirm_polls")]
+    pub error_confirm_polls: u32,
+    /// 切断済みセッションを保持する秒数（過ぎたものは破棄、0で無期限）
+    #[serde(default = "default_disconnected_session_retention_secs")]
+    pub disconnected_session_retention_secs: u64,
+    /// 保持するセッション数の上限（超えた分は古い切断済みセッションから破棄、0で無制限）
+    #[serde(default = "default_max_sessions")]
+    pub max_sessions: usize,
+    /// Idle のセッションをツリーに表示しない（実行中は h で切り替え）
+    #[serde(default)]
+    pub hide_idle_sessions: bool,
+    /// ワークスペース内のセッションの並び順（"registration" / "recent_activity" / "status"）
+    #[serde(default)]
+    pub session_sort: SessionSortOrder,
+    /// パスの表示方法（"absolute" / "tilde" / "relative_to_root"）
+    #[serde(default)]
+    pub path_display: PathDisplay,
+    /// リポジトリグループに origin のホスト名を表示する
+    #[serde(default)]
+    pub show_remote_host: bool,
+    /// 折りたたまれたリポジトリを、セッションが Working / NeedsInput になったとき展開する
+    #[serde(default)]
+    pub auto_expand_on_activity: bool,
+    /// スキャン対象外のパスでセッションが登録されたとき、一時的なワークスペースとして追加する
+    #[serde(default)]
+    pub auto_add_unknown_workspaces: bool,
+    /// セッション概要（summary）の最大文字数
+    #[serde(default = "default_summary_max_len")]
+    pub summary_max_len: usize,
+    /// 現在のタスク（current_task）の最大文字数
+    #[serde(default = "default_task_max_len")]
+    pub task_max_len: usize,
+    /// 詳細サイドパネルの幅（画面幅に対する%、20〜80）
+    #[serde(default = "default_detail_pane_percent")]
+    pub detail_pane_percent: u16,
+    /// ダブルクリックで実行するアクション名（"select", "open_in_editor" など）
+    #[serde(default = "default_mouse_double_click")]
+    pub mouse_double_click: String,
+    /// ミドルクリックで実行するアクション名
+    #[serde(default = "default_mouse_middle_click")]
+    pub mouse_middle_click: String,
+    /// リストでワークスペースを選択したときにバックグラウンドで実行するコマンド（{path}/{branch}をシェルクォートして置換）
+    #[serde(default)]
+    pub on_workspace_select_command: Option<String>,
+    /// on_workspace_select_command を実行するまでのデバウンス時間（ミリ秒）
+    #[serde(default = "default_on_workspace_select_debounce_ms")]
+    pub on_workspace_select_debounce_ms: u64,
+    /// ステータスバーのメッセージを消すまでの秒数（0で消さない）
+    #[serde(default = "default_status_message_ttl_secs")]
+    pub status_message_ttl_secs: u64,
+    /// 失敗・エラーのメッセージは時間で消さず、次のメッセージまで残す
+    #[serde(default)]
+    pub sticky_error_messages: bool,
+    /// 描画・入力ポーリングの間隔（ミリ秒、10〜1000）。リモートでは長め、ローカルでは短めが目安
+    #[serde(default = "default_event_poll_ms")]
+    pub event_poll_ms: u64,
+    /// ダブルクリックと判定する2回のクリックの最大間隔（ミリ秒）
+    #[serde(default = "default_double_click_threshold_ms")]
+    pub double_click_threshold_ms: u64,
+    /// 作業中のセッションがあるとき終了前に確認する
+    #[serde(default)]
+    pub confirm_quit_when_working: bool,
+    /// セッションが Success になったらデスクトップ通知とベルで知らせる
+    #[serde(default)]
+    pub notify_on_complete: bool,
+    /// セッションが NeedsInput / Error / Success に遷移したら端末のベルを鳴らす
+    #[serde(default)]
+    pub bell_on_transition: bool,
+    /// セッションが NeedsInput / Error / Success に遷移したらステータスバーを点滅させる
+    #[serde(default)]
+    pub flash_on_transition: bool,
+    /// E で書き出すスナップショットの形式（"markdown" / "json"）
+    #[serde(default)]
+    pub export_format: super::export::ExportFormat,
+    /// スナップショットの書き出し先（未設定時はデータディレクトリの exports）
+    #[serde(default)]
+    pub export_dir: Option<PathBuf>,
     /// Worktree設定
+    #[serde(default)]
     pub worktree: WorktreeConfig,
     /// Log watch設定
     #[serde(default)]
@@ -181,33 +363,145 @@ pub struct Config {
     /// Yazi連携設定
     #[serde(default)]
     pub yazi: YaziConfig,
+    /// 新規ターミナルウィンドウ設定
+    #[serde(default)]
+    pub terminal: TerminalConfig,
+    /// 配色設定
+    #[serde(default)]
+    pub theme: ThemeConfig,
+}
+
+fn default_search_paths() -> Vec<PathBuf> {
+    crate::workspace::get_default_search_paths()
+}
+
+fn default_max_scan_depth() -> usize {
+    3
+}
+
+fn default_socket_path() -> PathBuf {
+    std::env::temp_dir().join("workspace-manager.sock")
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
 }
 
 fn default_use_nerd_font() -> bool {
     true
 }
 
+fn default_error_confirm_polls() -> u32 {
+    crate::app::state::DEFAULT_ERROR_CONFIRM_POLLS
+}
+
+fn default_summary_max_len() -> usize {
+    crate::logwatch::DEFAULT_SUMMARY_MAX_LEN
+}
+
+fn default_task_max_len() -> usize {
+    crate::logwatch::DEFAULT_TASK_MAX_LEN
+}
+
+fn default_detail_pane_percent() -> u16 {
+    crate::app::state::DEFAULT_DETAIL_PANE_PERCENT
+}
+
+fn default_mouse_double_click() -> String {
+    "select".to_string()
+}
+
+fn default_mouse_middle_click() -> String {
+    "close_workspace".to_string()
+}
+
+fn default_on_workspace_select_debounce_ms() -> u64 {
+    300
+}
+
+fn default_status_message_ttl_secs() -> u64 {
+    5
+}
+
+fn default_event_poll_ms() -> u64 {
+    100
+}
+
+fn default_double_click_threshold_ms() -> u64 {
+    300
+}
+
+fn default_activity_days() -> u32 {
+    7
+}
+
+fn default_disconnected_session_retention_secs() -> u64 {
+    60 * 60
+}
+
+fn default_max_sessions() -> usize {
+    500
+}
+
 fn default_editor() -> String {
     "code".to_string()
 }
 
+fn default_terminal_editors() -> Vec<String> {
+    ["vi", "vim", "nvim", "emacs -nw", "nano", "hx", "helix", "micro", "kak"]
+        .iter()
+        .map(|e| e.to_string())
+        .collect()
+}
+
 impl Default for Config {
     fn default() -> Self {
-        let socket_path = std::env::temp_dir().join("workspace-manager.sock");
-
         Self {
-            search_paths: crate::workspace::get_default_search_paths(),
-            max_scan_depth: 3,
-            socket_path,
-            log_level: "info".to_string(),
+            search_paths: default_search_paths(),
+            max_scan_depth: default_max_scan_depth(),
+            socket_path: default_socket_path(),
+            log_level: default_log_level(),
             editor: default_editor(),
+            terminal_editors: default_terminal_editors(),
+            default_open_file: None,
+            diff_command: None,
             use_nerd_font: default_use_nerd_font(),
+            activity_days: default_activity_days(),
+            session_timeout_secs: 0,
+            error_confirm_polls: default_error_confirm_polls(),
+            disconnected_session_retention_secs: default_disconnected_session_retention_secs(),
+            max_sessions: default_max_sessions(),
+            hide_idle_sessions: false,
+            session_sort: SessionSortOrder::default(),
+            path_display: PathDisplay::default(),
+            auto_expand_on_activity: false,
+            auto_add_unknown_workspaces: false,
+            show_remote_host: false,
+            summary_max_len: default_summary_max_len(),
+            task_max_len: default_task_max_len(),
+            detail_pane_percent: default_detail_pane_percent(),
+            mouse_double_click: default_mouse_double_click(),
+            mouse_middle_click: default_mouse_middle_click(),
+            on_workspace_select_command: None,
+            on_workspace_select_debounce_ms: default_on_workspace_select_debounce_ms(),
+            status_message_ttl_secs: default_status_message_ttl_secs(),
+            sticky_error_messages: false,
+            event_poll_ms: default_event_poll_ms(),
+            double_click_threshold_ms: default_double_click_threshold_ms(),
+            confirm_quit_when_working: false,
+            notify_on_complete: false,
+            bell_on_transition: false,
+            flash_on_transition: false,
+            export_format: super::export::ExportFormat::default(),
+            export_dir: None,
             zellij: ZellijConfig::default(),
             multiplexer: None,
             worktree: WorktreeConfig::default(),
             logwatch: LogWatchConfig::default(),
             favorite_repos: Vec::new(),
             yazi: YaziConfig::default(),
+            terminal: TerminalConfig::default(),
+            theme: ThemeConfig::default(),
         }
     }
 }
@@ -217,57 +511,151 @@ impl Default for Config {
 /// ## Architecture
 /// - Claude Code: Event-driven via hooks (no AI analysis, no polling)
 /// - Kiro CLI: SQLite polling (no hooks needed, reads from database)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// - OpenCode: session store polling (opt-in via `tracked_tools`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LogWatchConfig {
     /// Enable log watching and analysis
     #[serde(default = "default_logwatch_enabled")]
     pub enabled: bool,
 
+    /// Match sessions to running AI processes (Claude/Kiro polling).
+    /// Disable to drive session status purely through hooks / `notify` messages.
+    #[serde(default = "default_process_matching_enabled")]
+    pub process_matching_enabled: bool,
+
+    /// AI tools whose sessions are tracked by polling (e.g. `["claude", "kiro"]`).
+    /// Tools not listed get no polling task; hooks / `notify` messages still work.
+    #[serde(default = "default_tracked_tools")]
+    pub tracked_tools: Vec<String>,
+
     // === Claude Code Settings ===
-    /// Enable Claude Code hooks integration
-    #[serde(default = "default_claude_hooks_enabled")]
-    pub claude_hooks_enabled: bool,
-    /// Claude home directory (for log reading if needed)
-    #[serde(default = "default_claude_home")]
-    pub claude_home: PathBuf,
+    /// Claude config directories to scan for sessions (one per `CLAUDE_CONFIG_DIR` profile)
+    #[serde(default = "default_claude_homes")]
+    pub claude_homes: Vec<PathBuf>,
+    /// Process names treated as Claude Code (exact match, e.g. a wrapper or `claude-code`)
+    #[serde(default = "default_claude_process_names")]
+    pub claude_process_names: Vec<String>,
 
     // === Kiro CLI Settings ===
-    /// Enable Kiro CLI SQLite polling
-    #[serde(default = "default_kiro_polling_enabled")]
-    pub kiro_polling_enabled: bool,
     /// Kiro polling interval in seconds
     #[serde(default = "default_kiro_polling_interval")]
     pub kiro_polling_interval_secs: u64,
     /// Path to Kiro CLI SQLite database
     #[serde(default = "default_kiro_db_path")]
     pub kiro_db_path: PathBuf,
+    /// Process names treated as Kiro CLI (exact match)
+    #[serde(default = "default_kiro_process_names")]
+    pub kiro_process_names: Vec<String>,
+    /// Open the Kiro database strictly read-only (`SQLITE_OPEN_READ_ONLY`).
+    /// Off by default: a read-only handle may miss uncheckpointed WAL writes,
+    /// so the database is opened read-write with `PRAGMA query_only` instead.
+    #[serde(default)]
+    pub kiro_db_read_only: bool,
+    /// Maximum Kiro sessions shown per workspace. The process count still decides
+    /// how many conversations are fetched; this only caps it (0 = no cap).
+    #[serde(default = "default_max_kiro_sessions_per_workspace")]
+    pub max_kiro_sessions_per_workspace: usize,
+    /// Show the most recent Kiro conversation of workspaces without a running
+    /// `kiro-cli` process as an idle session
+    #[serde(default)]
+    pub show_idle_kiro_sessions: bool,
+
+    // === OpenCode Settings ===
+    /// OpenCode polling interval in seconds
+    #[serde(default = "default_opencode_polling_interval")]
+    pub opencode_polling_interval_secs: u64,
+    /// OpenCode storage directory (contains `session/` and `message/`)
+    #[serde(default = "default_opencode_storage_dir")]
+    pub opencode_storage_dir: PathBuf,
+    /// Process names treated as OpenCode (exact match)
+    #[serde(default = "default_opencode_process_names")]
+    pub opencode_process_names: Vec<String>,
+
+    // === Codex Settings ===
+    /// Codex polling interval in seconds
+    #[serde(default = "default_codex_polling_interval")]
+    pub codex_polling_interval_secs: u64,
+    /// Codex home directory (contains `sessions/`; `$CODEX_HOME` for Codex itself)
+    #[serde(default = "default_codex_home")]
+    pub codex_home: PathBuf,
+    /// Process names treated as Codex (exact match)
+    #[serde(default = "default_codex_process_names")]
+    pub codex_process_names: Vec<String>,
+
+    // === tmux Capture Settings ===
+    /// Capture tmux windows opened by workspace-manager and derive a status from
+    /// the screen contents (for AI CLIs without a readable log or database)
+    #[serde(default)]
+    pub tmux_capture_enabled: bool,
+    /// Interval between captures (seconds)
+    #[serde(default = "default_tmux_capture_interval")]
+    pub tmux_capture_interval_secs: u64,
 
-    // === Legacy Settings (for backwards compatibility) ===
-    /// CLI tool to use for analysis ("claude" or "kiro") - DEPRECATED
-    #[serde(default = "default_analyzer_tool")]
-    pub analyzer_tool: String,
-    /// Interval between analyses in seconds - DEPRECATED, use kiro_polling_interval_secs
-    #[serde(default = "default_analysis_interval")]
-    pub analysis_interval_secs: u64,
     /// Maximum log lines to analyze
     #[serde(default = "default_max_log_lines")]
     pub max_log_lines: usize,
-    /// Kiro logs directory (optional) - DEPRECATED, use kiro_db_path
-    pub kiro_logs_dir: Option<PathBuf>,
-    /// Use heuristic analysis instead of AI (for testing/offline) - DEPRECATED
-    #[serde(default)]
-    pub use_heuristic: bool,
-    /// Enable periodic polling - DEPRECATED, use kiro_polling_enabled
-    #[serde(default = "default_polling_enabled")]
-    pub polling_enabled: bool,
 }
 
-fn default_claude_hooks_enabled() -> bool {
+/// Deprecated `[logwatch]` keys and the keys that replace them (`None` = dropped).
+/// Migrated by [`migrate_deprecated_fields`] when the config is loaded.
+const DEPRECATED_LOGWATCH_FIELDS: &[(&str, Option<&str>)] = &[
+    ("polling_enabled", Some("kiro_polling_enabled")),
+    ("analysis_interval_secs", Some("kiro_polling_interval_secs")),
+    // ログディレクトリと kiro_db_path（DBファイル）は意味が異なるため値は移さない
+    ("kiro_logs_dir", None),
+    ("analyzer_tool", None),
+    ("use_heuristic", None),
+];
+
+/// Deprecated per-tool `[logwatch]` switches, folded into `tracked_tools` on load.
+/// Runs after [`DEPRECATED_LOGWATCH_FIELDS`], so `polling_enabled` is covered too.
+const DEPRECATED_TOOL_FLAGS: &[(&str, &str)] = &[
+    ("claude_hooks_enabled", "claude"),
+    ("kiro_polling_enabled", "kiro"),
+];
+
+fn default_process_matching_enabled() -> bool {
     true
 }
 
-fn default_kiro_polling_enabled() -> bool {
-    true
+fn default_tracked_tools() -> Vec<String> {
+    vec!["claude".to_string(), "kiro".to_string()]
+}
+
+fn default_claude_process_names() -> Vec<String> {
+    crate::logwatch::process::DEFAULT_CLAUDE_PROCESS_NAMES.iter().map(|n| n.to_string()).collect()
+}
+
+fn default_kiro_process_names() -> Vec<String> {
+    crate::logwatch::process::DEFAULT_KIRO_PROCESS_NAMES.iter().map(|n| n.to_string()).collect()
+}
+
+fn default_opencode_process_names() -> Vec<String> {
+    crate::logwatch::process::DEFAULT_OPENCODE_PROCESS_NAMES.iter().map(|n| n.to_string()).collect()
+}
+
+fn default_opencode_storage_dir() -> PathBuf {
+    crate::logwatch::opencode::default_storage_dir()
+}
+
+fn default_opencode_polling_interval() -> u64 {
+    10
+}
+
+fn default_codex_process_names() -> Vec<String> {
+    crate::logwatch::process::DEFAULT_CODEX_PROCESS_NAMES.iter().map(|n| n.to_string()).collect()
+}
+
+fn default_codex_home() -> PathBuf {
+    crate::logwatch::codex::default_codex_home()
+}
+
+fn default_codex_polling_interval() -> u64 {
+    10
+}
+
+fn default_tmux_capture_interval() -> u64 {
+    5
 }
 
 fn default_kiro_polling_interval() -> u64 {
@@ -281,75 +669,155 @@ fn default_kiro_db_path() -> PathBuf {
         .join("Library/Application Support/kiro-cli/data.sqlite3")
 }
 
-fn default_polling_enabled() -> bool {
-    true
+fn default_max_kiro_sessions_per_workspace() -> usize {
+    crate::logwatch::process::DEFAULT_MAX_SESSIONS_PER_WORKSPACE
 }
 
 fn default_logwatch_enabled() -> bool {
     true // Enabled by default (no API costs with new architecture)
 }
 
-fn default_analyzer_tool() -> String {
-    "claude".to_string()
-}
-
-fn default_analysis_interval() -> u64 {
-    10
-}
-
 fn default_max_log_lines() -> usize {
     500
 }
 
-fn default_claude_home() -> PathBuf {
-    directories::BaseDirs::new()
+/// 先頭の `~` をホームディレクトリに展開
+pub fn expand_tilde(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn default_claude_homes() -> Vec<PathBuf> {
+    vec![directories::BaseDirs::new()
         .map(|d| d.home_dir().to_path_buf())
         .unwrap_or_else(|| PathBuf::from("/tmp"))
-        .join(".claude")
+        .join(".claude")]
 }
 
-impl Default for LogWatchConfig {
-    fn default() -> Self {
-        let claude_home = default_claude_home();
+/// Move deprecated `[logwatch]` values to their replacements.
+///
+/// A deprecated value is only carried over when the replacement key is not set
+/// explicitly. Returns one notice per deprecated key found (empty = nothing to do).
+fn migrate_deprecated_fields(value: &mut toml::Value) -> Vec<String> {
+    let Some(logwatch) = value.get_mut("logwatch").and_then(|v| v.as_table_mut()) else {
+        return Vec::new();
+    };
 
-        // Kiro logs on macOS (legacy)
-        let kiro_logs_dir = if cfg!(target_os = "macos") {
-            directories::BaseDirs::new().map(|d| {
-                d.home_dir().join("Library/Application Support/Kiro/logs/kiroAgent")
-            })
-        } else {
-            None
+    let mut notices = Vec::new();
+    for (old, new) in DEPRECATED_LOGWATCH_FIELDS {
+        let Some(old_value) = logwatch.remove(*old) else {
+            continue;
         };
+        match new {
+            Some(new) if !logwatch.contains_key(*new) => {
+                logwatch.insert(new.to_string(), old_value);
+                notices.push(format!("logwatch.{} is deprecated; read as logwatch.{}", old, new));
+            }
+            Some(new) => {
+                notices.push(format!("logwatch.{} is deprecated; logwatch.{} is already set (ignored)", old, new));
+            }
+            None => {
+                notices.push(format!("logwatch.{} is no longer used (ignored)", old));
+            }
+        }
+    }
+
+    // Per-tool switches: drop disabled tools from the default list, unless tracked_tools is set
+    let explicit = logwatch.contains_key("tracked_tools");
+    let mut tracked = default_tracked_tools();
+    let mut found = false;
+    for (flag, tool) in DEPRECATED_TOOL_FLAGS {
+        let Some(old_value) = logwatch.remove(*flag) else {
+            continue;
+        };
+        found = true;
+        if explicit {
+            notices.push(format!("logwatch.{} is deprecated; logwatch.tracked_tools is already set (ignored)", flag));
+        } else {
+            if old_value.as_bool() == Some(false) {
+                tracked.retain(|name| name != tool);
+            }
+            notices.push(format!("logwatch.{} is deprecated; read as logwatch.tracked_tools", flag));
+        }
+    }
+    if found && !explicit {
+        let tracked = tracked.into_iter().map(toml::Value::String).collect();
+        logwatch.insert("tracked_tools".to_string(), toml::Value::Array(tracked));
+    }
 
+    // claude_home (single path) became the claude_homes list
+    if let Some(old_value) = logwatch.remove("claude_home") {
+        if logwatch.contains_key("claude_homes") {
+            notices.push("logwatch.claude_home is deprecated; logwatch.claude_homes is already set (ignored)".to_string());
+        } else {
+            logwatch.insert("claude_homes".to_string(), toml::Value::Array(vec![old_value]));
+            notices.push("logwatch.claude_home is deprecated; read as logwatch.claude_homes".to_string());
+        }
+    }
+    notices
+}
+
+impl LogWatchConfig {
+    /// Whether `tool` is listed in `tracked_tools` (case-insensitive)
+    pub fn tracks(&self, tool: crate::workspace::AiTool) -> bool {
+        self.tracked_tools.iter().any(|name| name.trim().eq_ignore_ascii_case(tool.name()))
+    }
+}
+
+impl Default for LogWatchConfig {
+    fn default() -> Self {
         Self {
             enabled: default_logwatch_enabled(),
+            process_matching_enabled: default_process_matching_enabled(),
+            tracked_tools: default_tracked_tools(),
             // Claude Code settings
-            claude_hooks_enabled: default_claude_hooks_enabled(),
-            claude_home,
+            claude_homes: default_claude_homes(),
+            claude_process_names: default_claude_process_names(),
             // Kiro CLI settings
-            kiro_polling_enabled: default_kiro_polling_enabled(),
             kiro_polling_interval_secs: default_kiro_polling_interval(),
             kiro_db_path: default_kiro_db_path(),
-            // Legacy settings
-            analyzer_tool: default_analyzer_tool(),
-            analysis_interval_secs: default_analysis_interval(),
+            kiro_process_names: default_kiro_process_names(),
+            kiro_db_read_only: false,
+            max_kiro_sessions_per_workspace: default_max_kiro_sessions_per_workspace(),
+            show_idle_kiro_sessions: false,
+            // OpenCode settings
+            opencode_polling_interval_secs: default_opencode_polling_interval(),
+            opencode_storage_dir: default_opencode_storage_dir(),
+            opencode_process_names: default_opencode_process_names(),
+            codex_polling_interval_secs: default_codex_polling_interval(),
+            codex_home: default_codex_home(),
+            codex_process_names: default_codex_process_names(),
+            // tmux capture settings
+            tmux_capture_enabled: false,
+            tmux_capture_interval_secs: default_tmux_capture_interval(),
             max_log_lines: default_max_log_lines(),
-            kiro_logs_dir,
-            use_heuristic: false,
-            polling_enabled: default_polling_enabled(),
         }
     }
 }
 
 impl Config {
+    /// セッションのテキストの最大文字数（"..." が収まるよう最低 4 文字）
+    pub fn text_limits(&self) -> crate::logwatch::TextLimits {
+        crate::logwatch::TextLimits {
+            summary: self.summary_max_len.max(4),
+            task: self.task_max_len.max(4),
+        }
+    }
+
     /// 設定ファイルから読み込み（存在しない場合はデフォルトを作成して保存）
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
 
         if config_path.exists() {
             let content = std::fs::read_to_string(&config_path)?;
-            let config: Config = toml::from_str(&content)
-                .map_err(|e| anyhow::anyhow!("Failed to parse config: {}", e))?;
+            let (config, notices) = Self::parse(&content)?;
+
+            // 廃止フィールドは読み替えて通知するだけ（保存し直すとコメントが消えるためファイルは書き換えない）
+            for notice in &notices {
+                tracing::warn!("{}", notice);
+            }
             Ok(config)
         } else {
             // 初回起動時はデフォルト設定をファイルに保存
@@ -361,6 +829,17 @@ impl Config {
         }
     }
 
+    /// 設定ファイルの内容をパース（廃止フィールドは読み替え、その通知も返す）
+    pub fn parse(content: &str) -> Result<(Self, Vec<String>)> {
+        let mut value: toml::Value = toml::from_str(content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse config: {}", e))?;
+        let notices = migrate_deprecated_fields(&mut value);
+        let config: Config = value
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("Failed to parse config: {}", e))?;
+        Ok((config, notices))
+    }
+
     /// 設定ファイルパスを取得
     pub fn config_path() -> Result<PathBuf> {
         // ~/.config/workspace-manager/config.toml を使用
@@ -423,6 +902,16 @@ impl Config {
         self.effective_multiplexer_config().generate_tab_name(repo, branch)
     }
 
+    /// ダブルクリックに割り当てたアクション（不明な名前は既定の select）
+    pub fn mouse_double_click_action(&self) -> Action {
+        resolve_mouse_action("mouse_double_click", &self.mouse_double_click, Action::Select)
+    }
+
+    /// ミドルクリックに割り当てたアクション（不明な名前は既定の close_workspace）
+    pub fn mouse_middle_click_action(&self) -> Action {
+        resolve_mouse_action("mouse_middle_click", &self.mouse_middle_click, Action::CloseWorkspace)
+    }
+
     /// 有効なデフォルトレイアウトを取得
     pub fn effective_default_layout(&self) -> Option<PathBuf> {
         self.multiplexer
@@ -448,20 +937,33 @@ impl Config {
     }
 }
 
+fn resolve_mouse_action(key: &str, name: &str, fallback: Action) -> Action {
+    Action::from_name(name).unwrap_or_else(|| {
+        tracing::warn!("Unknown action for {}: {}", key, name);
+        fallback
+    })
+}
+
 /// Zellij連携設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ZellijConfig {
     /// Zellij連携を有効にするか
+    #[serde(default = "default_zellij_enabled")]
     pub enabled: bool,
     /// 対象セッション名（未設定時は選択ダイアログを表示）
+    #[serde(default)]
     pub session_name: Option<String>,
     /// デフォルトレイアウトファイル
+    #[serde(default)]
     pub default_layout: Option<PathBuf>,
     /// レイアウトディレクトリ（選択用）
+    #[serde(default = "default_layout_dir")]
     pub layout_dir: Option<PathBuf>,
     /// タブ名テンプレート（{repo}, {branch} を置換）
+    #[serde(default = "default_zellij_tab_name_template")]
     pub tab_name_template: String,
     /// AIコマンド（claude, kiro-cli, codex など）
+    #[serde(default = "default_zellij_ai_command")]
     pub ai_command: String,
     /// タブ切り替え後に実行するコマンド（ターミナルのタブ移動等）
     /// 例: "osascript -e 'tell application \"System Events\" to keystroke \"]\" using {command down, shift down}'"
@@ -469,25 +971,111 @@ pub struct ZellijConfig {
     pub post_select_command: Option<String>,
 }
 
+fn default_zellij_enabled() -> bool {
+    true
+}
+
+/// workspace-manager のレイアウトディレクトリを使用
+fn default_layout_dir() -> Option<PathBuf> {
+    directories::BaseDirs::new().map(|d| d.home_dir().join(".config/workspace-manager/layouts"))
+}
+
+fn default_zellij_tab_name_template() -> String {
+    "{repo}/{branch}".to_string()
+}
+
+fn default_zellij_ai_command() -> String {
+    "claude".to_string()
+}
+
 impl Default for ZellijConfig {
     fn default() -> Self {
-        // workspace-manager のレイアウトディレクトリを使用
-        let layout_dir = directories::BaseDirs::new().map(|d| d.home_dir().join(".config/workspace-manager/layouts"));
-
         Self {
-            enabled: true,
+            enabled: default_zellij_enabled(),
             session_name: None,
             default_layout: None,
-            layout_dir,
-            tab_name_template: "{repo}/{branch}".to_string(),
-            ai_command: "claude".to_string(),
+            layout_dir: default_layout_dir(),
+            tab_name_template: default_zellij_tab_name_template(),
+            ai_command: default_zellij_ai_command(),
             post_select_command: None,
         }
     }
 }
 
+/// 配色設定
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ThemeConfig {
+    /// 選択行の背景色（"darkgray", "blue", "#334455", 256色インデックスなど）
+    #[serde(default = "default_selection_bg")]
+    pub selection_bg: String,
+    /// 選択行の文字色（未指定なら各要素の色を維持）
+    #[serde(default)]
+    pub selection_fg: Option<String>,
+    /// 選択行の修飾（bold, dim, italic, underlined, reversed）
+    #[serde(default = "default_selection_modifiers")]
+    pub selection_modifiers: Vec<String>,
+    /// フォーカス中ペインの枠線色
+    #[serde(default = "default_focus_border")]
+    pub focus_border: String,
+    /// マウスカーソル下の行の背景色
+    #[serde(default = "default_hover_bg")]
+    pub hover_bg: String,
+    /// ステータスメッセージ（通常）の文字色
+    #[serde(default = "default_message_info")]
+    pub message_info: String,
+    /// ステータスメッセージ（警告）の文字色
+    #[serde(default = "default_message_warn")]
+    pub message_warn: String,
+    /// ステータスメッセージ（エラー）の文字色
+    #[serde(default = "default_message_error")]
+    pub message_error: String,
+}
+
+fn default_selection_bg() -> String {
+    "darkgray".to_string()
+}
+
+fn default_selection_modifiers() -> Vec<String> {
+    vec!["bold".to_string()]
+}
+
+fn default_focus_border() -> String {
+    "cyan".to_string()
+}
+
+fn default_hover_bg() -> String {
+    "#303030".to_string()
+}
+
+fn default_message_info() -> String {
+    "cyan".to_string()
+}
+
+fn default_message_warn() -> String {
+    "yellow".to_string()
+}
+
+fn default_message_error() -> String {
+    "red".to_string()
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            selection_bg: default_selection_bg(),
+            selection_fg: None,
+            selection_modifiers: default_selection_modifiers(),
+            focus_border: default_focus_border(),
+            hover_bg: default_hover_bg(),
+            message_info: default_message_info(),
+            message_warn: default_message_warn(),
+            message_error: default_message_error(),
+        }
+    }
+}
+
 /// Yazi連携設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct YaziConfig {
     /// Yazi連携を有効にするか
     #[serde(default)]
@@ -562,3 +1150,137 @@ impl ZellijConfig {
         Ok(())
     }
 }
+
+/// 新規ターミナルウィンドウ設定（マルチプレクサを使わない運用向け）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TerminalConfig {
+    /// 起動コマンドテンプレート（`{path}` / `{command}` を置換して `sh -c` で実行）
+    /// 未設定時は `$TERMINAL`、なければ OS の既定ターミナルを使用
+    /// 例: "kitty --directory {path} {command}"
+    #[serde(default)]
+    pub command: Option<String>,
+    /// 新しいウィンドウで AI コマンドを起動するか
+    #[serde(default)]
+    pub run_ai_command: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.max_scan_depth, 3);
+        assert_eq!(config.log_level, "info");
+        assert_eq!(config.worktree.default_remote, "origin");
+        assert_eq!(config.zellij.tab_name_template, "{repo}/{branch}");
+        assert!(config.zellij.enabled);
+    }
+
+    #[test]
+    fn test_migrate_deprecated_logwatch_fields() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+            [logwatch]
+            polling_enabled = false
+            analysis_interval_secs = 30
+            kiro_polling_interval_secs = 5
+            analyzer_tool = "kiro"
+            use_heuristic = true
+            "#,
+        )
+        .unwrap();
+
+        let notices = migrate_deprecated_fields(&mut value);
+        // polling_enabled → kiro_polling_enabled → tracked_tools の2段で移行される
+        assert_eq!(notices.len(), 5);
+
+        let config: Config = value.try_into().unwrap();
+        assert_eq!(config.logwatch.tracked_tools, vec!["claude".to_string()]);
+        // 新フィールドが明示されていればそちらを優先
+        assert_eq!(config.logwatch.kiro_polling_interval_secs, 5);
+    }
+
+    #[test]
+    fn test_parse_migrates_and_reports_errors() {
+        let (config, notices) = Config::parse("[logwatch]\npolling_enabled = false\n").unwrap();
+        assert!(!notices.is_empty());
+        assert!(!config.logwatch.tracks(crate::workspace::AiTool::Kiro));
+        assert!(Config::parse("search_paths = ").is_err());
+    }
+
+    #[test]
+    fn test_migrate_tool_flags_respects_explicit_tracked_tools() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+            [logwatch]
+            claude_hooks_enabled = false
+            tracked_tools = ["claude", "Kiro"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(migrate_deprecated_fields(&mut value).len(), 1);
+        let config: Config = value.try_into().unwrap();
+        assert!(config.logwatch.tracks(crate::workspace::AiTool::Claude));
+        assert!(config.logwatch.tracks(crate::workspace::AiTool::Kiro));
+        assert!(!config.logwatch.tracks(crate::workspace::AiTool::Codex));
+    }
+
+    #[test]
+    fn test_migrate_claude_home_to_list() {
+        let mut value: toml::Value =
+            toml::from_str("[logwatch]\nclaude_home = \"/profiles/work/.claude\"\n").unwrap();
+
+        assert_eq!(migrate_deprecated_fields(&mut value).len(), 1);
+        let config: Config = value.try_into().unwrap();
+        assert_eq!(config.logwatch.claude_homes, vec![PathBuf::from("/profiles/work/.claude")]);
+    }
+
+    #[test]
+    fn test_migrate_without_deprecated_fields_is_noop() {
+        let mut value: toml::Value = toml::from_str("[logwatch]\nenabled = true\n").unwrap();
+        assert!(migrate_deprecated_fields(&mut value).is_empty());
+    }
+
+    #[test]
+    fn test_partial_config_keeps_other_defaults() {
+        let config: Config = toml::from_str(
+            r#"
+            editor = "nvim"
+
+            [worktree]
+            path_style = "Subdirectory"
+
+            [zellij]
+            session_name = "main"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.editor, "nvim");
+        assert!(matches!(config.worktree.path_style, WorktreePathStyle::Subdirectory));
+        assert_eq!(config.worktree.default_remote, "origin");
+        assert_eq!(config.worktree.max_remote_branches, 100);
+        assert_eq!(config.zellij.session_name.as_deref(), Some("main"));
+        assert_eq!(config.zellij.ai_command, "claude");
+    }
+
+    #[test]
+    fn test_mouse_click_actions() {
+        let config = Config::default();
+        assert_eq!(config.mouse_double_click_action(), Action::Select);
+        assert_eq!(config.mouse_middle_click_action(), Action::CloseWorkspace);
+        assert_eq!(config.double_click_threshold_ms, 300);
+
+        let config: Config = toml::from_str(
+            r#"
+            mouse_double_click = "open_in_editor"
+            mouse_middle_click = "no_such_action"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.mouse_double_click_action(), Action::OpenInEditor);
+        assert_eq!(config.mouse_middle_click_action(), Action::CloseWorkspace);
+    }
+}
@@ -29,6 +29,10 @@ pub enum AppEvent {
     SessionUnregister {
         external_id: String,
     },
+    /// セッションの生存通知（ステータスは変えない）
+    SessionHeartbeat {
+        external_id: String,
+    },
     /// AI解析によるセッションステータス更新
     SessionStatusAnalyzed {
         external_id: String,
@@ -39,6 +43,22 @@ pub enum AppEvent {
     TabFocusChanged {
         tab_name: String,
     },
+    /// Kiro データベースが繰り返し読めない
+    KiroDbError {
+        message: String,
+    },
+    /// worktree作成後フック（post_create_command）の完了
+    PostCreateHookFinished {
+        path: String,
+        result: Result<(), String>,
+    },
+    /// バックグラウンドの git fetch の完了
+    FetchFinished {
+        repo_path: String,
+        result: Result<(), String>,
+    },
+    /// バックグラウンドで数えた直近のコミット数（パス, 件数）
+    RecentCommitsCounted(Vec<(String, usize)>),
     /// リフレッシュ要求
     Refresh,
     /// 終了要求
@@ -66,6 +86,10 @@ pub enum Action {
     ToggleDisplayMode,
     /// リフレッシュ
     Refresh,
+    /// 選択中のワークスペースのステータスだけを即時再取得
+    RefreshSelected,
+    /// 設定ファイルを読み直す（再起動不要）
+    ReloadConfig,
     /// 終了
     Quit,
     /// Zellij: lazygit起動
@@ -76,6 +100,10 @@ pub enum Action {
     LaunchYazi,
     /// Zellij: 新規Claude Codeセッション
     NewSession,
+    /// 停止したセッションのツールを再起動
+    RelaunchSession,
+    /// ワークスペースとセッションの状態をファイルに書き出す
+    ExportSnapshot,
     /// Zellij: ワークスペース終了（Internal→ペイン閉じる、External→タブ閉じる）
     CloseWorkspace,
     /// 現在のタブにペインを追加（External mode用）
@@ -88,6 +116,22 @@ pub enum Action {
     ForceDeleteWorktree,
     /// エディタで開く
     OpenInEditor,
+    /// セッションが最後に触ったファイル、なければ README などをエディタで開く
+    OpenFile,
+    /// 新しいターミナルウィンドウで開く
+    OpenInTerminal,
+    /// リモートのブランチページをブラウザで開く
+    OpenInBrowser,
+    /// gh / glab で PR（MR）を作成
+    CreatePullRequest,
+    /// デフォルトブランチとの差分を表示
+    ShowDiff,
+    /// アプリケーションのログファイルを開く
+    OpenLogFile,
+    /// ログビューア表示切替
+    ToggleLogView,
+    /// ステータスメッセージ履歴を表示
+    ShowMessages,
     /// マウスクリックで行選択
     MouseSelect(u16),
     /// マウススクロール上
@@ -98,20 +142,52 @@ pub enum Action {
     MouseDoubleClick(u16),
     /// マウスミドルクリックで行選択＋ワークスペース閉じる
     MouseMiddleClick(u16),
+    /// マウスカーソル下の行（ホバー表示、リスト外なら None）
+    MouseHover(Option<u16>),
+    /// マウス右クリックで行選択＋コンテキストメニュー
+    MouseRightClick(u16),
+    /// 選択中の項目のコンテキストメニューを開く
+    ContextMenu,
+    /// レイアウトを選んで開く
+    OpenWithLayout,
+    /// パスをクリップボードにコピー
+    CopyPath,
+    /// リモートから fetch
+    FetchRepo,
     /// 展開（右キー）
     Expand,
     /// 折りたたみ（左キー）
     Collapse,
     /// ブランチフィルター
     FilterBranches,
+    /// ツリー全体のフィルター
+    FilterTree,
     /// フィルタークリア
     ClearFilter,
     /// お気に入りトグル
     ToggleFavorite,
     /// お気に入り以外を折りたたむ/展開する
     CollapseNonFavorites,
+    /// すべてのリポジトリグループを折りたたむ
+    CollapseAll,
+    /// すべてのリポジトリグループを展開する
+    ExpandAll,
+    /// 2打鍵コマンドの1打目（`z` など、次のキーを待つ）
+    KeyPrefix(char),
+    /// 名前がこの文字で始まる次のリポジトリグループへ移動
+    JumpToRepo(char),
     /// Git logペイン表示切替
     ToggleGitLog,
+    /// Idle セッションの表示切替
+    ToggleIdleSessions,
+    /// 選択中のセッションを確認済みにする（次の状態変化まで注意表示をやめる）
+    AcknowledgeSession,
+    /// 表示するセッションのツールを切り替え
+    FilterByTool,
+    /// 詳細サイドパネル表示切替
+    ToggleDetailPane,
+    /// 詳細サイドパネルの幅を切り替え
+    CycleDetailPane,
     /// Git logを上スクロール
     GitLogScrollUp,
     /// Git logを下スクロール
@@ -120,6 +196,61 @@ pub enum Action {
     None,
 }
 
+impl Action {
+    /// 設定ファイルで使うアクション名（snake_case）から変換
+    ///
+    /// 行番号などの引数を持つマウス用アクションは対象外。
+    pub fn from_name(name: &str) -> Option<Self> {
+        let action = match name.trim() {
+            "move_up" => Action::MoveUp,
+            "move_down" => Action::MoveDown,
+            "select" => Action::Select,
+            "toggle_expand" => Action::ToggleExpand,
+            "expand" => Action::Expand,
+            "collapse" => Action::Collapse,
+            "toggle_help" => Action::ToggleHelp,
+            "toggle_display_mode" => Action::ToggleDisplayMode,
+            "refresh" => Action::Refresh,
+            "refresh_selected" => Action::RefreshSelected,
+            "reload_config" => Action::ReloadConfig,
+            "launch_lazygit" => Action::LaunchLazygit,
+            "launch_shell" => Action::LaunchShell,
+            "launch_yazi" => Action::LaunchYazi,
+            "new_session" => Action::NewSession,
+            "relaunch_session" => Action::RelaunchSession,
+            "export_snapshot" => Action::ExportSnapshot,
+            "close_workspace" => Action::CloseWorkspace,
+            "add_pane" => Action::AddPane,
+            "create_worktree" => Action::CreateWorktree,
+            "delete_worktree" => Action::DeleteWorktree,
+            "force_delete_worktree" => Action::ForceDeleteWorktree,
+            "open_in_editor" => Action::OpenInEditor,
+            "open_file" => Action::OpenFile,
+            "open_in_terminal" => Action::OpenInTerminal,
+            "open_in_browser" => Action::OpenInBrowser,
+            "create_pull_request" => Action::CreatePullRequest,
+            "show_diff" => Action::ShowDiff,
+            "open_with_layout" => Action::OpenWithLayout,
+            "copy_path" => Action::CopyPath,
+            "fetch" => Action::FetchRepo,
+            "collapse_all" => Action::CollapseAll,
+            "expand_all" => Action::ExpandAll,
+            "toggle_favorite" => Action::ToggleFavorite,
+            "toggle_git_log" => Action::ToggleGitLog,
+            "toggle_idle_sessions" => Action::ToggleIdleSessions,
+            "acknowledge_session" => Action::AcknowledgeSession,
+            "filter_by_tool" => Action::FilterByTool,
+            "filter_tree" => Action::FilterTree,
+            "show_messages" => Action::ShowMessages,
+            "toggle_detail_pane" => Action::ToggleDetailPane,
+            "context_menu" => Action::ContextMenu,
+            "none" => Action::None,
+            _ => return None,
+        };
+        Some(action)
+    }
+}
+
 impl From<KeyEvent> for Action {
     fn from(key: KeyEvent) -> Self {
         match (key.code, key.modifiers) {
@@ -139,8 +270,10 @@ impl From<KeyEvent> for Action {
             (KeyCode::Char('?'), _) => Action::ToggleHelp,
             // 表示モード切り替え
             (KeyCode::Char('v'), _) => Action::ToggleDisplayMode,
-            // リフレッシュ
+            // リフレッシュ（Ctrl+r は設定の再読み込み）
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => Action::ReloadConfig,
             (KeyCode::Char('r'), _) => Action::Refresh,
+            (KeyCode::Char('R'), _) => Action::RefreshSelected,
             // 閉じる/戻る
             (KeyCode::Esc, _) => Action::Back,
             // 終了
@@ -155,26 +288,66 @@ impl From<KeyEvent> for Action {
             (KeyCode::Char('D'), _) => Action::ForceDeleteWorktree,
             // エディタで開く
             (KeyCode::Char('e'), _) => Action::OpenInEditor,
+            (KeyCode::Char('w'), _) => Action::OpenFile,
+            // 新しいターミナルウィンドウで開く
+            (KeyCode::Char('t'), _) => Action::OpenInTerminal,
+            // リモートのブランチページをブラウザで開く
+            (KeyCode::Char('b'), _) => Action::OpenInBrowser,
+            // PR / MR 作成
+            (KeyCode::Char('P'), _) => Action::CreatePullRequest,
+            // デフォルトブランチとの差分
+            (KeyCode::Char('V'), _) => Action::ShowDiff,
+            // ログファイルを開く
+            (KeyCode::Char('O'), _) => Action::OpenLogFile,
+            (KeyCode::Char('o'), _) => Action::ToggleLogView,
+            (KeyCode::Char('M'), _) => Action::ShowMessages,
             // Zellijアクション
             (KeyCode::Char('l'), _) => Action::LaunchLazygit,
             (KeyCode::Char('g'), _) => Action::LaunchShell,
             (KeyCode::Char('y'), _) => Action::LaunchYazi,
             (KeyCode::Char('n'), _) => Action::NewSession,
+            (KeyCode::Char('N'), _) => Action::RelaunchSession,
+            (KeyCode::Char('E'), _) => Action::ExportSnapshot,
             (KeyCode::Char('p'), _) => Action::AddPane,
             (KeyCode::Char('x'), _) => Action::CloseWorkspace,
             // ブランチフィルター
             (KeyCode::Char('/'), _) => Action::FilterBranches,
+            // ツリー全体のフィルター
+            (KeyCode::Char('s'), _) => Action::FilterTree,
+            // 折りたたみ（zM / zR）
+            (KeyCode::Char('z'), _) => Action::KeyPrefix('z'),
+            // 頭文字でリポジトリへ移動（'<文字>）
+            (KeyCode::Char('\''), _) => Action::KeyPrefix('\''),
             // お気に入り
             (KeyCode::Char('f'), _) => Action::ToggleFavorite,
             (KeyCode::Char('F'), _) => Action::CollapseNonFavorites,
             // Git log
             (KeyCode::Char('L'), _) => Action::ToggleGitLog,
+            // Idle セッションの表示切替
+            (KeyCode::Char('h'), _) => Action::ToggleIdleSessions,
+            // セッションの確認済み切り替え
+            (KeyCode::Char('A'), _) => Action::AcknowledgeSession,
+            // ツールフィルター
+            (KeyCode::Char('T'), _) => Action::FilterByTool,
+            // 詳細サイドパネル
+            (KeyCode::Char('i'), _) => Action::ToggleDetailPane,
+            (KeyCode::Char('I'), _) => Action::CycleDetailPane,
             // その他
             _ => Action::None,
         }
     }
 }
 
+/// 2打鍵コマンドの2打目をアクションに変換（対応しないキーは何もしない）
+pub fn chord_action(prefix: char, key: KeyEvent) -> Action {
+    match (prefix, key.code) {
+        ('z', KeyCode::Char('M')) => Action::CollapseAll,
+        ('z', KeyCode::Char('R')) => Action::ExpandAll,
+        ('\'', KeyCode::Char(c)) if c.is_alphanumeric() => Action::JumpToRepo(c),
+        _ => Action::None,
+    }
+}
+
 /// マウスイベントからアクションへの変換
 /// list_area_y: リスト領域の開始Y座標
 /// header_height: ボーダー(1) + ヘッダー行(1) = 2
@@ -200,6 +373,20 @@ pub fn mouse_action(event: MouseEvent, list_area_y: u16, header_height: u16) ->
                 Action::None
             }
         }
+        MouseEventKind::Down(crossterm::event::MouseButton::Right) => {
+            // 右クリックでコンテキストメニュー
+            let data_start = list_area_y + header_height;
+            if event.row >= data_start {
+                let row_index = event.row - data_start;
+                Action::MouseRightClick(row_index)
+            } else {
+                Action::None
+            }
+        }
+        MouseEventKind::Moved => {
+            let data_start = list_area_y + header_height;
+            Action::MouseHover(event.row.checked_sub(data_start))
+        }
         MouseEventKind::ScrollUp => Action::ScrollUp,
         MouseEventKind::ScrollDown => Action::ScrollDown,
         _ => Action::None,
@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::config::expand_tilde;
+use super::state::AppState;
+use crate::workspace::{Session, Workspace};
+
+/// スナップショットの出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// スタンドアップなどに貼れる読みやすいレポート
+    #[default]
+    Markdown,
+    /// Workspace / Session をそのままシリアライズ
+    Json,
+}
+
+impl ExportFormat {
+    /// 出力ファイルの拡張子
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// JSON 出力の1ワークスペース分
+#[derive(Serialize)]
+struct WorkspaceSnapshot<'a> {
+    #[serde(flatten)]
+    workspace: &'a Workspace,
+    sessions: Vec<&'a Session>,
+}
+
+/// JSON 出力全体
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    exported_at: String,
+    workspaces: Vec<WorkspaceSnapshot<'a>>,
+}
+
+/// 現在のワークスペースとセッションを指定形式の文字列にする
+pub fn render_snapshot(state: &AppState, format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Json => {
+            let snapshot = Snapshot {
+                exported_at: chrono::Local::now().to_rfc3339(),
+                workspaces: state
+                    .workspaces
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, workspace)| WorkspaceSnapshot {
+                        workspace,
+                        sessions: state.all_sessions_for_workspace(idx),
+                    })
+                    .collect(),
+            };
+            Ok(serde_json::to_string_pretty(&snapshot)?)
+        }
+        ExportFormat::Markdown => Ok(render_markdown(state)),
+    }
+}
+
+/// スナップショットを `dir` に書き出し、作成したファイルのパスを返す
+pub fn write_snapshot(state: &AppState, format: ExportFormat, dir: &Path) -> Result<PathBuf> {
+    let content = render_snapshot(state, format)?;
+    std::fs::create_dir_all(dir)?;
+    let name = format!(
+        "snapshot-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    );
+    let path = dir.join(name);
+    std::fs::write(&path, content)?;
+    Ok(path)
+}
+
+/// 書き出し先ディレクトリ（先頭の ~ はホームに展開、未設定時はデータディレクトリ配下の exports）
+pub fn export_dir(configured: Option<&Path>) -> PathBuf {
+    match configured {
+        Some(dir) => expand_tilde(dir),
+        None => directories::ProjectDirs::from("", "", "workspace-manager")
+            .map(|d| d.data_dir().to_path_buf())
+            .unwrap_or_else(|| std::env::temp_dir().join("workspace-manager"))
+            .join("exports"),
+    }
+}
+
+/// リポジトリごとに見出しを立て、ワークスペースとセッションを箇条書きにする
+fn render_markdown(state: &AppState) -> String {
+    let mut repos: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, ws) in state.workspaces.iter().enumerate() {
+        repos.entry(state.get_repo_key(ws)).or_default().push(idx);
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# Current work ({})", chrono::Local::now().format("%Y-%m-%d %H:%M"));
+    for (repo, indices) in &repos {
+        let _ = writeln!(out, "\n## {}\n", repo);
+        for &idx in indices {
+            let ws = &state.workspaces[idx];
+            let _ = writeln!(out, "- **{}** (`{}`)", ws.branch, state.display_path(&ws.project_path));
+            for session in state.all_sessions_for_workspace(idx) {
+                let _ = write!(out, "  - {}: {}", session.tool.name(), session.status);
+                if let Some(ref summary) = session.summary {
+                    let _ = write!(out, " — {}", summary);
+                }
+                if let Some(time) = session.time_since_activity() {
+                    let _ = write!(out, " ({})", time);
+                }
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{AiTool, SessionStatus};
+
+    fn sample_state() -> AppState {
+        let mut state = AppState::new();
+        state.workspaces = vec![Workspace::new(
+            "/nonexistent/app__feature".to_string(),
+            "app__feature".to_string(),
+            "feature".to_string(),
+        )];
+        let mut session = Session::new("claude:s1".to_string(), 0, AiTool::Claude);
+        session.update_status(SessionStatus::Working, Some("Fixing tests".to_string()));
+        state.add_session(session);
+        state
+    }
+
+    #[test]
+    fn test_markdown_groups_by_repo() {
+        let report = render_snapshot(&sample_state(), ExportFormat::Markdown).unwrap();
+        assert!(report.contains("## app\n"));
+        assert!(report.contains("- **feature**"));
+        assert!(report.contains("  - Claude: working — Fixing tests"));
+    }
+
+    #[test]
+    fn test_json_nests_sessions_under_workspace() {
+        let json = render_snapshot(&sample_state(), ExportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let workspace = &value["workspaces"][0];
+        assert_eq!(workspace["branch"], "feature");
+        assert_eq!(workspace["sessions"][0]["status"], "working");
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// 直近のログ行を保持する共有リングバッファ
+pub type LogBuffer = Arc<Mutex<VecDeque<String>>>;
+
+/// リングバッファの既定容量（行数）
+pub const DEFAULT_LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// tracing イベントを整形してリングバッファに積む Layer
+///
+/// 容量を超えた場合は古い行から捨てる。ログビューアはファイルを読み直さずにここを参照する。
+pub struct RingBufferLayer {
+    buffer: LogBuffer,
+    capacity: usize,
+}
+
+impl RingBufferLayer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// TUI から参照するためのバッファハンドル
+    pub fn buffer(&self) -> LogBuffer {
+        Arc::clone(&self.buffer)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for RingBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+
+        let meta = event.metadata();
+        // fmt レイヤーと同じ "<timestamp> <LEVEL> <target>: <message>" 形式
+        let line = format!(
+            "{} {:>5} {}: {}{}",
+            chrono::Local::now().format("%H:%M:%S%.3f"),
+            meta.level(),
+            meta.target(),
+            visitor.message,
+            visitor.fields
+        );
+
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return;
+        };
+        while buffer.len() >= self.capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(line);
+    }
+}
+
+/// イベントの message と追加フィールドを文字列化する
+#[derive(Default)]
+struct LineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for LineVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_ring_buffer_layer_caps_and_formats() {
+        let layer = RingBufferLayer::new(2);
+        let buffer = layer.buffer();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("first");
+            tracing::warn!(count = 3, "second");
+            tracing::error!("third");
+        });
+
+        let lines: Vec<String> = buffer.lock().unwrap().iter().cloned().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(" WARN ") && lines[0].ends_with("second count=3"));
+        assert!(lines[1].contains("ERROR") && lines[1].ends_with("third"));
+    }
+}
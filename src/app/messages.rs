@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 保持するステータスメッセージの件数
+const MESSAGE_HISTORY_CAPACITY: usize = 100;
+
+/// ステータスメッセージの重要度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl MessageLevel {
+    /// 履歴ビューでのラベル
+    pub fn label(&self) -> &'static str {
+        match self {
+            MessageLevel::Info => "INFO",
+            MessageLevel::Warn => "WARN",
+            MessageLevel::Error => "ERROR",
+        }
+    }
+}
+
+/// 時刻付きのステータスメッセージ
+#[derive(Debug, Clone)]
+pub struct StatusMessage {
+    pub text: String,
+    pub level: MessageLevel,
+    /// 発生時刻（表示期限の判定用）
+    pub at: Instant,
+    /// 発生時刻（履歴ビューの表示用）
+    pub time: chrono::DateTime<chrono::Local>,
+}
+
+/// ステータスメッセージの履歴（古いものから捨てるリングバッファ）
+#[derive(Debug, Default)]
+pub struct MessageLog {
+    messages: VecDeque<StatusMessage>,
+}
+
+impl MessageLog {
+    /// メッセージを追加
+    pub fn push(&mut self, text: String, level: MessageLevel) {
+        if self.messages.len() >= MESSAGE_HISTORY_CAPACITY {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(StatusMessage {
+            text,
+            level,
+            at: Instant::now(),
+            time: chrono::Local::now(),
+        });
+    }
+
+    /// ステータスバーに出す最新のメッセージ
+    ///
+    /// `ttl` を過ぎたものは出さない（None なら期限なし）。`sticky_errors` のときエラーは期限なし。
+    pub fn current(&self, ttl: Option<Duration>, sticky_errors: bool) -> Option<&StatusMessage> {
+        let latest = self.messages.back()?;
+        let expired = ttl.is_some_and(|ttl| latest.at.elapsed() >= ttl);
+        let sticky = sticky_errors && latest.level == MessageLevel::Error;
+        (!expired || sticky).then_some(latest)
+    }
+
+    /// 新しい順のメッセージ
+    pub fn newest_first(&self) -> impl Iterator<Item = &StatusMessage> {
+        self.messages.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_respects_ttl_and_sticky_errors() {
+        let mut log = MessageLog::default();
+        assert!(log.current(None, false).is_none());
+
+        log.push("Created tab: app".to_string(), MessageLevel::Info);
+        assert_eq!(log.current(None, false).unwrap().text, "Created tab: app");
+        assert!(log.current(Some(Duration::ZERO), false).is_none());
+
+        log.push("Failed to fetch".to_string(), MessageLevel::Error);
+        assert!(log.current(Some(Duration::ZERO), false).is_none());
+        assert!(log.current(Some(Duration::ZERO), true).is_some());
+        // 期限切れでも履歴には残る
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.newest_first().next().unwrap().text, "Failed to fetch");
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut log = MessageLog::default();
+        for i in 0..MESSAGE_HISTORY_CAPACITY + 5 {
+            log.push(i.to_string(), MessageLevel::Info);
+        }
+        assert_eq!(log.len(), MESSAGE_HISTORY_CAPACITY);
+        assert_eq!(log.newest_first().last().unwrap().text, "5");
+    }
+}
@@ -1,8 +1,16 @@
 pub mod config;
 pub mod events;
+pub mod export;
+pub mod log_buffer;
+pub mod messages;
+pub mod persisted;
 pub mod state;
+pub mod terminal;
 
-pub use config::{Config, LogWatchConfig, WorktreeConfig, WorktreePathStyle, ZellijConfig};
+pub use config::{
+    Config, LogWatchConfig, TerminalConfig, WorktreeConfig, WorktreePathStyle, ZellijConfig,
+};
 // MultiplexerConfig は crate::multiplexer から直接参照
-pub use events::{Action, AppEvent, mouse_action, poll_event};
+pub use events::{Action, AppEvent, chord_action, mouse_action, poll_event};
+pub use persisted::PersistedState;
 pub use state::{AppState, CommitDetail, FocusedPane, GitLogEntry, ListDisplayMode, TreeItem, ViewMode};
@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// 再起動をまたいで保持する表示状態（終了時に保存し、起動時に復元）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistedState {
+    /// 折りたたまれたリポジトリの repo_key
+    #[serde(default)]
+    pub collapsed_repos: Vec<String>,
+    /// リモートブランチを展開しているリポジトリの repo_key
+    #[serde(default)]
+    pub expanded_remote_branches: Vec<String>,
+}
+
+impl PersistedState {
+    /// 保存先（データディレクトリのログと同じ場所の state.json）
+    pub fn default_path() -> PathBuf {
+        directories::ProjectDirs::from("", "", "workspace-manager")
+            .map(|d| d.data_dir().to_path_buf())
+            .unwrap_or_else(|| std::env::temp_dir().join("workspace-manager"))
+            .join("state.json")
+    }
+
+    /// 読み込む（ファイルがない・壊れている場合は空の状態）
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable state file {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    /// 書き出す（親ディレクトリがなければ作る）
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        // 親ディレクトリがなくても保存できる
+        let path = dir.path().join("state").join("state.json");
+        assert_eq!(PersistedState::load(&path), PersistedState::default());
+
+        let state = PersistedState {
+            collapsed_repos: vec!["app".to_string()],
+            expanded_remote_branches: vec!["lib".to_string()],
+        };
+        state.save(&path).unwrap();
+        assert_eq!(PersistedState::load(&path), state);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(PersistedState::load(&path), PersistedState::default());
+    }
+}
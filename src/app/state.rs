@@ -1,12 +1,21 @@
 use crate::workspace::{
-    AiTool, Pane, Session, SessionStatus, Workspace, WorktreeManager, get_default_search_paths,
-    scan_for_repositories,
+    AiTool, BranchStats, Pane, Session, SessionId, SessionStatus, Workspace, WorktreeManager, abbreviate_home,
+    get_default_search_paths, scan_for_repositories, session_key,
 };
 use ratatui::widgets::TableState;
+use chrono::{DateTime, Utc};
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
-use std::time::Instant;
-
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::app::config::{PathDisplay, SessionSortOrder};
+use crate::app::persisted::PersistedState;
+use crate::app::messages::{MessageLevel, MessageLog, StatusMessage};
+use crate::app::Action;
+use crate::logwatch::{truncate_text, TextLimits};
+use crate::ui::theme::Theme;
 use crate::ui::{InputDialog, SelectionContext, SelectionDialog, SelectionDialogKind};
 
 /// アプリケーションの表示モード
@@ -20,6 +29,10 @@ pub enum ViewMode {
     Input,
     /// 選択ダイアログ表示中
     Selection,
+    /// ログビューア表示中
+    Logs,
+    /// ステータスメッセージ履歴表示中
+    Messages,
 }
 
 /// リスト表示モード（ブランチ表示の有無）
@@ -62,6 +75,97 @@ pub enum FocusedPane {
     GitLog,
 }
 
+/// ポーリングの最終実行時刻（バックグラウンドのポーリングタスクと共有）
+#[derive(Debug, Clone, Default)]
+pub struct PollTimestamp(Arc<AtomicU64>);
+
+impl PollTimestamp {
+    /// 現在時刻をポーリング実行時刻として記録
+    pub fn mark(&self) {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.0.store(millis, Ordering::Relaxed);
+    }
+
+    /// 最終ポーリングからの経過時間（未実行なら None）
+    pub fn elapsed(&self) -> Option<Duration> {
+        let millis = self.0.load(Ordering::Relaxed);
+        if millis == 0 {
+            return None;
+        }
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH + Duration::from_millis(millis))
+            .ok()
+            .or(Some(Duration::ZERO))
+    }
+}
+
+/// 詳細サイドパネルの既定幅（%）
+pub const DEFAULT_DETAIL_PANE_PERCENT: u16 = 33;
+
+/// ポーリングの Error を表示するまでに必要な連続回数の既定値
+pub const DEFAULT_ERROR_CONFIRM_POLLS: u32 = 2;
+
+/// 詳細サイドパネル幅の切り替え候補（%、昇順）
+const DETAIL_PANE_PRESETS: [u16; 2] = [30, 50];
+
+/// 同じセッションの完了通知を繰り返さない間隔
+const COMPLETION_NOTIFY_DEBOUNCE: Duration = Duration::from_secs(60);
+
+/// ステータス遷移時にステータスバーを点滅させる時間
+pub const STATUS_FLASH_DURATION: Duration = Duration::from_millis(600);
+
+/// ログビューアのレベルフィルター（指定レベル以上を表示）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogLevelFilter {
+    #[default]
+    All,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevelFilter {
+    /// 次のフィルターへ切り替え
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Info,
+            Self::Info => Self::Warn,
+            Self::Warn => Self::Error,
+            Self::Error => Self::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "ALL",
+            Self::Info => "INFO+",
+            Self::Warn => "WARN+",
+            Self::Error => "ERROR",
+        }
+    }
+
+    /// ログ行がフィルターを通過するか
+    pub fn matches(self, line: &str) -> bool {
+        let allowed: &[&str] = match self {
+            Self::All => return true,
+            Self::Info => &["INFO", "WARN", "ERROR"],
+            Self::Warn => &["WARN", "ERROR"],
+            Self::Error => &["ERROR"],
+        };
+        log_line_level(line).is_some_and(|level| allowed.contains(&level))
+    }
+}
+
+/// tracing fmt 形式のログ行からレベルを抽出（"<timestamp>  INFO target: msg"）
+pub fn log_line_level(line: &str) -> Option<&str> {
+    line.split_whitespace()
+        .take(2)
+        .find(|t| matches!(*t, "TRACE" | "DEBUG" | "INFO" | "WARN" | "ERROR"))
+}
+
 /// Yaziに送信するコマンド
 #[derive(Debug, Clone)]
 pub enum YaziCommand {
@@ -80,6 +184,8 @@ pub enum TreeItem {
         path: String,
         expanded: bool,
         worktree_count: usize,
+        /// origin のホスト名（グループ内の最初に見つかったもの）
+        host: Option<String>,
     },
     /// ワークスペース（worktree）
     Worktree {
@@ -88,7 +194,7 @@ pub enum TreeItem {
     },
     /// セッション（AI CLI）
     Session {
-        session_index: usize,
+        session_id: SessionId,
         is_last: bool,
         parent_is_last: bool,
     },
@@ -104,6 +210,14 @@ pub enum TreeItem {
         is_local: bool,
         repo_path: String,
         is_last: bool,
+        /// デフォルトブランチより先行しているコミット数
+        ahead: Option<usize>,
+        /// デフォルトブランチより遅れているコミット数
+        behind: Option<usize>,
+        /// 最終コミット日時
+        last_commit: Option<DateTime<Utc>>,
+        /// 既にチェックアウトしている worktree（スキャン対象外の場所にあるもの）
+        worktree_path: Option<String>,
     },
     /// リモートブランチグループ（折りたたみ可能）
     RemoteBranchGroup {
@@ -120,18 +234,22 @@ pub enum TreeItem {
 pub struct AppState {
     /// 検出されたワークスペース一覧
     pub workspaces: Vec<Workspace>,
-    /// アクティブなセッション一覧
-    pub sessions: Vec<Session>,
+    /// セッション一覧（位置ではなく SessionId で引くので、削除しても他の参照は壊れない）
+    pub sessions: HashMap<SessionId, Session>,
     /// ツリー表示用のフラット化されたリスト
     pub tree_items: Vec<TreeItem>,
     /// 折りたたまれたリポジトリのパス
     collapsed_repos: HashSet<String>,
     /// 折りたたまれたリモートブランチグループのリポパス
     expanded_remote_branches: HashSet<String>,
-    /// external_id -> session index のマッピング
-    session_map: HashMap<String, usize>,
-    /// workspace_index -> session indices のマッピング
-    sessions_by_workspace: HashMap<usize, Vec<usize>>,
+    /// (repo_path, branch, is_local) -> ブランチの鮮度情報（Refresh / fetch 完了で破棄）
+    branch_stats_cache: HashMap<(String, String, bool), BranchStats>,
+    /// repo_path -> (ブランチ -> チェックアウト中の worktree のパス)（スキャンごとに破棄）
+    checked_out_cache: HashMap<String, HashMap<String, String>>,
+    /// external_id -> SessionId のマッピング（統合された別表記の external_id も含む）
+    session_map: HashMap<String, SessionId>,
+    /// workspace_index -> SessionId のマッピング（登録順）
+    sessions_by_workspace: HashMap<usize, Vec<SessionId>>,
     /// 検出されたペイン一覧
     pub panes: Vec<Pane>,
     /// pane_id -> pane index のマッピング
@@ -150,24 +268,80 @@ pub struct AppState {
     pub selection_dialog: Option<SelectionDialog>,
     /// 終了フラグ
     pub should_quit: bool,
-    /// ステータスバーメッセージ
-    pub status_message: Option<String>,
+    /// ステータスバーメッセージの履歴（最新をステータスバーに表示）
+    pub messages: MessageLog,
+    /// ステータスバーにメッセージを出しておく時間（None なら次のメッセージまで）
+    pub status_message_ttl: Option<Duration>,
+    /// エラーのメッセージは時間で消さない
+    pub sticky_error_messages: bool,
+    /// 確認されるまで中央に出し続けるエラー
+    pub error_toast: Option<StatusMessage>,
     /// Zellijで開いているタブ名のキャッシュ
     open_tabs: HashSet<String>,
     /// ブランチフィルター（検索文字列）
     pub branch_filter: Option<String>,
+    /// ツリー全体のフィルター（リポジトリ名・ブランチ・パス・セッション概要の部分一致）
+    pub tree_filter: Option<String>,
     /// テーブルのスクロール状態（フレーム間で維持）
     pub table_state: TableState,
     /// Nerd Fontアイコンを使用するか
     pub use_nerd_font: bool,
+    /// 配色
+    pub theme: Theme,
+    /// アクティビティ表示の集計期間（日数、0で無効）
+    pub activity_days: u32,
+    /// スキャン後、直近のコミット数をまだ数え直していない
+    recent_commits_pending: bool,
+    /// ワークスペースを探すディレクトリ
+    pub search_paths: Vec<PathBuf>,
+    /// ディレクトリを辿る最大深度
+    pub max_scan_depth: usize,
+    /// 更新が途絶えたセッションを切断扱いにするまでの秒数（0で無効）
+    pub session_timeout_secs: u64,
+    /// ポーリングの Error を表示するまでに必要な連続回数
+    pub error_confirm_polls: u32,
+    /// 切断済みセッションを保持する時間（None で無期限）
+    pub disconnected_session_retention: Option<Duration>,
+    /// 保持するセッション数の上限（0で無制限）
+    pub max_sessions: usize,
+    /// Idle のセッションをツリーに表示しない
+    pub hide_idle_sessions: bool,
+    /// ツリーでのワークスペース内セッションの並び順
+    pub session_sort: SessionSortOrder,
+    /// パスの表示方法
+    pub path_display: PathDisplay,
+    /// セッションの summary / current_task の最大文字数
+    pub text_limits: TextLimits,
+    /// リポジトリ数（表示モードに関係なく全ワークスペースから数える）
+    pub repo_count: usize,
+    /// 表示するセッションのツール（None なら全ツール）
+    pub tool_filter: Option<AiTool>,
+    /// リポジトリグループにリモートホストのバッジを表示する
+    pub show_remote_host: bool,
+    /// 折りたたまれたリポジトリを活動開始時に自動展開する
+    pub auto_expand_on_activity: bool,
+    /// 未知のパスで登録されたセッションのために一時ワークスペースを作る
+    pub auto_add_unknown_workspaces: bool,
     /// タブ名テンプレート（マッチング用）
     pub tab_name_template: String,
     /// お気に入りリポジトリ（repo_key のセット）
     pub favorite_repos: HashSet<String>,
     /// Yazi連携: デバウンス中のコマンド (発火時刻, コマンド)
     pub pending_yazi: Option<(Instant, YaziCommand)>,
+    /// 設定の再読み込み要求（メインループで処理する）
+    pub reload_config_requested: bool,
+    /// 完了通知を最後に出した時刻（external_id → 時刻、デバウンス用）
+    completion_notified: HashMap<String, Instant>,
+    /// 次のループで再描画が必要か（イベント処理・状態更新で立て、描画で下ろす）
+    pub needs_redraw: bool,
+    /// ステータスバーの点滅（遷移先ステータス, 開始時刻）
+    pub status_flash: Option<(SessionStatus, Instant)>,
     /// Yazi連携: 最後に送信したコマンドのパス（重複送信防止）
     pub last_yazi_path: Option<std::path::PathBuf>,
+    /// on_workspace_select_command: デバウンス中の実行 (発火時刻, パス, ブランチ)
+    pub pending_select_hook: Option<(Instant, String, String)>,
+    /// on_workspace_select_command: 最後に実行したワークスペースのパス（同じ選択での再実行防止）
+    pub last_select_hook_path: Option<String>,
     /// Git logペインの表示フラグ
     pub show_git_log: bool,
     /// Git logのキャッシュ (project_path, commits)
@@ -176,6 +350,18 @@ pub struct AppState {
     pub git_log_scroll: usize,
     /// Git logペインのレンダリング領域（マウスイベント判定用）
     pub git_log_area: Option<ratatui::layout::Rect>,
+    /// 詳細サイドパネルの表示フラグ
+    pub show_detail_pane: bool,
+    /// 詳細サイドパネルの幅（画面幅に対する%）
+    pub detail_pane_percent: u16,
+    /// 詳細サイドパネルのレンダリング領域（マウスイベント判定用）
+    pub detail_pane_area: Option<ratatui::layout::Rect>,
+    /// マウスカーソル下のツリー項目インデックス（ホバー表示用）
+    pub hover_index: Option<usize>,
+    /// ステータスの即時再取得を要求されたワークスペースのパス（メインループで送信）
+    pub refresh_request: Option<String>,
+    /// 2打鍵コマンドの入力待ち中の1打目
+    pub pending_key: Option<char>,
     /// Git logでフォーカス中のコミットインデックス
     pub git_log_selected: Option<usize>,
     /// コミット詳細を表示中かどうか
@@ -192,6 +378,20 @@ pub struct AppState {
     pub active_pane_project_path: Option<String>,
     /// ユーザーが明示的に選択操作したフラグ（キーボード/マウス操作でtrue、ペイン切替でfalse）
     pub user_selected: bool,
+    /// アプリケーションのログファイルパス（init_logging で作成）
+    pub log_file_path: Option<std::path::PathBuf>,
+    /// 直近のログ行のリングバッファ（init_logging で作成、ログビューアの表示元）
+    pub log_buffer: Option<crate::app::log_buffer::LogBuffer>,
+    /// ログビューア: 表示中のログ行
+    pub log_lines: Vec<String>,
+    /// ログビューア: 末尾からのスクロール量（0で末尾追従）
+    pub log_scroll: usize,
+    /// 詳細ビューのスクロール位置（先頭からの行数、描画時に最大値へ丸める）
+    pub detail_scroll: u16,
+    /// ログビューア: レベルフィルター
+    pub log_level_filter: LogLevelFilter,
+    /// セッションポーリングの最終実行時刻（ステータスバー表示用）
+    pub last_poll: PollTimestamp,
 }
 
 /// コミット詳細情報
@@ -223,10 +423,12 @@ impl AppState {
     pub fn new() -> Self {
         Self {
             workspaces: Vec::new(),
-            sessions: Vec::new(),
+            sessions: HashMap::new(),
             tree_items: Vec::new(),
             collapsed_repos: HashSet::new(),
             expanded_remote_branches: HashSet::new(),
+            branch_stats_cache: HashMap::new(),
+            checked_out_cache: HashMap::new(),
             session_map: HashMap::new(),
             sessions_by_workspace: HashMap::new(),
             panes: Vec::new(),
@@ -238,19 +440,53 @@ impl AppState {
             input_dialog: None,
             selection_dialog: None,
             should_quit: false,
-            status_message: None,
+            messages: MessageLog::default(),
+            status_message_ttl: None,
+            sticky_error_messages: false,
+            error_toast: None,
             open_tabs: HashSet::new(),
             branch_filter: None,
+            tree_filter: None,
             table_state: TableState::default(),
             use_nerd_font: true,
+            theme: Theme::default(),
+            activity_days: 7,
+            recent_commits_pending: false,
+            search_paths: get_default_search_paths(),
+            max_scan_depth: 3,
+            session_timeout_secs: 0,
+            error_confirm_polls: DEFAULT_ERROR_CONFIRM_POLLS,
+            disconnected_session_retention: None,
+            max_sessions: 0,
+            hide_idle_sessions: false,
+            session_sort: SessionSortOrder::default(),
+            path_display: PathDisplay::default(),
+            repo_count: 0,
+            text_limits: TextLimits::default(),
+            tool_filter: None,
+            show_remote_host: false,
+            auto_expand_on_activity: false,
+            auto_add_unknown_workspaces: false,
             tab_name_template: "{repo}/{branch}".to_string(),
             favorite_repos: HashSet::new(),
             pending_yazi: None,
+            reload_config_requested: false,
+            completion_notified: HashMap::new(),
+            status_flash: None,
+            needs_redraw: true,
             last_yazi_path: None,
+            pending_select_hook: None,
+            last_select_hook_path: None,
             show_git_log: true,
             git_log_cache: None,
             git_log_scroll: 0,
             git_log_area: None,
+            show_detail_pane: false,
+            detail_pane_percent: DEFAULT_DETAIL_PANE_PERCENT,
+            detail_pane_area: None,
+            hover_index: None,
+            refresh_request: None,
+            pending_key: None,
             git_log_selected: None,
             git_log_show_detail: false,
             git_log_split_ratio: 0.65,
@@ -259,6 +495,13 @@ impl AppState {
             focused_pane: FocusedPane::default(),
             active_pane_project_path: None,
             user_selected: false,
+            log_file_path: None,
+            log_buffer: None,
+            log_lines: Vec::new(),
+            log_scroll: 0,
+            detail_scroll: 0,
+            log_level_filter: LogLevelFilter::default(),
+            last_poll: PollTimestamp::default(),
         }
     }
 
@@ -269,25 +512,69 @@ impl AppState {
             .replace("{branch}", branch)
     }
 
+    /// 前回終了時の折りたたみ状態を復元して作成
+    pub fn with_persisted_state(persisted: PersistedState) -> Self {
+        let mut state = Self::new();
+        state.collapsed_repos = persisted.collapsed_repos.into_iter().collect();
+        state.expanded_remote_branches = persisted.expanded_remote_branches.into_iter().collect();
+        state
+    }
+
+    /// 保存する折りたたみ状態（現在のワークスペースに存在する repo_key だけ残す）
+    pub fn persisted_state(&self) -> PersistedState {
+        let known: HashSet<String> = self.workspaces.iter().map(|ws| self.get_repo_key(ws)).collect();
+        let keep = |keys: &HashSet<String>| {
+            let mut kept: Vec<String> = keys.iter().filter(|k| known.contains(*k)).cloned().collect();
+            kept.sort();
+            kept
+        };
+        PersistedState {
+            collapsed_repos: keep(&self.collapsed_repos),
+            expanded_remote_branches: keep(&self.expanded_remote_branches),
+        }
+    }
+
     /// ワークスペースをスキャンして読み込み
     pub fn scan_workspaces(&mut self) {
-        let search_paths = get_default_search_paths();
         let mut workspaces: Vec<Workspace> = Vec::new();
+        self.checked_out_cache.clear();
 
-        for path in &search_paths {
-            let infos = scan_for_repositories(path, 3);
+        for path in &self.search_paths {
+            let infos = scan_for_repositories(path, self.max_scan_depth);
             for info in infos {
                 workspaces.push(info.into());
             }
         }
 
+        // 直近のコミット数は数え直すまで前回の値を使う（集計は take_recent_commits_request の呼び出し側で）
+        for ws in &mut workspaces {
+            if let Some(prev) = self.workspaces.iter().find(|w| w.project_path == ws.project_path) {
+                ws.recent_commits = prev.recent_commits;
+            }
+        }
+        self.recent_commits_pending = self.activity_days > 0;
+
+        // スキャンで見つからない一時ワークスペースは、セッションかペインが残っている間だけ残す
+        let transient: Vec<Workspace> = self
+            .workspaces
+            .iter()
+            .enumerate()
+            .filter(|(idx, w)| {
+                w.transient
+                    && !workspaces.iter().any(|s| s.project_path == w.project_path)
+                    && (!self.sessions_for_workspace(*idx).is_empty() || !self.panes_for_workspace(*idx).is_empty())
+            })
+            .map(|(_, w)| w.clone())
+            .collect();
+        workspaces.extend(transient);
+
         // パスでソート
         workspaces.sort_by(|a, b| a.project_path.cmp(&b.project_path));
 
         self.workspaces = workspaces;
         self.rebuild_tree();
 
-        self.status_message = Some(format!("Found {} workspaces", self.workspaces.len()));
+        self.set_status(format!("Found {} workspaces", self.workspaces.len()));
     }
 
     /// ツリー構造を再構築
@@ -302,25 +589,47 @@ impl AppState {
         // リポジトリごとにグループ化
         let mut repo_groups: HashMap<String, Vec<usize>> = HashMap::new();
         let mut repo_paths: HashMap<String, String> = HashMap::new(); // repo_key -> project_path
+        let mut all_repo_keys: HashSet<String> = HashSet::new();
 
         for (idx, ws) in self.workspaces.iter().enumerate() {
+            // 親リポジトリのパスを推定（worktreeの場合は親ディレクトリ）
+            let repo_key = self.get_repo_key(ws);
+            all_repo_keys.insert(repo_key.clone());
+
             // RunningOnly モードでは、アクティブセッションがないワークスペースをスキップ
             if self.list_display_mode == ListDisplayMode::RunningOnly {
                 let sessions = self.sessions_for_workspace(idx);
+                let has_sessions = sessions
+                    .iter()
+                    .any(|id| self.sessions.get(id).is_some_and(|s| self.matches_tool_filter(s)));
                 let panes = self.panes_for_workspace(idx);
-                if sessions.is_empty() && panes.is_empty() {
+                if !has_sessions && panes.is_empty() {
                     continue;
                 }
             }
 
-            // 親リポジトリのパスを推定（worktreeの場合は親ディレクトリ）
-            let repo_key = self.get_repo_key(ws);
             repo_groups.entry(repo_key.clone()).or_default().push(idx);
             // 最初のワークスペースのパスを保存
             repo_paths
                 .entry(repo_key)
                 .or_insert_with(|| ws.project_path.clone());
         }
+        self.repo_count = all_repo_keys.len();
+
+        // ツリーフィルター: 名前が一致したリポジトリは子をすべて残し、
+        // それ以外は一致する worktree があるものだけ、その worktree に絞って残す
+        let mut name_matched_repos: HashSet<String> = HashSet::new();
+        if self.tree_filter.as_deref().is_some_and(|f| !f.is_empty()) {
+            repo_groups.retain(|repo_key, indices| {
+                let name = repo_paths.get(repo_key).map(|p| Self::repo_display_name(p)).unwrap_or_default();
+                if self.matches_tree_filter(&name) {
+                    name_matched_repos.insert(repo_key.clone());
+                    return true;
+                }
+                indices.retain(|&idx| self.workspace_matches_tree_filter(idx));
+                !indices.is_empty()
+            });
+        }
 
         // お気に入りを先頭に、それ以外を後に
         let mut fav_keys: Vec<_> = repo_groups.keys()
@@ -371,15 +680,17 @@ impl AppState {
             let (local_branches, remote_branches) =
                 if self.list_display_mode == ListDisplayMode::WithBranches {
                     if let Some(manager) = worktree_manager {
-                        // フィルターを適用するクロージャ
+                        // フィルターを適用するクロージャ（リポジトリ名で一致していなければツリーフィルターも）
                         let filter_ref = self.branch_filter.as_ref();
+                        let repo_name_matched = name_matched_repos.contains(&repo_key);
                         let matches_filter = |b: &String| -> bool {
-                            match filter_ref {
+                            let branch_match = match filter_ref {
                                 Some(filter) if !filter.is_empty() => {
                                     b.to_lowercase().contains(&filter.to_lowercase())
                                 }
                                 _ => true,
-                            }
+                            };
+                            branch_match && (repo_name_matched || self.matches_tree_filter(b))
                         };
 
                         let local = manager
@@ -395,7 +706,7 @@ impl AppState {
                                 .list_remote_branches(std::path::Path::new(&repo_path))
                                 .unwrap_or_default()
                                 .into_iter()
-                                .filter(|b| {
+                                .filter(|(_, b)| {
                                     !existing_branches.contains(b)
                                         && !local.contains(b)
                                         && matches_filter(b)
@@ -422,11 +733,17 @@ impl AppState {
             let remote_expanded = self.expanded_remote_branches.contains(&repo_key);
 
             // グループヘッダーを追加
+            let host = indices
+                .iter()
+                .filter_map(|&idx| self.workspaces.get(idx))
+                .find_map(|ws| ws.remote_host.clone());
+
             self.tree_items.push(TreeItem::RepoGroup {
                 name: repo_name,
                 path: repo_key.clone(),
                 expanded: is_expanded,
                 worktree_count: indices.len(),
+                host,
             });
 
             // 展開されている場合はworktreeとセッション、ブランチを追加
@@ -439,7 +756,7 @@ impl AppState {
 
                 // Worktreeとそのセッションを追加
                 for (ws_idx_pos, &ws_idx) in indices.iter().enumerate() {
-                    let workspace_sessions = self.sessions_for_workspace(ws_idx);
+                    let workspace_sessions = self.visible_sessions_for_workspace(ws_idx);
                     let is_last_in_group = ws_idx_pos == indices.len() - 1
                         && !has_local_branches
                         && !has_remote_branches;
@@ -462,9 +779,9 @@ impl AppState {
                             });
                         }
                     } else {
-                        for (sess_idx_pos, &sess_idx) in workspace_sessions.iter().enumerate() {
+                        for (sess_idx_pos, &session_id) in workspace_sessions.iter().enumerate() {
                             self.tree_items.push(TreeItem::Session {
-                                session_index: sess_idx,
+                                session_id,
                                 is_last: sess_idx_pos == workspace_sessions.len() - 1,
                                 parent_is_last: parent_last,
                             });
@@ -476,11 +793,17 @@ impl AppState {
                 let local_count = local_branches.len();
                 for (i, branch) in local_branches.into_iter().enumerate() {
                     let is_last = i == local_count - 1 && !has_remote_branches;
+                    let stats = self.branch_stats(worktree_manager, &repo_path, &branch, true);
+                    let worktree_path = self.branch_worktree(worktree_manager, &repo_path, &branch);
                     self.tree_items.push(TreeItem::Branch {
                         name: branch,
                         is_local: true,
                         repo_path: repo_path.clone(),
                         is_last,
+                        ahead: stats.ahead,
+                        behind: stats.behind,
+                        last_commit: stats.last_commit,
+                        worktree_path,
                     });
                 }
 
@@ -497,12 +820,18 @@ impl AppState {
 
                     if remote_expanded {
                         let branch_count = remote_branches.len();
-                        for (i, branch) in remote_branches.into_iter().enumerate() {
+                        for (i, (remote, branch)) in remote_branches.into_iter().enumerate() {
+                            let remote_ref = format!("{}/{}", remote, branch);
+                            let stats = self.branch_stats(worktree_manager, &repo_path, &remote_ref, false);
                             self.tree_items.push(TreeItem::Branch {
                                 name: branch,
                                 is_local: false,
                                 repo_path: repo_path.clone(),
                                 is_last: i == branch_count - 1, // RemoteBranchGroup内の最後
+                                ahead: stats.ahead,
+                                behind: stats.behind,
+                                last_commit: stats.last_commit,
+                                worktree_path: None,
                             });
                         }
                     }
@@ -511,6 +840,56 @@ impl AppState {
         }
     }
 
+    /// ブランチの鮮度情報をキャッシュ経由で取得
+    ///
+    /// 全ブランチ分を計算すると重いので、ツリーに表示するブランチについてのみ呼ぶ。
+    fn branch_stats(
+        &mut self,
+        worktree_manager: Option<&WorktreeManager>,
+        repo_path: &str,
+        branch: &str,
+        is_local: bool,
+    ) -> BranchStats {
+        let key = (repo_path.to_string(), branch.to_string(), is_local);
+        if let Some(stats) = self.branch_stats_cache.get(&key) {
+            return *stats;
+        }
+        let Some(manager) = worktree_manager else {
+            return BranchStats::default();
+        };
+        let stats = manager.branch_stats(Path::new(repo_path), branch, is_local);
+        self.branch_stats_cache.insert(key, stats);
+        stats
+    }
+
+    /// ブランチをチェックアウトしている worktree のパス（リポジトリごとに1回だけ git に問い合わせる）
+    ///
+    /// 名前で照合する `existing_branches` では、スキャン対象外の場所にある worktree を見逃すため。
+    fn branch_worktree(
+        &mut self,
+        worktree_manager: Option<&WorktreeManager>,
+        repo_path: &str,
+        branch: &str,
+    ) -> Option<String> {
+        let manager = worktree_manager?;
+        self.checked_out_cache
+            .entry(repo_path.to_string())
+            .or_insert_with(|| {
+                manager
+                    .checked_out_branches(Path::new(repo_path))
+                    .into_iter()
+                    .map(|(branch, path)| (branch, path.to_string_lossy().into_owned()))
+                    .collect()
+            })
+            .get(branch)
+            .cloned()
+    }
+
+    /// ブランチの鮮度情報のキャッシュを破棄（次回のツリー再構築で再計算）
+    pub fn invalidate_branch_stats(&mut self) {
+        self.branch_stats_cache.clear();
+    }
+
     /// プロジェクトパスからアカウント名/リポジトリ名の表示名を生成
     /// 例: /Users/stanah/work/github.com/stanah/config -> stanah/config
     fn repo_display_name(project_path: &str) -> String {
@@ -574,61 +953,223 @@ impl AppState {
     // ===== Session management =====
 
     /// セッションを追加
-    pub fn add_session(&mut self, session: Session) -> usize {
-        let workspace_index = session.workspace_index;
-        let external_id = session.external_id.clone();
-        let session_index = self.sessions.len();
-
-        self.sessions.push(session);
-        self.session_map.insert(external_id, session_index);
+    pub fn add_session(&mut self, session: Session) -> SessionId {
+        let id = session.id;
+        self.session_map.insert(session.external_id.clone(), id);
         self.sessions_by_workspace
-            .entry(workspace_index)
+            .entry(session.workspace_index)
             .or_default()
-            .push(session_index);
+            .push(id);
+        self.sessions.insert(id, session);
+        id
+    }
 
-        session_index
+    /// セッションを完全に取り除く（別表記の external_id も含めて）
+    pub fn purge_session(&mut self, id: SessionId) -> Option<Session> {
+        let session = self.sessions.remove(&id)?;
+        self.session_map.retain(|_, mapped| *mapped != id);
+        if let Some(ids) = self.sessions_by_workspace.get_mut(&session.workspace_index) {
+            ids.retain(|mapped| *mapped != id);
+        }
+        self.completion_notified.remove(&session.external_id);
+        Some(session)
     }
 
     /// セッションを外部IDで検索
     pub fn get_session_by_external_id(&self, external_id: &str) -> Option<&Session> {
         self.session_map
             .get(external_id)
-            .and_then(|&idx| self.sessions.get(idx))
+            .and_then(|id| self.sessions.get(id))
     }
 
     /// セッションを外部IDで検索（mutable）
     pub fn get_session_by_external_id_mut(&mut self, external_id: &str) -> Option<&mut Session> {
         self.session_map
             .get(external_id)
-            .and_then(|&idx| self.sessions.get_mut(idx))
+            .and_then(|id| self.sessions.get_mut(id))
     }
 
-    /// ワークスペースのセッション一覧を取得（切断されたセッションは除外）
-    pub fn sessions_for_workspace(&self, workspace_index: usize) -> Vec<usize> {
+    /// ワークスペースのセッション一覧を取得（登録順、切断されたセッションも含む）
+    pub fn all_sessions_for_workspace(&self, workspace_index: usize) -> Vec<&Session> {
         self.sessions_by_workspace
             .get(&workspace_index)
-            .map(|indices| {
-                indices
-                    .iter()
-                    .filter(|&&idx| {
-                        self.sessions
-                            .get(idx)
-                            .map(|s| s.is_active())
-                            .unwrap_or(false)
-                    })
-                    .copied()
-                    .collect()
-            })
+            .map(|ids| ids.iter().filter_map(|id| self.sessions.get(id)).collect())
             .unwrap_or_default()
     }
 
+    /// ワークスペースのセッション一覧を取得（切断されたセッションは除外）
+    pub fn sessions_for_workspace(&self, workspace_index: usize) -> Vec<SessionId> {
+        self.all_sessions_for_workspace(workspace_index)
+            .into_iter()
+            .filter(|s| s.is_active())
+            .map(|s| s.id)
+            .collect()
+    }
+
+    /// 指定セッションが Idle かどうか（存在しなければ false）
+    pub fn is_session_idle(&self, external_id: &str) -> bool {
+        self.get_session_by_external_id(external_id)
+            .is_some_and(|s| s.status == SessionStatus::Idle)
+    }
+
+    /// ツリーに表示するセッション（ツールフィルター、hide_idle_sessions、session_sort を適用）
+    fn visible_sessions_for_workspace(&self, workspace_index: usize) -> Vec<SessionId> {
+        let mut sessions: Vec<&Session> = self
+            .all_sessions_for_workspace(workspace_index)
+            .into_iter()
+            .filter(|s| s.is_active() && self.matches_tool_filter(s))
+            .filter(|s| !(self.hide_idle_sessions && s.status == SessionStatus::Idle))
+            .collect();
+        // 安定ソートなので同順位は登録順のまま
+        match self.session_sort {
+            SessionSortOrder::Registration => {}
+            SessionSortOrder::RecentActivity => sessions
+                .sort_by_key(|s| std::cmp::Reverse(s.last_activity.unwrap_or(s.updated_at))),
+            SessionSortOrder::Status => sessions.sort_by_key(|s| status_sort_rank(s.status)),
+        }
+        sessions.into_iter().map(|s| s.id).collect()
+    }
+
+    /// 表示用のパス（path_display に従い ~ 短縮や search_paths からの相対パスにする）
+    pub fn display_path(&self, path: &str) -> String {
+        match self.path_display {
+            PathDisplay::Absolute => path.to_string(),
+            PathDisplay::Tilde => abbreviate_home(path),
+            PathDisplay::RelativeToRoot => {
+                // ネストしたルートがあれば最も深いものを使う
+                let relative = self
+                    .search_paths
+                    .iter()
+                    .filter_map(|root| Path::new(path).strip_prefix(root).ok())
+                    .filter(|rel| !rel.as_os_str().is_empty())
+                    .min_by_key(|rel| rel.components().count());
+                match relative {
+                    Some(rel) => rel.to_string_lossy().into_owned(),
+                    None => abbreviate_home(path),
+                }
+            }
+        }
+    }
+
+    /// ツリーフィルターに部分一致するか（大文字小文字を区別しない、未設定なら常に一致）
+    fn matches_tree_filter(&self, text: &str) -> bool {
+        match self.tree_filter.as_deref() {
+            Some(filter) if !filter.is_empty() => text.to_lowercase().contains(&filter.to_lowercase()),
+            _ => true,
+        }
+    }
+
+    /// ワークスペースのブランチ・パス・セッション概要のいずれかがツリーフィルターに一致するか
+    fn workspace_matches_tree_filter(&self, idx: usize) -> bool {
+        let Some(ws) = self.workspaces.get(idx) else {
+            return false;
+        };
+        self.matches_tree_filter(&ws.branch)
+            || self.matches_tree_filter(&ws.project_path)
+            || self
+                .sessions_for_workspace(idx)
+                .iter()
+                .filter_map(|id| self.sessions.get(id))
+                .filter_map(|s| s.summary.as_deref())
+                .any(|summary| self.matches_tree_filter(summary))
+    }
+
+    /// セッションがツールフィルターに合うか
+    fn matches_tool_filter(&self, session: &Session) -> bool {
+        self.tool_filter.is_none_or(|tool| session.tool == tool)
+    }
+
+    /// ツールフィルターを切り替え（All → Claude → Kiro → … → All）
+    pub fn cycle_tool_filter(&mut self) {
+        self.tool_filter = match self.tool_filter {
+            None => AiTool::ALL.first().copied(),
+            Some(current) => AiTool::ALL
+                .iter()
+                .position(|&tool| tool == current)
+                .and_then(|pos| AiTool::ALL.get(pos + 1))
+                .copied(),
+        };
+    }
+
     /// セッションを削除（実際には切断状態にする）
     pub fn remove_session(&mut self, external_id: &str) {
-        if let Some(&session_index) = self.session_map.get(external_id) {
-            if let Some(session) = self.sessions.get_mut(session_index) {
+        if let Some(session) = self.get_session_by_external_id_mut(external_id) {
+            session.disconnect();
+        }
+    }
+
+    /// セッションのハートビートを更新（未登録なら false）
+    pub fn heartbeat_session(&mut self, external_id: &str) -> bool {
+        match self.get_session_by_external_id_mut(external_id) {
+            Some(session) => {
+                session.heartbeat();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 詳細サイドパネルの幅を切り替え（非表示 → 設定幅 → より広いプリセット → 非表示）
+    pub fn cycle_detail_pane(&mut self, configured_percent: u16) {
+        if !self.show_detail_pane {
+            self.show_detail_pane = true;
+            self.detail_pane_percent = configured_percent;
+            return;
+        }
+        match DETAIL_PANE_PRESETS.iter().find(|&&p| p > self.detail_pane_percent) {
+            Some(&next) => self.detail_pane_percent = next,
+            None => self.show_detail_pane = false,
+        }
+    }
+
+    /// ハートビートが途絶えたセッションを切断状態にする
+    ///
+    /// 切断したセッションの external_id を返す（タイムアウト無効時は常に空）
+    pub fn expire_stale_sessions(&mut self) -> Vec<String> {
+        if self.session_timeout_secs == 0 {
+            return Vec::new();
+        }
+        let timeout = Duration::from_secs(self.session_timeout_secs);
+        let mut expired = Vec::new();
+        for session in self.sessions.values_mut() {
+            if session.is_stale(timeout) {
                 session.disconnect();
+                expired.push(session.external_id.clone());
             }
         }
+        expired
+    }
+
+    /// 古い切断済みセッションを破棄する（破棄した数を返す）
+    ///
+    /// 保持期間を過ぎたものに加え、総数が `max_sessions` を超える分を古い順に破棄する。
+    /// 接続中のセッションは破棄しない。破棄したらツリーを再構築すること。
+    pub fn gc_sessions(&mut self) -> usize {
+        let now = std::time::SystemTime::now();
+        let mut disconnected: Vec<&Session> = self.sessions.values().filter(|s| !s.is_active()).collect();
+        disconnected.sort_by_key(|s| s.updated_at);
+
+        let expired = |s: &Session| {
+            self.disconnected_session_retention
+                .is_some_and(|ttl| now.duration_since(s.updated_at).unwrap_or_default() >= ttl)
+        };
+        let mut drop: Vec<SessionId> = disconnected.iter().filter(|s| expired(s)).map(|s| s.id).collect();
+
+        if self.max_sessions > 0 {
+            let excess = (self.sessions.len() - drop.len()).saturating_sub(self.max_sessions);
+            drop.extend(
+                disconnected
+                    .iter()
+                    .filter(|s| !expired(s))
+                    .take(excess)
+                    .map(|s| s.id),
+            );
+        }
+
+        for &id in &drop {
+            self.purge_session(id);
+        }
+        drop.len()
     }
 
     /// セッションステータスを更新
@@ -638,20 +1179,41 @@ impl AppState {
         status: SessionStatus,
         message: Option<String>,
     ) {
-        if let Some(&session_index) = self.session_map.get(external_id) {
-            if let Some(session) = self.sessions.get_mut(session_index) {
-                session.update_status(status, message);
-            }
+        let limit = self.text_limits.summary;
+        if let Some(session) = self.get_session_by_external_id_mut(external_id) {
+            let message = message.map(|m| truncate_text(&m, limit));
+            session.update_status(status, message);
         }
     }
 
     /// プロジェクトパスからワークスペースインデックスを検索
+    ///
+    /// 完全一致がなければ、パスを含む（祖先である）ワークスペースのうち最も深いものを返す。
+    /// サブディレクトリで起動したエージェントも所属ワークスペースに紐付けるため。
     pub fn find_workspace_by_path(&self, project_path: &str) -> Option<usize> {
         // 正規化されたパスで比較
         let normalized = normalize_path(project_path);
-        self.workspaces
+        if let Some(idx) = self
+            .workspaces
             .iter()
             .position(|w| normalize_path(&w.project_path) == normalized)
+        {
+            return Some(idx);
+        }
+
+        let target = Path::new(&normalized);
+        self.workspaces
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, w)| {
+                let ws_path = normalize_path(&w.project_path);
+                // Path::starts_with はコンポーネント単位なので /repo が /repo2 に一致することはない
+                target
+                    .starts_with(&ws_path)
+                    .then(|| (idx, Path::new(&ws_path).components().count()))
+            })
+            .max_by_key(|&(_, depth)| depth)
+            .map(|(idx, _)| idx)
     }
 
     /// セッションを登録（新規または既存を更新）
@@ -661,34 +1223,167 @@ impl AppState {
         project_path: &str,
         tool: AiTool,
         pane_id: Option<u32>,
-    ) -> Option<usize> {
-        // ワークスペースを検索
-        let workspace_index = self.find_workspace_by_path(project_path)?;
+    ) -> Option<SessionId> {
+        // ワークスペースを検索（見つからなければ設定に応じて一時ワークスペースを追加）
+        let workspace_index = match self.find_workspace_by_path(project_path) {
+            Some(idx) => idx,
+            None if self.auto_add_unknown_workspaces => {
+                let workspace = transient_workspace(Path::new(&normalize_path(project_path)));
+                tracing::info!("Added transient workspace: {}", workspace.project_path);
+                self.workspaces.push(workspace);
+                self.workspaces.len() - 1
+            }
+            None => return None,
+        };
 
         // 既存セッションがあれば更新
-        if let Some(&session_index) = self.session_map.get(&external_id) {
-            if let Some(session) = self.sessions.get_mut(session_index) {
-                session.status = SessionStatus::Idle;
+        if let Some(session) = self.get_session_by_external_id_mut(&external_id) {
+            session.set_status(SessionStatus::Idle);
+            session.pane_id = pane_id;
+            session.updated_at = std::time::SystemTime::now();
+            session.heartbeat();
+            return Some(session.id);
+        }
+
+        // notify と polling で ID の表記が違っても、同じエージェントなら既存セッションに統合する
+        if let Some(id) = self.find_equivalent_session(&external_id, workspace_index) {
+            let session = self.sessions.get_mut(&id)?;
+            tracing::debug!("Merged session {} into {}", external_id, session.external_id);
+            // notify 経由で分かっている pane_id は polling 側の登録で消さない
+            if pane_id.is_some() {
                 session.pane_id = pane_id;
-                session.updated_at = std::time::SystemTime::now();
             }
-            return Some(session_index);
+            if session.status == SessionStatus::Disconnected {
+                session.set_status(SessionStatus::Idle);
+            }
+            session.heartbeat();
+            self.session_map.insert(external_id, id);
+            return Some(id);
         }
 
         // 新規セッションを作成
         let mut session = Session::new(external_id, workspace_index, tool);
         session.pane_id = pane_id;
-        let session_index = self.add_session(session);
+        Some(self.add_session(session))
+    }
+
+    /// 同じワークスペースで (ツール, セッションID) が一致する既存セッション
+    fn find_equivalent_session(&self, external_id: &str, workspace_index: usize) -> Option<SessionId> {
+        let key = session_key(external_id);
+        self.all_sessions_for_workspace(workspace_index)
+            .into_iter()
+            .find(|s| session_key(&s.external_id) == key)
+            .map(|s| s.id)
+    }
+
+    /// Working / NeedsInput のセッションを持つワークスペース
+    pub fn attention_workspaces(&self) -> HashSet<usize> {
+        self.sessions
+            .values()
+            .filter(|s| s.is_active() && matches!(s.status, SessionStatus::Working | SessionStatus::NeedsInput))
+            .map(|s| s.workspace_index)
+            .collect()
+    }
+
+    /// `before` の時点から新たに Working / NeedsInput になったワークスペースのリポジトリを展開
+    ///
+    /// 遷移した瞬間だけ展開するので、その後ユーザーが折りたたみ直せばそのまま。
+    /// 展開したリポジトリがあれば true（ツリーの再構築が必要）。
+    pub fn expand_on_activity(&mut self, before: &HashSet<usize>) -> bool {
+        let repo_keys: Vec<String> = self
+            .attention_workspaces()
+            .difference(before)
+            .filter_map(|&idx| self.workspaces.get(idx))
+            .map(|ws| self.get_repo_key(ws))
+            .collect();
+
+        let mut expanded = false;
+        for key in repo_keys {
+            expanded |= self.collapsed_repos.remove(&key);
+        }
+        expanded
+    }
+
+    /// セッションごとの現在のステータス（external_id → ステータス）
+    pub fn session_statuses(&self) -> HashMap<String, SessionStatus> {
+        self.sessions
+            .values()
+            .map(|s| (s.external_id.clone(), s.status))
+            .collect()
+    }
+
+    /// `before` の時点から Success に遷移したセッションの通知文（"repo/branch: summary"）
+    ///
+    /// 同じセッションは [`COMPLETION_NOTIFY_DEBOUNCE`] の間は再通知しない。
+    pub fn take_completions(&mut self, before: &HashMap<String, SessionStatus>) -> Vec<String> {
+        let now = Instant::now();
+        let mut messages = Vec::new();
+        for session in self.sessions.values() {
+            // 初めて見たセッション（起動直後のポーリングなど）は遷移とみなさない
+            let transitioned = before
+                .get(&session.external_id)
+                .is_some_and(|prev| *prev != SessionStatus::Success);
+            if session.status != SessionStatus::Success || !transitioned {
+                continue;
+            }
+            let recently_notified = self
+                .completion_notified
+                .get(&session.external_id)
+                .is_some_and(|at| now.duration_since(*at) < COMPLETION_NOTIFY_DEBOUNCE);
+            if recently_notified {
+                continue;
+            }
+            self.completion_notified.insert(session.external_id.clone(), now);
+
+            let mut message = match self.workspaces.get(session.workspace_index) {
+                Some(ws) => format!("{}/{}", self.get_repo_key(ws), ws.branch),
+                None => session.tool.name().to_string(),
+            };
+            if let Some(ref summary) = session.summary {
+                message.push_str(": ");
+                message.push_str(summary);
+            }
+            messages.push(message);
+        }
+        messages
+    }
+
+    /// `before` の時点から NeedsInput / Error / Success に遷移したセッションのうち最も目立たせるべきもの
+    ///
+    /// 優先度: Error > NeedsInput > Success。初めて見たセッションは遷移とみなさない。
+    pub fn notable_transition(&self, before: &HashMap<String, SessionStatus>) -> Option<SessionStatus> {
+        let rank = |status: SessionStatus| match status {
+            SessionStatus::Error => 3,
+            SessionStatus::NeedsInput => 2,
+            SessionStatus::Success => 1,
+            _ => 0,
+        };
+        self.sessions
+            .values()
+            .filter(|s| before.get(&s.external_id).is_some_and(|prev| *prev != s.status))
+            .map(|s| s.status)
+            .filter(|&status| rank(status) > 0)
+            .max_by_key(|&status| rank(status))
+    }
 
-        Some(session_index)
+    /// ステータスバーの点滅を開始
+    pub fn flash_status(&mut self, status: SessionStatus) {
+        self.status_flash = Some((status, Instant::now()));
+    }
+
+    /// 点滅中なら遷移先ステータス
+    pub fn active_status_flash(&self) -> Option<SessionStatus> {
+        self.status_flash
+            .filter(|(_, started)| started.elapsed() < STATUS_FLASH_DURATION)
+            .map(|(status, _)| status)
     }
 
     /// ワークスペースの集約ステータスを取得
     /// 優先度: Working > NeedsInput > Idle > Disconnected
     pub fn workspace_aggregate_status(&self, workspace_index: usize) -> SessionStatus {
-        let session_indices = self.sessions_for_workspace(workspace_index);
+        let session_ids = self.sessions_for_workspace(workspace_index);
 
-        if session_indices.is_empty() {
+        if session_ids.is_empty() {
             return SessionStatus::Disconnected;
         }
 
@@ -696,8 +1391,8 @@ impl AppState {
         let mut has_needs_input = false;
         let mut has_idle = false;
 
-        for &idx in &session_indices {
-            if let Some(session) = self.sessions.get(idx) {
+        for id in &session_ids {
+            if let Some(session) = self.sessions.get(id) {
                 match session.status {
                     SessionStatus::Working => has_working = true,
                     SessionStatus::NeedsInput => has_needs_input = true,
@@ -891,8 +1586,8 @@ impl AppState {
             Some(TreeItem::Worktree { workspace_index, .. }) => {
                 self.workspaces.get(*workspace_index).map(|ws| self.get_repo_key(ws))
             }
-            Some(TreeItem::Session { session_index, .. }) => {
-                self.sessions.get(*session_index).and_then(|s| {
+            Some(TreeItem::Session { session_id, .. }) => {
+                self.sessions.get(session_id).and_then(|s| {
                     self.workspaces.get(s.workspace_index).map(|ws| self.get_repo_key(ws))
                 })
             }
@@ -942,6 +1637,85 @@ impl AppState {
         }
     }
 
+    /// すべてのリポジトリグループを折りたたむ
+    ///
+    /// 選択中の項目が属するグループのキーを返す（再構築後に `select_repo_group` で戻す）。
+    pub fn collapse_all(&mut self) -> Option<String> {
+        let current_group = match self.tree_items.get(self.selected_index) {
+            Some(TreeItem::RepoGroup { path, .. }) => Some(path.clone()),
+            _ => self
+                .find_parent_repo_group_index()
+                .and_then(|idx| match self.tree_items.get(idx) {
+                    Some(TreeItem::RepoGroup { path, .. }) => Some(path.clone()),
+                    _ => None,
+                }),
+        };
+
+        let keys: Vec<String> = self.tree_items.iter().filter_map(|item| match item {
+            TreeItem::RepoGroup { path, .. } => Some(path.clone()),
+            _ => None,
+        }).collect();
+        self.collapsed_repos.extend(keys);
+        current_group
+    }
+
+    /// 名前（owner/repo の repo 部分）が `letter` で始まる次のリポジトリグループへ移動
+    ///
+    /// 現在位置の次から末尾まで探し、なければ先頭から折り返す。見つかれば true。
+    pub fn jump_to_repo(&mut self, letter: char) -> bool {
+        let letter = letter.to_lowercase().to_string();
+        let len = self.tree_items.len();
+        let found = (1..=len)
+            .map(|offset| (self.selected_index + offset) % len)
+            .find(|&idx| match &self.tree_items[idx] {
+                TreeItem::RepoGroup { name, .. } => {
+                    let repo = name.rsplit('/').next().unwrap_or(name);
+                    repo.to_lowercase().starts_with(&letter)
+                }
+                _ => false,
+            });
+
+        if let Some(idx) = found {
+            self.set_selected_index(idx);
+        }
+        found.is_some()
+    }
+
+    /// パスのワークスペースをツリーで選択する（折りたたまれたグループは展開する）
+    ///
+    /// フィルター等でツリーに出ていなければ false。
+    pub fn reveal_workspace(&mut self, path: &Path, worktree_manager: Option<&WorktreeManager>) -> bool {
+        let Some(ws_idx) = self.workspaces.iter().position(|ws| Path::new(&ws.project_path) == path) else {
+            return false;
+        };
+        let repo_key = self.get_repo_key(&self.workspaces[ws_idx]);
+        if self.collapsed_repos.remove(&repo_key) {
+            self.rebuild_tree_with_manager(worktree_manager);
+        }
+        let index = self.tree_items.iter().position(|item| {
+            matches!(item, TreeItem::Worktree { workspace_index, .. } if *workspace_index == ws_idx)
+        });
+        if let Some(index) = index {
+            self.set_selected_index(index);
+        }
+        index.is_some()
+    }
+
+    /// 指定キーのリポジトリグループを選択
+    pub fn select_repo_group(&mut self, key: &str) {
+        let index = self.tree_items.iter().position(|item| {
+            matches!(item, TreeItem::RepoGroup { path, .. } if path == key)
+        });
+        if let Some(index) = index {
+            self.set_selected_index(index);
+        }
+    }
+
+    /// すべてのリポジトリグループを展開する
+    pub fn expand_all(&mut self) {
+        self.collapsed_repos.clear();
+    }
+
     // ===== Navigation =====
 
     /// 選択インデックスを設定し、テーブルのスクロール状態も同期する
@@ -1094,8 +1868,8 @@ impl AppState {
             Some(TreeItem::Worktree { workspace_index, .. }) => {
                 self.workspaces.get(*workspace_index)
             }
-            Some(TreeItem::Session { session_index, .. }) => {
-                self.sessions.get(*session_index).and_then(|s| {
+            Some(TreeItem::Session { session_id, .. }) => {
+                self.sessions.get(session_id).and_then(|s| {
                     self.workspaces.get(s.workspace_index)
                 })
             }
@@ -1114,14 +1888,44 @@ impl AppState {
         }
     }
 
+    /// `w` で開くファイル
+    ///
+    /// セッション行ならそのセッションが最後に触ったファイル、なければ `default_file`、
+    /// README.md などの順にワークスペース内で存在するものを選ぶ。
+    pub fn file_to_open(&self, default_file: Option<&str>) -> Option<std::path::PathBuf> {
+        let ws_path = Path::new(&self.selected_workspace()?.project_path);
+        let last_file = self.selected_session().and_then(|s| s.last_file.as_deref());
+        last_file
+            .into_iter()
+            .chain(default_file)
+            .chain(["README.md", "README", "README.rst", "README.txt", "readme.md"])
+            .map(|file| ws_path.join(file))
+            .find(|path| path.is_file())
+    }
+
     /// 現在選択中のセッションを取得
     pub fn selected_session(&self) -> Option<&Session> {
         match self.tree_items.get(self.selected_index) {
-            Some(TreeItem::Session { session_index, .. }) => self.sessions.get(*session_index),
+            Some(TreeItem::Session { session_id, .. }) => self.sessions.get(session_id),
             _ => None,
         }
     }
 
+    /// 選択中の NeedsInput / Error セッションの確認済みフラグを切り替え、切り替え後の値を返す
+    ///
+    /// 対象外（セッション行でない、注意の要らない状態）なら None。
+    pub fn toggle_acknowledge_selected(&mut self) -> Option<bool> {
+        let Some(TreeItem::Session { session_id, .. }) = self.tree_items.get(self.selected_index) else {
+            return None;
+        };
+        let session = self.sessions.get_mut(session_id)?;
+        if !matches!(session.status, SessionStatus::NeedsInput | SessionStatus::Error) {
+            return None;
+        }
+        session.acknowledged = !session.acknowledged;
+        Some(session.acknowledged)
+    }
+
     /// 現在選択中のブランチ情報を取得
     pub fn selected_branch_info(&self) -> Option<(&str, bool, &str)> {
         match self.tree_items.get(self.selected_index) {
@@ -1135,6 +1939,16 @@ impl AppState {
         }
     }
 
+    /// Idle セッションの表示/非表示を切り替え
+    pub fn toggle_hide_idle_sessions(&mut self) {
+        self.hide_idle_sessions = !self.hide_idle_sessions;
+        self.set_status(if self.hide_idle_sessions {
+            "Idle sessions hidden".to_string()
+        } else {
+            "Idle sessions shown".to_string()
+        });
+    }
+
     /// 表示モードを切り替え
     pub fn toggle_display_mode(&mut self) {
         self.list_display_mode = self.list_display_mode.next();
@@ -1154,20 +1968,145 @@ impl AppState {
         };
     }
 
+    /// ログビューアの表示を切り替え
+    pub fn toggle_log_view(&mut self) {
+        if self.view_mode == ViewMode::Logs {
+            self.view_mode = ViewMode::List;
+        } else {
+            self.view_mode = ViewMode::Logs;
+            self.log_scroll = 0;
+            self.reload_log_lines();
+        }
+    }
+
+    /// ログ行を再読み込み（リングバッファ優先、なければログファイルの末尾）
+    pub fn reload_log_lines(&mut self) {
+        const MAX_LOG_LINES: usize = 1000;
+        if let Some(ref buffer) = self.log_buffer {
+            if let Ok(buffer) = buffer.lock() {
+                self.log_lines = buffer.iter().cloned().collect();
+            }
+            return;
+        }
+        let Some(ref path) = self.log_file_path else {
+            return;
+        };
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let start = lines.len().saturating_sub(MAX_LOG_LINES);
+        self.log_lines = lines[start..].iter().map(|l| l.to_string()).collect();
+    }
+
+    /// フィルター適用後のログ行
+    pub fn filtered_log_lines(&self) -> Vec<&str> {
+        self.log_lines
+            .iter()
+            .map(|l| l.as_str())
+            .filter(|l| self.log_level_filter.matches(l))
+            .collect()
+    }
+
+    /// ログビューアを上（古い方）へスクロール
+    pub fn log_scroll_up(&mut self, amount: usize) {
+        let max = self.filtered_log_lines().len().saturating_sub(1);
+        self.log_scroll = (self.log_scroll + amount).min(max);
+    }
+
+    /// ステータスバーにメッセージを出す
+    pub fn set_status(&mut self, text: impl Into<String>) {
+        self.messages.push(text.into(), MessageLevel::Info);
+    }
+
+    /// ステータスバーに警告を出す
+    pub fn set_warning(&mut self, text: impl Into<String>) {
+        self.messages.push(text.into(), MessageLevel::Warn);
+    }
+
+    /// エラーを出す（ステータスバーに加えて、確認されるまでトーストを表示）
+    pub fn set_error(&mut self, text: impl Into<String>) {
+        self.set_status_with_level(MessageLevel::Error, text);
+    }
+
+    /// 重要度を指定してステータスバーにメッセージを出す
+    pub fn set_status_with_level(&mut self, level: MessageLevel, text: impl Into<String>) {
+        self.messages.push(text.into(), level);
+        if level == MessageLevel::Error {
+            self.error_toast = self.messages.newest_first().next().cloned();
+        }
+    }
+
+    /// エラートーストを閉じる
+    pub fn dismiss_error_toast(&mut self) {
+        self.error_toast = None;
+    }
+
+    /// ステータスバーに表示中のメッセージ（期限切れなら None）
+    pub fn current_status_message(&self) -> Option<&StatusMessage> {
+        self.messages.current(self.status_message_ttl, self.sticky_error_messages)
+    }
+
+    /// メッセージ履歴の表示切替
+    pub fn toggle_message_history(&mut self) {
+        self.view_mode = if self.view_mode == ViewMode::Messages {
+            ViewMode::List
+        } else {
+            ViewMode::Messages
+        };
+    }
+
+    /// 詳細ビューを先頭から開く
+    pub fn open_detail_view(&mut self) {
+        self.detail_scroll = 0;
+        self.view_mode = ViewMode::Detail;
+    }
+
+    /// 詳細ビューを上へスクロール
+    pub fn detail_scroll_up(&mut self, amount: u16) {
+        self.detail_scroll = self.detail_scroll.saturating_sub(amount);
+    }
+
+    /// 詳細ビューを下へスクロール（上限は描画時に丸める）
+    pub fn detail_scroll_down(&mut self, amount: u16) {
+        self.detail_scroll = self.detail_scroll.saturating_add(amount);
+    }
+
+    /// ログビューアを下（新しい方）へスクロール
+    pub fn log_scroll_down(&mut self, amount: usize) {
+        self.log_scroll = self.log_scroll.saturating_sub(amount);
+    }
+
     /// アクティブなセッション数を取得
     pub fn active_count(&self) -> usize {
-        self.sessions.iter().filter(|s| s.is_active()).count()
+        self.sessions.values().filter(|s| s.is_active()).count()
     }
 
     /// 作業中のセッション数を取得
     pub fn working_count(&self) -> usize {
         self.sessions
-            .iter()
+            .values()
             .filter(|s| s.status == SessionStatus::Working)
             .count()
     }
 
-    /// ツリーアイテム数を取得
+    /// 入力待ちのセッション数を取得（確認済みのものは除く）
+    pub fn needs_input_count(&self) -> usize {
+        self.sessions
+            .values()
+            .filter(|s| s.status == SessionStatus::NeedsInput && !s.acknowledged)
+            .count()
+    }
+
+    /// エラー状態のセッション数を取得
+    pub fn error_count(&self) -> usize {
+        self.sessions
+            .values()
+            .filter(|s| s.status == SessionStatus::Error)
+            .count()
+    }
+
+    /// ツリーアイテム数を取得
     pub fn tree_item_count(&self) -> usize {
         self.tree_items.len()
     }
@@ -1178,8 +2117,8 @@ impl AppState {
             Some(TreeItem::Worktree { workspace_index, .. }) => {
                 self.workspaces.get(*workspace_index).map(|ws| ws.branch.clone())
             }
-            Some(TreeItem::Session { session_index, .. }) => {
-                self.sessions.get(*session_index).and_then(|s| {
+            Some(TreeItem::Session { session_id, .. }) => {
+                self.sessions.get(session_id).and_then(|s| {
                     self.workspaces.get(s.workspace_index).map(|ws| ws.branch.clone())
                 })
             }
@@ -1211,8 +2150,8 @@ impl AppState {
             Some(TreeItem::Worktree { workspace_index, .. }) => {
                 self.workspaces.get(*workspace_index).map(|ws| ws.project_path.clone())
             }
-            Some(TreeItem::Session { session_index, .. }) => {
-                self.sessions.get(*session_index).and_then(|s| {
+            Some(TreeItem::Session { session_id, .. }) => {
+                self.sessions.get(session_id).and_then(|s| {
                     self.workspaces.get(s.workspace_index).map(|ws| ws.project_path.clone())
                 })
             }
@@ -1408,11 +2347,23 @@ impl AppState {
         self.view_mode = ViewMode::Input;
     }
 
+    /// 終了確認ダイアログを開く
+    pub fn open_confirm_quit_dialog(&mut self, working: usize) {
+        self.input_dialog = Some(InputDialog::new_confirm_quit(working));
+        self.view_mode = ViewMode::Input;
+    }
+
     /// worktree削除ダイアログを開く
     pub fn open_delete_worktree_dialog(&mut self, force: bool) {
         if let Some(ws) = self.selected_workspace() {
             let path = ws.display_path();
-            self.input_dialog = Some(InputDialog::new_delete_worktree(path, force));
+            let branch = Some(ws.branch.clone()).filter(|b| !b.is_empty());
+            let operation = ws.git_operation;
+            let mut dialog = InputDialog::new_delete_worktree(path, force, branch);
+            if let Some(op) = operation {
+                dialog.set_error(format!("Worktree has an operation in progress: {}", op.label()));
+            }
+            self.input_dialog = Some(dialog);
             self.view_mode = ViewMode::Input;
         }
     }
@@ -1435,6 +2386,45 @@ impl AppState {
         self.view_mode = ViewMode::Selection;
     }
 
+    /// 選択中の項目に応じたコンテキストメニューの項目
+    pub fn context_menu_entries(&self) -> Vec<(&'static str, Action)> {
+        match self.tree_items.get(self.selected_index) {
+            Some(TreeItem::Session { session_id, .. })
+                if self.sessions.get(session_id).is_some_and(Session::can_relaunch) =>
+            {
+                vec![
+                    ("Relaunch agent", Action::RelaunchSession),
+                    ("Open", Action::Select),
+                    ("Open with layout...", Action::OpenWithLayout),
+                    ("Delete worktree", Action::DeleteWorktree),
+                    ("Copy path", Action::CopyPath),
+                ]
+            }
+            Some(TreeItem::Worktree { .. } | TreeItem::Session { .. } | TreeItem::Pane { .. }) => vec![
+                ("Open", Action::Select),
+                ("Open with layout...", Action::OpenWithLayout),
+                ("Delete worktree", Action::DeleteWorktree),
+                ("Copy path", Action::CopyPath),
+            ],
+            Some(TreeItem::Branch { .. }) => vec![("Create worktree", Action::CreateWorktree)],
+            Some(TreeItem::RepoGroup { expanded, .. } | TreeItem::RemoteBranchGroup { expanded, .. }) => vec![
+                (if *expanded { "Collapse" } else { "Expand" }, Action::ToggleExpand),
+                ("Fetch", Action::FetchRepo),
+            ],
+            Some(TreeItem::Separator) | None => Vec::new(),
+        }
+    }
+
+    /// 選択中の項目のコンテキストメニューを開く（項目がなければ何もしない）
+    pub fn open_context_menu(&mut self) {
+        let entries = self.context_menu_entries();
+        if entries.is_empty() {
+            return;
+        }
+        self.selection_dialog = Some(SelectionDialog::new_context_menu("Actions", entries));
+        self.view_mode = ViewMode::Selection;
+    }
+
     /// 選択ダイアログを閉じる
     pub fn close_selection_dialog(&mut self) {
         self.selection_dialog = None;
@@ -1501,11 +2491,7 @@ impl AppState {
         for (idx, item) in self.tree_items.iter().enumerate() {
             if let TreeItem::Worktree { workspace_index, .. } = item {
                 if let Some(ws) = self.workspaces.get(*workspace_index) {
-                    let pattern1 = self.generate_tab_name(&ws.repo_name, &ws.branch);
-                    let base_repo = ws.repo_name.split("__").next().unwrap_or(&ws.repo_name);
-                    let pattern2 = self.generate_tab_name(base_repo, &ws.branch);
-
-                    if tab_name == pattern1 || tab_name == pattern2 || tab_name == ws.branch {
+                    if self.matches_tab_name(ws, tab_name) {
                         self.set_selected_index(idx);
                         return true;
                     }
@@ -1515,6 +2501,51 @@ impl AppState {
         false
     }
 
+    /// タブ名がワークスペースを指しているか（テンプレート、"__" 形式のベース名、ブランチ名のみ）
+    fn matches_tab_name(&self, ws: &Workspace, tab_name: &str) -> bool {
+        let pattern1 = self.generate_tab_name(&ws.repo_name, &ws.branch);
+        let base_repo = ws.repo_name.split("__").next().unwrap_or(&ws.repo_name);
+        let pattern2 = self.generate_tab_name(base_repo, &ws.branch);
+        tab_name == pattern1 || tab_name == pattern2 || tab_name == ws.branch
+    }
+
+    /// リポジトリ名（とブランチ）でワークスペースを探す
+    ///
+    /// リポジトリ名は完全一致か worktree の `repo__branch` 形式のベース名で照合し、
+    /// 省略時は完全一致するもの（worktree よりメインのリポジトリ）を優先して返す。
+    /// タブ名テンプレートは経由しない（`{branch}` だけのテンプレートだと別リポジトリにも一致するため）。
+    pub fn find_workspace_by_name(&self, repo: &str, branch: Option<&str>) -> Option<usize> {
+        let matches_branch = |ws: &Workspace| branch.is_none_or(|branch| ws.branch == branch);
+        self.workspaces
+            .iter()
+            .position(|ws| ws.repo_name == repo && matches_branch(ws))
+            .or_else(|| {
+                self.workspaces
+                    .iter()
+                    .position(|ws| ws.repo_name.split("__").next() == Some(repo) && matches_branch(ws))
+            })
+    }
+
+    /// `open` / `path` の引数の補完候補
+    ///
+    /// リポジトリ未指定ならリポジトリ名（worktree の `repo__branch` はまとめる）、
+    /// 指定ありならそのリポジトリのブランチ名を返す。
+    pub fn name_candidates(&self, repo: Option<&str>) -> Vec<String> {
+        let base_name = |ws: &Workspace| ws.repo_name.split("__").next().unwrap_or_default().to_string();
+        let mut names: Vec<String> = match repo {
+            None => self.workspaces.iter().map(base_name).collect(),
+            Some(repo) => self
+                .workspaces
+                .iter()
+                .filter(|ws| ws.repo_name == repo || base_name(ws) == repo)
+                .map(|ws| ws.branch.clone())
+                .collect(),
+        };
+        names.sort();
+        names.dedup();
+        names
+    }
+
     /// 選択中のリポジトリのルートパスを取得
     ///
     /// ワークツリーが選択されている場合でも、git commondir から
@@ -1525,8 +2556,8 @@ impl AppState {
                 .workspaces
                 .get(*workspace_index)
                 .map(|ws| ws.project_path.clone()),
-            Some(TreeItem::Session { session_index, .. }) => {
-                self.sessions.get(*session_index).and_then(|s| {
+            Some(TreeItem::Session { session_id, .. }) => {
+                self.sessions.get(session_id).and_then(|s| {
                     self.workspaces
                         .get(s.workspace_index)
                         .map(|ws| ws.project_path.clone())
@@ -1570,8 +2601,8 @@ impl AppState {
                 let ws = self.workspaces.get(*workspace_index)?;
                 Some(YaziCommand::Cd(std::path::PathBuf::from(&ws.project_path)))
             }
-            TreeItem::Session { session_index, .. } => {
-                let session = self.sessions.get(*session_index)?;
+            TreeItem::Session { session_id, .. } => {
+                let session = self.sessions.get(session_id)?;
                 let ws = self.workspaces.get(session.workspace_index)?;
                 Some(YaziCommand::Cd(std::path::PathBuf::from(&ws.project_path)))
             }
@@ -1639,6 +2670,61 @@ impl AppState {
             deadline.saturating_duration_since(Instant::now())
         })
     }
+
+    /// 選択中のワークスペースで on_workspace_select_command のデバウンスタイマーをセットする
+    ///
+    /// ワークスペース以外の行や、前回実行したのと同じワークスペースならセットしない。
+    pub fn schedule_select_hook(&mut self, debounce_ms: u64) {
+        let Some(ws) = self.selected_workspace() else {
+            self.pending_select_hook = None;
+            return;
+        };
+        if self.last_select_hook_path.as_deref() == Some(ws.project_path.as_str()) {
+            self.pending_select_hook = None;
+            return;
+        }
+        let deadline = Instant::now() + Duration::from_millis(debounce_ms);
+        self.pending_select_hook = Some((deadline, ws.project_path.clone(), ws.branch.clone()));
+    }
+
+    /// デバウンスが明けていれば実行すべき (パス, ブランチ) を取り出す
+    pub fn take_ready_select_hook(&mut self) -> Option<(String, String)> {
+        let (deadline, _, _) = self.pending_select_hook.as_ref()?;
+        if Instant::now() < *deadline {
+            return None;
+        }
+        let (_, path, branch) = self.pending_select_hook.take()?;
+        self.last_select_hook_path = Some(path.clone());
+        Some((path, branch))
+    }
+
+    /// スキャン後に直近のコミット数を数えるべき (パス一覧, 日数) を取り出す
+    ///
+    /// リポジトリごとに履歴を辿るので、TUI では UI スレッドの外で数えて `set_recent_commits` で戻す。
+    pub fn take_recent_commits_request(&mut self) -> Option<(Vec<String>, u32)> {
+        if !std::mem::take(&mut self.recent_commits_pending) {
+            return None;
+        }
+        let paths = self.workspaces.iter().map(|ws| ws.project_path.clone()).collect();
+        Some((paths, self.activity_days))
+    }
+
+    /// 数えた直近のコミット数を反映（その間に消えたワークスペースの分は捨てる）
+    pub fn set_recent_commits(&mut self, counts: &[(String, usize)]) {
+        for (path, count) in counts {
+            if let Some(ws) = self.workspaces.iter_mut().find(|ws| &ws.project_path == path) {
+                ws.recent_commits = *count;
+            }
+        }
+        self.needs_redraw = true;
+    }
+
+    /// on_workspace_select_command のデッドラインまでの残り時間を返す
+    pub fn select_hook_timeout(&self) -> Option<Duration> {
+        self.pending_select_hook
+            .as_ref()
+            .map(|(deadline, _, _)| deadline.saturating_duration_since(Instant::now()))
+    }
 }
 
 impl Default for AppState {
@@ -1680,6 +2766,13 @@ fn resolve_repo_root(path: &str) -> String {
         .unwrap_or_else(|| path.to_string())
 }
 
+/// スキャン対象外のパス用の一時ワークスペース（git 管理下ならリポジトリ名・ブランチも取得）
+fn transient_workspace(path: &Path) -> Workspace {
+    let mut workspace = crate::workspace::workspace_for_path(path);
+    workspace.transient = true;
+    workspace
+}
+
 /// Normalize a path by expanding ~ to home directory
 fn normalize_path(path: &str) -> String {
     if path.starts_with("~/") {
@@ -1690,6 +2783,18 @@ fn normalize_path(path: &str) -> String {
     path.to_string()
 }
 
+/// `SessionSortOrder::Status` での並び順（小さいほど先頭）
+fn status_sort_rank(status: SessionStatus) -> u8 {
+    match status {
+        SessionStatus::NeedsInput => 0,
+        SessionStatus::Error => 1,
+        SessionStatus::Working => 2,
+        SessionStatus::Success => 3,
+        SessionStatus::Idle => 4,
+        SessionStatus::Disconnected => 5,
+    }
+}
+
 #[cfg(test)]
 mod yazi_tests {
     use super::*;
@@ -1722,4 +2827,757 @@ mod yazi_tests {
         assert!(timeout.is_some());
         assert!(timeout.unwrap() <= std::time::Duration::from_millis(500));
     }
+
+    #[test]
+    fn test_select_hook_fires_once_per_workspace() {
+        let mut state = AppState::new();
+        state.workspaces = vec![Workspace::new("/work/app".to_string(), "app".to_string(), "main".to_string())];
+        state.tree_items = vec![TreeItem::Worktree { workspace_index: 0, is_last: true }];
+        state.set_selected_index(0);
+
+        state.schedule_select_hook(60_000);
+        assert!(state.take_ready_select_hook().is_none());
+
+        state.schedule_select_hook(0);
+        assert_eq!(state.take_ready_select_hook(), Some(("/work/app".to_string(), "main".to_string())));
+        // 同じワークスペースに留まっている間は再実行しない
+        state.schedule_select_hook(0);
+        assert!(state.pending_select_hook.is_none());
+    }
+}
+
+#[cfg(test)]
+mod log_view_tests {
+    use super::*;
+
+    #[test]
+    fn test_log_line_level() {
+        assert_eq!(log_line_level("2025-01-01T00:00:00Z  INFO workspace_manager: started"), Some("INFO"));
+        assert_eq!(log_line_level("2025-01-01T00:00:00Z ERROR x: boom"), Some("ERROR"));
+        assert_eq!(log_line_level("    continuation line"), None);
+    }
+
+    #[test]
+    fn test_log_level_filter() {
+        let warn = "2025-01-01T00:00:00Z  WARN x: careful";
+        let debug = "2025-01-01T00:00:00Z DEBUG x: noisy";
+        assert!(LogLevelFilter::All.matches(debug));
+        assert!(!LogLevelFilter::Info.matches(debug));
+        assert!(LogLevelFilter::Warn.matches(warn));
+        assert!(!LogLevelFilter::Error.matches(warn));
+    }
+
+    #[test]
+    fn test_log_scroll_is_clamped() {
+        let mut state = AppState::new();
+        state.log_lines = (0..5).map(|i| format!("2025-01-01T00:00:00Z  INFO x: {}", i)).collect();
+        state.log_scroll_up(100);
+        assert_eq!(state.log_scroll, 4);
+        state.log_scroll_down(100);
+        assert_eq!(state.log_scroll, 0);
+    }
+}
+
+#[cfg(test)]
+mod detail_pane_tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_detail_pane() {
+        let mut state = AppState::new();
+        assert!(!state.show_detail_pane);
+
+        state.cycle_detail_pane(40);
+        assert!(state.show_detail_pane);
+        assert_eq!(state.detail_pane_percent, 40);
+
+        state.cycle_detail_pane(40);
+        assert_eq!(state.detail_pane_percent, 50);
+
+        state.cycle_detail_pane(40);
+        assert!(!state.show_detail_pane);
+
+        // 設定幅がプリセットより狭ければ全プリセットを巡回
+        state.cycle_detail_pane(25);
+        state.cycle_detail_pane(25);
+        assert_eq!(state.detail_pane_percent, 30);
+    }
+}
+
+#[cfg(test)]
+mod context_menu_tests {
+    use super::*;
+
+    #[test]
+    fn test_context_menu_entries_by_item_type() {
+        let mut state = AppState::new();
+        state.tree_items = vec![
+            TreeItem::RepoGroup {
+                name: "repo".to_string(),
+                path: "repo".to_string(),
+                expanded: true,
+                worktree_count: 0,
+                host: None,
+            },
+            TreeItem::Branch {
+                name: "feature".to_string(),
+                is_local: true,
+                repo_path: "/work/repo".to_string(),
+                is_last: true,
+                ahead: None,
+                behind: None,
+                last_commit: None,
+                worktree_path: None,
+            },
+            TreeItem::Separator,
+        ];
+
+        let actions = |state: &AppState| -> Vec<Action> {
+            state.context_menu_entries().into_iter().map(|(_, a)| a).collect()
+        };
+
+        assert_eq!(actions(&state), vec![Action::ToggleExpand, Action::FetchRepo]);
+        assert_eq!(state.context_menu_entries()[0].0, "Collapse");
+
+        state.selected_index = 1;
+        assert_eq!(actions(&state), vec![Action::CreateWorktree]);
+
+        // 区切り線ではメニューを開かない
+        state.selected_index = 2;
+        state.open_context_menu();
+        assert!(state.selection_dialog.is_none());
+        assert_eq!(state.view_mode, ViewMode::List);
+    }
+}
+
+#[cfg(test)]
+mod idle_session_tests {
+    use super::*;
+
+    #[test]
+    fn test_hide_idle_sessions_filters_tree_only() {
+        let mut state = AppState::new();
+        let mut idle = Session::new("claude:idle".to_string(), 0, AiTool::Claude);
+        idle.status = SessionStatus::Idle;
+        let mut working = Session::new("claude:working".to_string(), 0, AiTool::Claude);
+        working.status = SessionStatus::Working;
+        let idle = state.add_session(idle);
+        let working = state.add_session(working);
+
+        assert_eq!(state.visible_sessions_for_workspace(0), vec![idle, working]);
+
+        state.toggle_hide_idle_sessions();
+        assert_eq!(state.visible_sessions_for_workspace(0), vec![working]);
+        // 集約ステータスなどに使う一覧はそのまま
+        assert_eq!(state.sessions_for_workspace(0), vec![idle, working]);
+    }
+
+    #[test]
+    fn test_tool_filter_cycles_and_filters() {
+        let mut state = AppState::new();
+        let claude = state.add_session(Session::new("claude:a".to_string(), 0, AiTool::Claude));
+        let kiro = state.add_session(Session::new("kiro:/w:b".to_string(), 0, AiTool::Kiro));
+
+        state.cycle_tool_filter();
+        assert_eq!(state.tool_filter, Some(AiTool::Claude));
+        assert_eq!(state.visible_sessions_for_workspace(0), vec![claude]);
+
+        state.cycle_tool_filter();
+        assert_eq!(state.visible_sessions_for_workspace(0), vec![kiro]);
+
+        for _ in 0..AiTool::ALL.len() - 1 {
+            state.cycle_tool_filter();
+        }
+        assert_eq!(state.tool_filter, None);
+        assert_eq!(state.visible_sessions_for_workspace(0), vec![claude, kiro]);
+    }
+
+    #[test]
+    fn test_session_sort_orders_tree_sessions() {
+        let mut state = AppState::new();
+        let base = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut ids = Vec::new();
+        for (i, (status, age)) in [
+            (SessionStatus::Idle, 0),
+            (SessionStatus::Working, 30),
+            (SessionStatus::NeedsInput, 10),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut session = Session::new(format!("claude:s{}", i), 0, AiTool::Claude);
+            session.status = status;
+            session.last_activity = Some(base - Duration::from_secs(age));
+            ids.push(state.add_session(session));
+        }
+
+        assert_eq!(state.visible_sessions_for_workspace(0), ids);
+        state.session_sort = SessionSortOrder::RecentActivity;
+        assert_eq!(state.visible_sessions_for_workspace(0), vec![ids[0], ids[2], ids[1]]);
+        state.session_sort = SessionSortOrder::Status;
+        assert_eq!(state.visible_sessions_for_workspace(0), vec![ids[2], ids[1], ids[0]]);
+    }
+}
+
+#[cfg(test)]
+mod aggregate_status_tests {
+    use super::*;
+
+    fn state_with(statuses: &[SessionStatus]) -> AppState {
+        let mut state = AppState::new();
+        for (i, status) in statuses.iter().enumerate() {
+            let mut session = Session::new(format!("claude:s{}", i), 0, AiTool::Claude);
+            session.status = *status;
+            state.add_session(session);
+        }
+        state
+    }
+
+    #[test]
+    fn test_workspace_aggregate_status_priority() {
+        assert_eq!(state_with(&[]).workspace_aggregate_status(0), SessionStatus::Disconnected);
+        assert_eq!(
+            state_with(&[SessionStatus::Disconnected, SessionStatus::Disconnected]).workspace_aggregate_status(0),
+            SessionStatus::Disconnected
+        );
+        assert_eq!(
+            state_with(&[SessionStatus::Idle, SessionStatus::Working]).workspace_aggregate_status(0),
+            SessionStatus::Working
+        );
+        assert_eq!(
+            state_with(&[SessionStatus::Idle, SessionStatus::NeedsInput]).workspace_aggregate_status(0),
+            SessionStatus::NeedsInput
+        );
+        // 他のワークスペースのセッションは数えない
+        assert_eq!(
+            state_with(&[SessionStatus::Working]).workspace_aggregate_status(1),
+            SessionStatus::Disconnected
+        );
+    }
+}
+
+#[cfg(test)]
+mod auto_expand_tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_on_activity_only_on_transition() {
+        let mut state = AppState::new();
+        state.workspaces = vec![Workspace::new(
+            "/nonexistent/app__feature".to_string(),
+            "app__feature".to_string(),
+            "feature".to_string(),
+        )];
+        let id = state.add_session(Session::new("claude:s1".to_string(), 0, AiTool::Claude));
+        state.sessions.get_mut(&id).unwrap().status = SessionStatus::Idle;
+        state.collapsed_repos.insert("app".to_string());
+
+        let before = state.attention_workspaces();
+        state.sessions.get_mut(&id).unwrap().status = SessionStatus::Working;
+        assert!(state.expand_on_activity(&before));
+        assert!(!state.collapsed_repos.contains("app"));
+
+        // 作業中のまま折りたたみ直しても再展開しない
+        state.collapsed_repos.insert("app".to_string());
+        let before = state.attention_workspaces();
+        assert!(!state.expand_on_activity(&before));
+        assert!(state.collapsed_repos.contains("app"));
+    }
+}
+
+#[cfg(test)]
+mod collapse_all_tests {
+    use super::*;
+
+    fn group(name: &str) -> TreeItem {
+        TreeItem::RepoGroup {
+            name: name.to_string(),
+            path: name.to_string(),
+            expanded: true,
+            worktree_count: 1,
+            host: None,
+        }
+    }
+
+    #[test]
+    fn test_collapse_all_and_expand_all() {
+        let mut state = AppState::new();
+        state.tree_items = vec![
+            group("a"),
+            TreeItem::Worktree { workspace_index: 0, is_last: true },
+            group("b"),
+            TreeItem::Worktree { workspace_index: 1, is_last: true },
+        ];
+        state.set_selected_index(3);
+
+        assert_eq!(state.collapse_all().as_deref(), Some("b"));
+        assert!(state.collapsed_repos.contains("a") && state.collapsed_repos.contains("b"));
+
+        // 再構築後の並びでもグループを選び直せる
+        state.tree_items = vec![group("a"), group("b")];
+        state.select_repo_group("b");
+        assert_eq!(state.selected_index, 1);
+
+        state.expand_all();
+        assert!(state.collapsed_repos.is_empty());
+    }
+
+    #[test]
+    fn test_jump_to_repo_wraps_around() {
+        let mut state = AppState::new();
+        state.tree_items = vec![
+            group("stanah/alpha"),
+            TreeItem::Worktree { workspace_index: 0, is_last: true },
+            group("stanah/beta"),
+            group("other/apple"),
+        ];
+        state.set_selected_index(0);
+
+        assert!(state.jump_to_repo('A'));
+        assert_eq!(state.selected_index, 3);
+        assert!(state.jump_to_repo('a'));
+        assert_eq!(state.selected_index, 0);
+        // owner 部分ではマッチしない
+        assert!(!state.jump_to_repo('s'));
+        assert_eq!(state.selected_index, 0);
+    }
+}
+
+#[cfg(test)]
+mod find_workspace_tests {
+    use super::*;
+
+    #[test]
+    fn test_find_workspace_by_repo_and_branch() {
+        let mut state = AppState::new();
+        state.workspaces = vec![
+            Workspace::new("/nonexistent/config".to_string(), "config".to_string(), "main".to_string()),
+            Workspace::new(
+                "/nonexistent/config__feature".to_string(),
+                "config__feature".to_string(),
+                "feature".to_string(),
+            ),
+            Workspace::new("/nonexistent/other".to_string(), "other".to_string(), "feature".to_string()),
+        ];
+        assert_eq!(state.find_workspace_by_name("config", None), Some(0));
+        assert_eq!(state.find_workspace_by_name("config", Some("feature")), Some(1));
+        assert_eq!(state.find_workspace_by_name("other", Some("feature")), Some(2));
+        assert_eq!(state.find_workspace_by_name("config", Some("missing")), None);
+        assert_eq!(state.find_workspace_by_name("nope", None), None);
+
+        // ブランチ名だけのタブ名テンプレートでも別リポジトリには一致しない
+        state.tab_name_template = "{branch}".to_string();
+        assert_eq!(state.find_workspace_by_name("other", Some("feature")), Some(2));
+        assert_eq!(state.find_workspace_by_name("nope", Some("feature")), None);
+
+        assert_eq!(state.name_candidates(None), vec!["config", "other"]);
+        assert_eq!(state.name_candidates(Some("config")), vec!["feature", "main"]);
+        assert!(state.name_candidates(Some("nope")).is_empty());
+    }
+
+    #[test]
+    fn test_reveal_workspace_expands_collapsed_group() {
+        let mut state = AppState::new();
+        state.workspaces = vec![
+            Workspace::new("/nonexistent/app".to_string(), "app".to_string(), "main".to_string()),
+            Workspace::new("/nonexistent/lib".to_string(), "lib".to_string(), "main".to_string()),
+        ];
+        let key = state.get_repo_key(&state.workspaces[1]);
+        state.collapsed_repos.insert(key);
+        state.rebuild_tree();
+
+        assert!(state.reveal_workspace(Path::new("/nonexistent/lib"), None));
+        assert!(matches!(
+            state.tree_items[state.selected_index],
+            TreeItem::Worktree { workspace_index: 1, .. }
+        ));
+        assert!(!state.reveal_workspace(Path::new("/nonexistent/gone"), None));
+    }
+}
+
+#[cfg(test)]
+mod persisted_state_tests {
+    use super::*;
+
+    #[test]
+    fn test_persisted_state_drops_unknown_repos() {
+        let mut state = AppState::with_persisted_state(PersistedState {
+            collapsed_repos: vec!["app".to_string(), "gone".to_string()],
+            expanded_remote_branches: vec!["gone".to_string()],
+        });
+        state.workspaces = vec![Workspace::new(
+            "/nonexistent/app".to_string(),
+            "app".to_string(),
+            "main".to_string(),
+        )];
+        state.rebuild_tree();
+        assert!(matches!(state.tree_items[0], TreeItem::RepoGroup { expanded: false, .. }));
+
+        let persisted = state.persisted_state();
+        assert_eq!(persisted.collapsed_repos, vec!["app".to_string()]);
+        assert!(persisted.expanded_remote_branches.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod path_display_tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_to_root_uses_deepest_search_path() {
+        let mut state = AppState::new();
+        state.path_display = PathDisplay::RelativeToRoot;
+        state.search_paths = vec![PathBuf::from("/srv/src"), PathBuf::from("/srv/src/github.com")];
+        assert_eq!(state.display_path("/srv/src/github.com/owner/repo"), "owner/repo");
+        assert_eq!(state.display_path("/srv/src/local/tool"), "local/tool");
+        // ルート外はそのまま（ホーム外なので ~ にもならない）
+        assert_eq!(state.display_path("/opt/other"), "/opt/other");
+
+        state.path_display = PathDisplay::Absolute;
+        assert_eq!(state.display_path("/srv/src/local/tool"), "/srv/src/local/tool");
+    }
+}
+
+#[cfg(test)]
+mod tree_filter_tests {
+    use super::*;
+
+    fn workspace(path: &str, branch: &str) -> Workspace {
+        let name = path.rsplit('/').next().unwrap().to_string();
+        Workspace::new(path.to_string(), name, branch.to_string())
+    }
+
+    fn visible(state: &AppState) -> Vec<String> {
+        state
+            .tree_items
+            .iter()
+            .filter_map(|item| match item {
+                TreeItem::RepoGroup { name, .. } => Some(name.clone()),
+                TreeItem::Worktree { workspace_index, .. } => Some(state.workspaces[*workspace_index].branch.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tree_filter_keeps_matching_repos_and_children() {
+        let mut state = AppState::new();
+        state.workspaces = vec![
+            workspace("/nonexistent/me/alpha", "main"),
+            workspace("/nonexistent/me/alpha__login", "login"),
+            workspace("/nonexistent/me/beta", "main"),
+            workspace("/nonexistent/me/beta__signup", "signup"),
+        ];
+        let mut session = Session::new("claude:s1".to_string(), 2, AiTool::Claude);
+        session.summary = Some("Fixing the LOGIN redirect".to_string());
+        state.add_session(session);
+
+        state.rebuild_tree();
+        assert_eq!(visible(&state).len(), 6);
+
+        // リポジトリ名が一致: 子はすべて残る
+        state.tree_filter = Some("ALPHA".to_string());
+        state.rebuild_tree();
+        assert_eq!(visible(&state), vec!["me/alpha", "main", "login"]);
+
+        // 子だけが一致: ヘッダーと一致した worktree（セッション概要を含む）だけ
+        state.tree_filter = Some("login".to_string());
+        state.rebuild_tree();
+        assert_eq!(visible(&state), vec!["me/alpha", "login", "me/beta", "main"]);
+
+        state.tree_filter = Some("nothing".to_string());
+        state.rebuild_tree();
+        assert!(visible(&state).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod summary_tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_counts() {
+        let mut state = AppState::new();
+        state.workspaces = ["app__main", "app__feature", "lib__main"]
+            .iter()
+            .map(|name| Workspace::new(format!("/nonexistent/{}", name), name.to_string(), "main".to_string()))
+            .collect();
+        for (id, ws, status) in [
+            ("claude:a", 0, SessionStatus::NeedsInput),
+            ("claude:b", 1, SessionStatus::Error),
+            ("claude:c", 2, SessionStatus::Disconnected),
+        ] {
+            let mut session = Session::new(id.to_string(), ws, AiTool::Claude);
+            session.status = status;
+            state.add_session(session);
+        }
+
+        // RunningOnly でもリポジトリ数は全ワークスペースから数える
+        state.list_display_mode = ListDisplayMode::RunningOnly;
+        state.rebuild_tree_with_manager(None);
+        assert_eq!(state.repo_count, 2);
+        assert_eq!(state.active_count(), 2);
+        assert_eq!(state.needs_input_count(), 1);
+        assert_eq!(state.error_count(), 1);
+    }
+}
+
+#[cfg(test)]
+mod completion_tests {
+    use super::*;
+
+    #[test]
+    fn test_take_completions_on_edge_with_debounce() {
+        let mut state = AppState::new();
+        state.workspaces = vec![Workspace::new(
+            "/nonexistent/app__feature".to_string(),
+            "app__feature".to_string(),
+            "feature".to_string(),
+        )];
+        let s1 = state.add_session(Session::new("claude:s1".to_string(), 0, AiTool::Claude));
+        state.sessions.get_mut(&s1).unwrap().status = SessionStatus::Working;
+
+        let before = state.session_statuses();
+        state.sessions.get_mut(&s1).unwrap().update_status(SessionStatus::Success, Some("Done".to_string()));
+        assert_eq!(state.take_completions(&before), vec!["app/feature: Done".to_string()]);
+
+        // Success のままなら通知しない
+        let before = state.session_statuses();
+        assert!(state.take_completions(&before).is_empty());
+
+        // 完了済みで新たに登録されたセッションは通知しない
+        let mut s2 = Session::new("claude:s2".to_string(), 0, AiTool::Claude);
+        s2.status = SessionStatus::Success;
+        state.add_session(s2);
+        assert!(state.take_completions(&before).is_empty());
+
+        // すぐに再び完了してもデバウンス中は通知しない
+        state.sessions.get_mut(&s1).unwrap().status = SessionStatus::Working;
+        let before = state.session_statuses();
+        state.sessions.get_mut(&s1).unwrap().status = SessionStatus::Success;
+        assert!(state.take_completions(&before).is_empty());
+    }
+
+    #[test]
+    fn test_notable_transition_prefers_error() {
+        let mut state = AppState::new();
+        let s1 = state.add_session(Session::new("claude:s1".to_string(), 0, AiTool::Claude));
+        let s2 = state.add_session(Session::new("claude:s2".to_string(), 0, AiTool::Claude));
+        let before = state.session_statuses();
+        assert_eq!(state.notable_transition(&before), None);
+
+        state.sessions.get_mut(&s1).unwrap().status = SessionStatus::Success;
+        state.sessions.get_mut(&s2).unwrap().status = SessionStatus::Error;
+        assert_eq!(state.notable_transition(&before), Some(SessionStatus::Error));
+
+        state.sessions.get_mut(&s2).unwrap().status = SessionStatus::Working;
+        assert_eq!(state.notable_transition(&before), Some(SessionStatus::Success));
+    }
+}
+
+#[cfg(test)]
+mod status_message_tests {
+    use super::*;
+
+    #[test]
+    fn test_status_messages_keep_history() {
+        let mut state = AppState::new();
+        state.set_status("Created tab: app");
+        state.set_error("Failed to launch Claude: not found");
+
+        let current = state.current_status_message().unwrap();
+        assert_eq!(current.level, MessageLevel::Error);
+        // エラーは確認されるまでトーストに残る
+        assert!(state.error_toast.is_some());
+        state.dismiss_error_toast();
+        assert!(state.error_toast.is_none());
+        // 上書きされたメッセージも履歴に残る
+        assert_eq!(state.messages.len(), 2);
+
+        state.status_message_ttl = Some(Duration::ZERO);
+        assert!(state.current_status_message().is_none());
+        state.sticky_error_messages = true;
+        assert!(state.current_status_message().is_some());
+    }
+}
+
+#[cfg(test)]
+mod workspace_match_tests {
+    use super::*;
+
+    #[test]
+    fn test_find_workspace_by_path_falls_back_to_deepest_ancestor() {
+        let mut state = AppState::new();
+        state.workspaces = vec![
+            Workspace::new("/work/app".to_string(), "app".to_string(), "main".to_string()),
+            Workspace::new("/work/app/.worktrees/feat".to_string(), "app".to_string(), "feat".to_string()),
+            Workspace::new("/work/app2".to_string(), "app2".to_string(), "main".to_string()),
+        ];
+
+        assert_eq!(state.find_workspace_by_path("/work/app"), Some(0));
+        assert_eq!(state.find_workspace_by_path("/work/app/src/ui"), Some(0));
+        assert_eq!(state.find_workspace_by_path("/work/app/.worktrees/feat/src"), Some(1));
+        assert_eq!(state.find_workspace_by_path("/work/app2/crates"), Some(2));
+        assert_eq!(state.find_workspace_by_path("/work/other"), None);
+    }
+
+    #[test]
+    fn test_poll_registration_merges_into_notify_session() {
+        let mut state = AppState::new();
+        state.workspaces = vec![Workspace::new("/work/app".to_string(), "app".to_string(), "main".to_string())];
+
+        let notified = state.register_session("claude:ABC-123".to_string(), "/work/app", AiTool::Claude, Some(7)).unwrap();
+        let polled = state.register_session("claude:abc-123".to_string(), "/work/app", AiTool::Claude, None).unwrap();
+
+        assert_eq!(notified, polled);
+        assert_eq!(state.sessions.len(), 1);
+        assert_eq!(state.sessions[&notified].pane_id, Some(7));
+        // 以降の polling 更新も統合先のセッションに届く
+        assert!(state.get_session_by_external_id("claude:abc-123").is_some());
+    }
+
+    #[test]
+    fn test_gc_sessions_purges_without_invalidating_ids() {
+        let mut state = AppState::new();
+        state.workspaces = vec![Workspace::new("/work/app".to_string(), "app".to_string(), "main".to_string())];
+        let ids: Vec<_> = ["claude:a", "claude:b", "claude:c"]
+            .iter()
+            .map(|id| state.register_session(id.to_string(), "/work/app", AiTool::Claude, None).unwrap())
+            .collect();
+        state.remove_session("claude:a");
+        state.remove_session("claude:b");
+
+        // 期限・上限なしなら何も破棄しない
+        assert_eq!(state.gc_sessions(), 0);
+
+        // 上限 2 なら古い切断済みの a だけ破棄
+        state.max_sessions = 2;
+        assert_eq!(state.gc_sessions(), 1);
+        assert!(state.get_session_by_external_id("claude:a").is_none());
+        assert_eq!(state.get_session_by_external_id("claude:c").unwrap().external_id, "claude:c");
+
+        // 保持期間切れで残りの切断済みも破棄、接続中は残る
+        state.disconnected_session_retention = Some(Duration::ZERO);
+        assert_eq!(state.gc_sessions(), 1);
+        assert_eq!(state.sessions.len(), 1);
+        assert_eq!(state.sessions_for_workspace(0), vec![ids[2]]);
+        // 残ったセッションの ID は破棄前と変わらない
+        state.rebuild_tree_with_manager(None);
+        assert!(state.tree_items.iter().any(|item| matches!(
+            item,
+            TreeItem::Session { session_id, .. } if *session_id == ids[2]
+        )));
+    }
+
+    #[test]
+    fn test_register_unknown_path_adds_transient_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+
+        let mut state = AppState::new();
+        assert!(state.register_session("claude:s1".to_string(), &path, AiTool::Claude, None).is_none());
+
+        state.auto_add_unknown_workspaces = true;
+        let idx = state.register_session("claude:s1".to_string(), &path, AiTool::Claude, None).unwrap();
+        let ws = &state.workspaces[state.sessions[&idx].workspace_index];
+        assert!(ws.transient);
+        assert_eq!(ws.project_path, path);
+
+        // セッションが残っている間はスキャンしても残り、なくなれば消える
+        state.scan_workspaces();
+        assert_eq!(state.workspaces.len(), 1);
+        state.remove_session("claude:s1");
+        state.scan_workspaces();
+        assert!(state.workspaces.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod branch_worktree_tests {
+    use super::*;
+    use git2::Repository;
+
+    #[test]
+    fn test_branch_checked_out_outside_search_paths_is_marked() {
+        let dir = tempfile::tempdir().unwrap();
+        let scan_root = dir.path().join("scan");
+        let repo_path = scan_root.join("repo");
+        let repo = Repository::init(&repo_path).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let root = repo.commit(Some("refs/heads/main"), &sig, &sig, "root", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        let root = repo.find_commit(root).unwrap();
+        repo.branch("feature", &root, false).unwrap();
+        repo.branch("idle", &root, false).unwrap();
+        // スキャン対象外に worktree を作る
+        let elsewhere = dir.path().join("elsewhere");
+        let status = std::process::Command::new("git")
+            .current_dir(&repo_path)
+            .args(["worktree", "add", "-q"])
+            .arg(&elsewhere)
+            .arg("feature")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let mut state = AppState::new();
+        state.search_paths = vec![scan_root];
+        state.max_scan_depth = 2;
+        state.scan_workspaces();
+        state.list_display_mode = ListDisplayMode::WithBranches;
+        state.rebuild_tree_with_manager(Some(&WorktreeManager::default()));
+
+        let branch_worktree = |name: &str| {
+            state.tree_items.iter().find_map(|item| match item {
+                TreeItem::Branch { name: n, worktree_path, .. } if n == name => Some(worktree_path.clone()),
+                _ => None,
+            })
+        };
+        let marked = branch_worktree("feature").flatten().map(|p| Path::new(&p).canonicalize().unwrap());
+        assert_eq!(marked, Some(elsewhere.canonicalize().unwrap()));
+        assert_eq!(branch_worktree("idle"), Some(None));
+    }
+}
+
+#[cfg(test)]
+mod recent_commits_tests {
+    use super::*;
+    use git2::Repository;
+
+    #[test]
+    fn test_recent_commits_counted_after_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_path = dir.path().join("repo");
+        let repo = Repository::init(&repo_path).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "root", &tree, &[]).unwrap();
+
+        let mut state = AppState::new();
+        state.search_paths = vec![dir.path().to_path_buf()];
+        state.max_scan_depth = 2;
+        state.scan_workspaces();
+        // スキャン自体は数えず、要求として取り出す
+        assert_eq!(state.workspaces[0].recent_commits, 0);
+        let (paths, days) = state.take_recent_commits_request().unwrap();
+        assert_eq!(days, 7);
+        assert!(state.take_recent_commits_request().is_none());
+
+        let counts: Vec<(String, usize)> = paths
+            .iter()
+            .map(|path| (path.clone(), crate::workspace::count_recent_commits(Path::new(path), days)))
+            .collect();
+        state.set_recent_commits(&counts);
+        assert_eq!(state.workspaces[0].recent_commits, 1);
+
+        // 再スキャンしても数え直すまで前回の値を残す
+        state.scan_workspaces();
+        assert_eq!(state.workspaces[0].recent_commits, 1);
+        assert!(state.take_recent_commits_request().is_some());
+
+        state.activity_days = 0;
+        state.scan_workspaces();
+        assert!(state.take_recent_commits_request().is_none());
+    }
 }
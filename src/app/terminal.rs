@@ -0,0 +1,294 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use super::config::TerminalConfig;
+
+/// 新しいターミナルウィンドウでワークスペースを開く
+///
+/// `config.command` が設定されていればテンプレートとして `sh -c` で実行し、
+/// 未設定なら `$TERMINAL` または OS ごとの既定コマンドを使う。
+pub fn open_terminal_window(config: &TerminalConfig, cwd: &Path, command: Option<&str>) -> Result<()> {
+    let mut cmd = match &config.command {
+        Some(template) => {
+            let mut c = Command::new("sh");
+            c.args(["-c", &expand_template(template, cwd, command)]);
+            c
+        }
+        None => default_terminal_command(cwd, command),
+    };
+
+    cmd.current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to open terminal window")?;
+    Ok(())
+}
+
+/// テンプレートの `{path}` / `{command}` を置換（値はシェルクォート済み）
+fn expand_template(template: &str, cwd: &Path, command: Option<&str>) -> String {
+    let command = command.map(shell_quote).unwrap_or_default();
+    template
+        .replace("{path}", &shell_quote(&cwd.to_string_lossy()))
+        .replace("{command}", &command)
+}
+
+/// `sh -c` に渡すテンプレートのプレースホルダ（`{path}` など）を置換（値はシェルクォート済み）
+pub fn expand_quoted(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |acc, (placeholder, value)| acc.replace(placeholder, &shell_quote(value)))
+}
+
+/// OS ごとの既定ターミナル起動コマンド
+fn default_terminal_command(cwd: &Path, command: Option<&str>) -> Command {
+    if let Ok(terminal) = std::env::var("TERMINAL") {
+        return generic_terminal_command(&terminal, command);
+    }
+
+    if cfg!(target_os = "macos") {
+        // Terminal.app に cd + コマンドを実行させる
+        let mut script = format!("cd {}", shell_quote(&cwd.to_string_lossy()));
+        if let Some(c) = command {
+            script.push_str(" && ");
+            script.push_str(c);
+        }
+        let script = script.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut c = Command::new("osascript");
+        c.args([
+            "-e",
+            &format!("tell application \"Terminal\" to do script \"{}\"", script),
+            "-e",
+            "tell application \"Terminal\" to activate",
+        ]);
+        c
+    } else {
+        generic_terminal_command("x-terminal-emulator", command)
+    }
+}
+
+/// `<terminal> [-e sh -c <command>]` 形式（cwd は current_dir で渡す）
+fn generic_terminal_command(terminal: &str, command: Option<&str>) -> Command {
+    let mut c = Command::new(terminal);
+    if let Some(command) = command {
+        c.args(["-e", "sh", "-c", command]);
+    }
+    c
+}
+
+/// URLを既定のブラウザで開く（macOS は `open`、それ以外は `xdg-open`）
+pub fn open_in_browser(url: &str) -> Result<()> {
+    let program = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+    Command::new(program)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", program))?;
+    Ok(())
+}
+
+/// デスクトップ通知を表示（macOS は `osascript`、それ以外は `notify-send`）
+pub fn send_desktop_notification(title: &str, body: &str) -> Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut c = Command::new("osascript");
+        c.args([
+            "-e",
+            &format!("display notification \"{}\" with title \"{}\"", escape(body), escape(title)),
+        ]);
+        c
+    } else {
+        let mut c = Command::new("notify-send");
+        c.args([title, body]);
+        c
+    };
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to send desktop notification")?;
+    Ok(())
+}
+
+/// 端末のベルを鳴らす
+pub fn ring_bell() {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
+
+/// テキストをシステムクリップボードにコピー
+///
+/// macOS は `pbcopy`、それ以外は `wl-copy` → `xclip` → `xsel` の順に使えるものを試す。
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else {
+        &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    };
+
+    for (program, args) in candidates {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes()).context("Failed to write to clipboard command")?;
+        }
+        let status = child.wait().context("Failed to wait for clipboard command")?;
+        if status.success() {
+            return Ok(());
+        }
+    }
+    anyhow::bail!("No clipboard command available (pbcopy, wl-copy, xclip or xsel)")
+}
+
+/// 設定のエディタ・$VISUAL・$EDITOR が見つからないときに探す GUI エディタ（この後に端末エディタ）
+const GUI_EDITORS: &[&str] = &["code", "cursor", "zed", "subl"];
+const TERMINAL_EDITORS: &[&str] = &["nvim", "vim", "vi"];
+
+/// 使うエディタコマンドを決める
+///
+/// `configured` が見つからなければ `$VISUAL` → `$EDITOR` → code / cursor / zed / subl → nvim / vim / vi の順に、
+/// 実行ファイルがあるものを選ぶ（PATH は開くときに調べる）。
+pub fn resolve_editor(configured: &str) -> Result<String> {
+    let env_editors: Vec<String> = ["VISUAL", "EDITOR"].iter().filter_map(|var| std::env::var(var).ok()).collect();
+    let candidates = editor_candidates(configured, &env_editors);
+    candidates
+        .iter()
+        .find(|editor| editor.split_whitespace().next().is_some_and(is_executable))
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("No editor found (tried {})", candidates.join(", ")))
+}
+
+/// `path` をエディタで開く（GUI エディタ向け、起動したら待たない）
+///
+/// エディタの値は空白で区切ってコマンドと引数に分ける（`code -n` など）。
+pub fn open_in_editor(editor: &str, path: &Path) -> Result<()> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("Editor command is empty")?;
+    Command::new(program)
+        .args(parts)
+        .arg(path)
+        .spawn()
+        .with_context(|| format!("Failed to launch {}", editor))?;
+    Ok(())
+}
+
+/// 端末内で動くエディタか（`terminal_editors` の各項目とコマンド先頭の語が一致するか）
+///
+/// 項目は `"nvim"` のようなコマンド名か、`"emacs -nw"` のように引数まで含めたもの。
+pub fn is_terminal_editor(editor: &str, terminal_editors: &[String]) -> bool {
+    let words: Vec<&str> = editor.split_whitespace().collect();
+    let Some((program, args)) = words.split_first() else {
+        return false;
+    };
+    let program = Path::new(program).file_name().and_then(|n| n.to_str()).unwrap_or(program);
+    terminal_editors.iter().any(|entry| {
+        let mut entry_words = entry.split_whitespace();
+        entry_words.next() == Some(program) && entry_words.all(|word| args.contains(&word))
+    })
+}
+
+/// 試す順のエディタコマンド（重複・空を除く）
+///
+/// `env_editors` は `$VISUAL`, `$EDITOR` の値（設定済みのものだけ）。
+fn editor_candidates(configured: &str, env_editors: &[String]) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for editor in std::iter::once(configured.to_string())
+        .chain(env_editors.iter().cloned())
+        .chain(GUI_EDITORS.iter().map(|e| e.to_string()))
+        .chain(TERMINAL_EDITORS.iter().map(|e| e.to_string()))
+    {
+        let editor = editor.trim().to_string();
+        if !editor.is_empty() && !candidates.contains(&editor) {
+            candidates.push(editor);
+        }
+    }
+    candidates
+}
+
+/// 実行できるファイルか（パス指定ならその場所、コマンド名なら PATH 上を探す）
+fn is_executable(program: &str) -> bool {
+    if program.contains('/') {
+        Path::new(program).is_file()
+    } else {
+        crate::workspace::forge::command_available(program)
+    }
+}
+
+/// シングルクォートでシェルクォート
+pub(crate) fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_quoted() {
+        let result = expand_quoted("cd {path} && echo {branch}", &[("{path}", "/a b"), ("{branch}", "x'; rm -rf ~")]);
+        assert_eq!(result, "cd '/a b' && echo 'x'\\''; rm -rf ~'");
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let result = expand_template(
+            "kitty --directory {path} {command}",
+            Path::new("/work/repo=feat"),
+            Some("claude"),
+        );
+        assert_eq!(result, "kitty --directory '/work/repo=feat' 'claude'");
+    }
+
+    #[test]
+    fn test_expand_template_without_command() {
+        let result = expand_template("wezterm start --cwd {path} {command}", Path::new("/a b"), None);
+        assert_eq!(result, "wezterm start --cwd '/a b' ");
+    }
+
+    #[test]
+    fn test_editor_candidates_start_with_configured_and_end_with_vi() {
+        let candidates = editor_candidates(" code ", &[]);
+        assert_eq!(candidates.first().map(String::as_str), Some("code"));
+        assert_eq!(candidates.last().map(String::as_str), Some("vi"));
+        assert_eq!(candidates.get(1).map(String::as_str), Some("cursor"));
+        assert_eq!(editor_candidates("vi", &[]).iter().filter(|e| *e == "vi").count(), 1);
+
+        // $VISUAL / $EDITOR come right after the configured editor
+        let env_editors = vec!["hx".to_string(), "vi".to_string()];
+        let candidates = editor_candidates("code", &env_editors);
+        assert_eq!(&candidates[..4], ["code", "hx", "vi", "cursor"]);
+        assert_eq!(candidates.last().map(String::as_str), Some("vim"));
+    }
+
+    #[test]
+    fn test_is_terminal_editor() {
+        let terminal_editors = vec!["nvim".to_string(), "emacs -nw".to_string()];
+        assert!(is_terminal_editor("nvim", &terminal_editors));
+        assert!(is_terminal_editor("/usr/local/bin/nvim -p", &terminal_editors));
+        assert!(is_terminal_editor("emacs -nw", &terminal_editors));
+        assert!(!is_terminal_editor("emacs", &terminal_editors));
+        assert!(!is_terminal_editor("code", &terminal_editors));
+    }
+
+    #[test]
+    fn test_shell_quote_escapes_single_quote() {
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
@@ -305,7 +305,7 @@ pub fn extract_status_heuristic(log: &LogContent) -> SessionStatus {
                                 "text" => {
                                     if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
                                         if last_text.is_empty() && text.len() < 100 {
-                                            last_text = text.chars().take(50).collect();
+                                            last_text = text.to_string();
                                         }
                                     }
                                 }
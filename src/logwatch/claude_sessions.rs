@@ -10,11 +10,13 @@ use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::io::{Read as _, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use tracing::debug;
 
 use crate::workspace::claude_external_id;
 use super::collector::encode_project_path;
+use super::process::{normalize_path, ProcessProvider, SystemProcessProvider};
 
 /// Default inactivity threshold in seconds
 const DEFAULT_INACTIVITY_THRESHOLD_SECS: u64 = 60;
@@ -22,8 +24,8 @@ const DEFAULT_INACTIVITY_THRESHOLD_SECS: u64 = 60;
 /// Configuration for Claude sessions fetcher
 #[derive(Debug, Clone)]
 pub struct ClaudeSessionsConfig {
-    /// Path to the .claude directory
-    pub claude_dir: PathBuf,
+    /// Paths to the .claude directories (one per `CLAUDE_CONFIG_DIR` profile)
+    pub claude_dirs: Vec<PathBuf>,
     /// Inactivity threshold in seconds (sessions modified after this are considered active)
     pub inactivity_threshold_secs: u64,
 }
@@ -35,7 +37,7 @@ impl Default for ClaudeSessionsConfig {
             .unwrap_or_else(|| PathBuf::from("~/.claude"));
 
         Self {
-            claude_dir,
+            claude_dirs: vec![claude_dir],
             inactivity_threshold_secs: DEFAULT_INACTIVITY_THRESHOLD_SECS,
         }
     }
@@ -102,10 +104,40 @@ pub struct JsonlSessionState {
     pub last_user_input: Option<String>,
     /// Last tool name used
     pub last_tool_name: Option<String>,
+    /// File path from the input of the most recent tool call that had one (Read, Edit, Write, ...)
+    pub last_tool_file: Option<String>,
+    /// Todo list from the most recent TodoWrite call (None if none was found)
+    pub todos: Option<Vec<super::TodoItem>>,
+    /// Model of the most recent assistant message
+    pub model: Option<String>,
+    /// Token usage of the most recent assistant message
+    pub usage: Option<TokenUsage>,
     /// Inferred state detail
     pub state_detail: super::StatusDetail,
 }
 
+/// Token usage reported on an assistant message
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    /// Input tokens including cache reads and writes (the context size for this turn)
+    pub context_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Parse the `message.usage` object of a JSONL entry
+    fn from_json(usage: &serde_json::Value) -> Option<Self> {
+        let field = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+        let output_tokens = usage.get("output_tokens")?.as_u64()?;
+        Some(Self {
+            context_tokens: field("input_tokens")
+                + field("cache_creation_input_tokens")
+                + field("cache_read_input_tokens"),
+            output_tokens,
+        })
+    }
+}
+
 /// Claude Code session information
 #[derive(Debug, Clone)]
 pub struct ClaudeSession {
@@ -179,7 +211,15 @@ impl ClaudeSession {
                 last_activity: Some(self.modified),
                 progress: None,
                 error: None,
-                context: None,
+                context: Some(super::AnalysisContext {
+                    last_file: jsonl.last_tool_file.clone(),
+                    model: jsonl.model.clone(),
+                    context_tokens: jsonl.usage.map(|u| u.context_tokens),
+                    output_tokens: jsonl.usage.map(|u| u.output_tokens),
+                    ..Default::default()
+                })
+                .filter(|context| !context.is_empty()),
+                todos: jsonl.todos.clone(),
             };
         }
 
@@ -208,6 +248,7 @@ impl ClaudeSession {
             progress: None,
             error: None,
             context: None,
+            todos: None,
         }
     }
 }
@@ -281,6 +322,10 @@ fn parse_jsonl_tail(path: &Path, max_bytes: u64) -> Option<JsonlSessionState> {
     let mut last_assistant_text: Option<String> = None;
     let mut last_user_input: Option<String> = None;
     let mut last_tool_name: Option<String> = None;
+    let mut last_tool_file: Option<String> = None;
+    let mut todos: Option<Vec<super::TodoItem>> = None;
+    let mut model: Option<String> = None;
+    let mut usage: Option<TokenUsage> = None;
     let mut last_entry_type: Option<String> = None;
     let mut last_content_kind: Option<String> = None; // "tool_use", "text", "thinking"
 
@@ -300,8 +345,19 @@ fn parse_jsonl_tail(path: &Path, max_bytes: u64) -> Option<JsonlSessionState> {
 
         match entry_type {
             "assistant" => {
-                let content = value
-                    .get("message")
+                let message = value.get("message");
+                // Locally generated messages (e.g. API errors) report "<synthetic>"
+                if model.is_none() {
+                    model = message
+                        .and_then(|m| m.get("model"))
+                        .and_then(|v| v.as_str())
+                        .filter(|m| !m.starts_with('<'))
+                        .map(|s| s.to_string());
+                }
+                if usage.is_none() {
+                    usage = message.and_then(|m| m.get("usage")).and_then(TokenUsage::from_json);
+                }
+                let content = message
                     .and_then(|m| m.get("content"))
                     .and_then(|c| c.as_array());
 
@@ -316,6 +372,22 @@ fn parse_jsonl_tail(path: &Path, max_bytes: u64) -> Option<JsonlSessionState> {
                                         .and_then(|v| v.as_str())
                                         .map(|s| s.to_string());
                                 }
+                                if todos.is_none()
+                                    && item.get("name").and_then(|v| v.as_str()) == Some("TodoWrite")
+                                {
+                                    todos = item
+                                        .get("input")
+                                        .and_then(|input| input.get("todos"))
+                                        .and_then(|t| serde_json::from_value(t.clone()).ok());
+                                }
+                                if last_tool_file.is_none() {
+                                    last_tool_file = item.get("input").and_then(|input| {
+                                        ["file_path", "notebook_path", "path"]
+                                            .iter()
+                                            .find_map(|key| input.get(key).and_then(|v| v.as_str()))
+                                            .map(|s| s.to_string())
+                                    });
+                                }
                                 if last_content_kind.is_none() {
                                     last_content_kind = Some("tool_use".to_string());
                                     last_entry_type = Some("assistant".to_string());
@@ -329,8 +401,7 @@ fn parse_jsonl_tail(path: &Path, max_bytes: u64) -> Option<JsonlSessionState> {
                                         .unwrap_or("");
                                     let trimmed = text.trim();
                                     if !trimmed.is_empty() {
-                                        last_assistant_text =
-                                            Some(truncate_text(trimmed, 50));
+                                        last_assistant_text = Some(trimmed.to_string());
                                     }
                                 }
                                 if last_content_kind.is_none() {
@@ -368,7 +439,7 @@ fn parse_jsonl_tail(path: &Path, max_bytes: u64) -> Option<JsonlSessionState> {
                             if !trimmed.is_empty()
                                 && !trimmed.starts_with("[Request interrupted")
                             {
-                                last_user_input = Some(truncate_text(trimmed, 80));
+                                last_user_input = Some(trimmed.to_string());
                             }
                         }
                     }
@@ -399,6 +470,10 @@ fn parse_jsonl_tail(path: &Path, max_bytes: u64) -> Option<JsonlSessionState> {
         if last_assistant_text.is_some()
             && last_user_input.is_some()
             && last_tool_name.is_some()
+            && last_tool_file.is_some()
+            && todos.is_some()
+            && model.is_some()
+            && usage.is_some()
             && last_entry_type.is_some()
         {
             break;
@@ -428,47 +503,92 @@ fn parse_jsonl_tail(path: &Path, max_bytes: u64) -> Option<JsonlSessionState> {
         last_assistant_text,
         last_user_input,
         last_tool_name,
+        last_tool_file,
+        todos,
+        model,
+        usage,
         state_detail,
     })
 }
 
-/// Truncate text to max characters, appending "..." if truncated
-fn truncate_text(s: &str, max_chars: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count > max_chars {
-        let truncated: String = s.chars().take(max_chars.saturating_sub(3)).collect();
-        format!("{}...", truncated)
-    } else {
-        s.to_string()
-    }
+/// Path of `<claude dir>/todos/<session>-agent-<session>.json`, where Claude Code keeps the
+/// main agent's todo list (`project_dir` is `<claude dir>/projects/<encoded path>`)
+fn todo_file_path(project_dir: &Path, session_id: &str) -> Option<PathBuf> {
+    let claude_dir = project_dir.parent()?.parent()?;
+    Some(claude_dir.join("todos").join(format!("{}-agent-{}.json", session_id, session_id)))
 }
 
+/// Todo file contents keyed by path, with the mtime they were read at
+type TodoCache = HashMap<PathBuf, (SystemTime, Option<Vec<super::TodoItem>>)>;
+
 /// Fetches Claude Code sessions from sessions-index.json files
 pub struct ClaudeSessionsFetcher {
     config: ClaudeSessionsConfig,
+    processes: Arc<dyn ProcessProvider>,
+    /// Parsed todo files, re-read only when their mtime changes
+    todo_cache: Mutex<TodoCache>,
 }
 
 impl ClaudeSessionsFetcher {
     /// Create a new fetcher with default configuration
     pub fn new() -> Self {
-        Self {
-            config: ClaudeSessionsConfig::default(),
-        }
+        Self::with_config(ClaudeSessionsConfig::default())
     }
 
     /// Create a new fetcher with custom configuration
     pub fn with_config(config: ClaudeSessionsConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            processes: Arc::new(SystemProcessProvider::default()),
+            todo_cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Use a different source of running processes
+    pub fn with_process_provider(mut self, processes: Arc<dyn ProcessProvider>) -> Self {
+        self.processes = processes;
+        self
     }
 
-    /// Check if the Claude directory exists and is accessible
+    /// Read a session's todo file, reusing the last parse while its mtime is unchanged
+    fn read_todo_file(&self, project_dir: &Path, session_id: &str) -> Option<Vec<super::TodoItem>> {
+        let path = todo_file_path(project_dir, session_id)?;
+        let Ok(mut cache) = self.todo_cache.lock() else {
+            return None;
+        };
+        let Ok(mtime) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+            cache.remove(&path);
+            return None;
+        };
+        if let Some((cached_mtime, todos)) = cache.get(&path) {
+            if *cached_mtime == mtime {
+                return todos.clone();
+            }
+        }
+        let todos = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        cache.insert(path, (mtime, todos.clone()));
+        todos
+    }
+
+    /// Check if any Claude directory exists and is accessible
     pub fn is_available(&self) -> bool {
-        self.config.claude_dir.join("projects").exists()
+        self.projects_dirs().next().is_some()
+    }
+
+    /// Get the Claude directory paths
+    pub fn claude_dirs(&self) -> &[PathBuf] {
+        &self.config.claude_dirs
     }
 
-    /// Get the Claude directory path
-    pub fn claude_dir(&self) -> &PathBuf {
-        &self.config.claude_dir
+    /// Existing `projects` directories of the configured Claude directories
+    fn projects_dirs(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.config
+            .claude_dirs
+            .iter()
+            .map(|dir| dir.join("projects"))
+            .filter(|dir| dir.exists())
     }
 
     /// Get all running Claude processes with their session IDs
@@ -481,68 +601,7 @@ impl ClaudeSessionsFetcher {
 
     /// Get raw running Claude processes without subagent filtering
     fn get_running_processes_raw(&self) -> Vec<ClaudeProcessInfo> {
-        let mut processes = Vec::new();
-
-        // Find claude processes, get their pid, cwd, --resume argument, and ppid
-        // Only include processes with a TTY (not background/subprocess with tty=??)
-        let script = r#"
-            for pid in $(pgrep -x 'claude' 2>/dev/null); do
-                tty=$(ps -p $pid -o tty= 2>/dev/null | tr -d ' ')
-                # Skip background processes (tty is ?? or empty)
-                if [ "$tty" = "??" ] || [ -z "$tty" ]; then
-                    continue
-                fi
-                state=$(ps -p $pid -o state= 2>/dev/null | tr -d ' ')
-                if [ "$state" != "T" ] && [ -n "$state" ]; then
-                    cwd=$(lsof -p $pid 2>/dev/null | grep cwd | awk '{print $NF}')
-                    args=$(ps -p $pid -o args= 2>/dev/null)
-                    # Extract session ID from --resume argument
-                    session_id=$(echo "$args" | grep -oE '\-\-resume [a-f0-9-]+' | awk '{print $2}')
-                    ppid=$(ps -p $pid -o ppid= 2>/dev/null | tr -d ' ')
-                    if [ -n "$cwd" ]; then
-                        echo "${pid}|${cwd}|${session_id}|${ppid}"
-                    fi
-                fi
-            done
-        "#;
-
-        let output = std::process::Command::new("sh")
-            .arg("-c")
-            .arg(script)
-            .output();
-
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                for line in stdout.lines() {
-                    let line = line.trim();
-                    if line.is_empty() {
-                        continue;
-                    }
-                    let parts: Vec<&str> = line.splitn(4, '|').collect();
-                    let pid = parts.first().and_then(|s| s.parse::<u32>().ok());
-                    let cwd = normalize_path(parts.get(1).unwrap_or(&""));
-                    let session_id = parts.get(2).and_then(|s| {
-                        let s = s.trim();
-                        if s.is_empty() { None } else { Some(s.to_string()) }
-                    });
-                    let ppid = parts.get(3).and_then(|s| {
-                        let s = s.trim();
-                        if s.is_empty() { None } else { s.parse::<u32>().ok() }
-                    });
-                    if let Some(pid) = pid {
-                        if !cwd.is_empty() {
-                            processes.push(ClaudeProcessInfo { pid, cwd, session_id, ppid });
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                debug!("Failed to check Claude processes: {}", e);
-            }
-        }
-
-        processes
+        self.processes.claude_processes()
     }
 
     /// Get all workspaces where Claude Code is currently running
@@ -580,12 +639,7 @@ impl ClaudeSessionsFetcher {
         running.get(&normalized).copied().unwrap_or(0)
     }
 
-    /// Get sessions for specific workspace paths
-    ///
-    /// Uses a hybrid approach:
-    /// 1. Reads sessions-index.json for metadata (summary, branch, etc.)
-    /// 2. Scans JSONL files directly for recent activity (sessions-index.json may be stale)
-    /// 3. Merges both sources, preferring JSONL file scan for activity detection
+    /// Get sessions for specific workspace paths, merged across all Claude directories
     ///
     /// Returns a map of project_path -> Vec<ClaudeSession>
     pub fn get_sessions(&self, workspace_paths: &[String]) -> HashMap<String, Vec<ClaudeSession>> {
@@ -594,110 +648,23 @@ impl ClaudeSessionsFetcher {
             return HashMap::new();
         }
 
-        let projects_dir = self.config.claude_dir.join("projects");
         let mut results: HashMap<String, Vec<ClaudeSession>> = HashMap::new();
         let now = SystemTime::now();
 
         for workspace_path in workspace_paths {
             let normalized_wp = normalize_path(workspace_path);
             let encoded = encode_project_path(&normalized_wp);
-            let project_dir = projects_dir.join(&encoded);
 
-            if !project_dir.is_dir() {
-                continue;
-            }
-
-            // Build session metadata from sessions-index.json (if available)
-            let mut index_sessions: HashMap<String, ClaudeSession> = HashMap::new();
-            let index_path = project_dir.join("sessions-index.json");
-            if index_path.exists() {
-                if let Ok(index) = self.read_sessions_index(&index_path) {
-                    for entry in &index.entries {
-                        if let Some(session) = self.entry_to_session(entry, now) {
-                            index_sessions.insert(session.session_id.clone(), session);
-                        }
-                    }
-                }
-            }
-
-            // Scan JSONL files directly for recent sessions
-            // This catches sessions not yet in sessions-index.json
+            // Merge sessions from every configured Claude directory (first one wins on duplicates)
             let mut sessions: Vec<ClaudeSession> = Vec::new();
-            let mut seen_ids: HashSet<String> = HashSet::new();
-
-            if let Ok(dir_entries) = std::fs::read_dir(&project_dir) {
-                for entry in dir_entries.filter_map(|e| e.ok()) {
-                    let file_path = entry.path();
-                    // Only root-level .jsonl files (not subagent files in subdirectories)
-                    if !file_path.is_file() {
-                        continue;
-                    }
-                    let file_name = match file_path.file_name().and_then(|n| n.to_str()) {
-                        Some(name) if name.ends_with(".jsonl") && name != "sessions-index.json" => name,
-                        _ => continue,
-                    };
-
-                    // Extract session ID from filename (UUID.jsonl)
-                    let session_id = match file_name.strip_suffix(".jsonl") {
-                        Some(id) if id.len() >= 36 => id.to_string(),
-                        _ => continue,
-                    };
-
-                    if seen_ids.contains(&session_id) {
-                        continue;
-                    }
-                    seen_ids.insert(session_id.clone());
-
-                    // Check actual file modification time
-                    let file_mtime = match std::fs::metadata(&file_path).and_then(|m| m.modified()) {
-                        Ok(mtime) => mtime,
-                        Err(_) => continue,
-                    };
-
-                    let is_active = now
-                        .duration_since(file_mtime)
-                        .map(|d| d.as_secs() < self.config.inactivity_threshold_secs)
-                        .unwrap_or(false);
-
-                    // Parse JSONL tail for active sessions to get rich status
-                    let jsonl_state = if is_active {
-                        let state = parse_jsonl_tail(&file_path, JSONL_TAIL_MAX_BYTES);
-                        if let Some(ref s) = state {
-                            debug!(
-                                session_id = %session_id,
-                                state_detail = ?s.state_detail,
-                                last_tool = ?s.last_tool_name,
-                                last_text = ?s.last_assistant_text,
-                                "Parsed JSONL tail"
-                            );
-                        }
-                        state
-                    } else {
-                        None
-                    };
-
-                    // Use metadata from index if available, otherwise create minimal entry
-                    if let Some(mut indexed) = index_sessions.remove(&session_id) {
-                        // Update is_active based on actual file mtime (more reliable)
-                        indexed.is_active = is_active;
-                        indexed.jsonl_state = jsonl_state;
-                        sessions.push(indexed);
-                    } else {
-                        // Session not in index - create minimal entry from file info
-                        let modified_chrono = chrono::DateTime::<Utc>::from(file_mtime);
-                        let external_id = crate::workspace::claude_external_id(&session_id);
-                        sessions.push(ClaudeSession {
-                            session_id,
-                            external_id,
-                            project_path: normalized_wp.clone(),
-                            summary: None,
-                            message_count: 0,
-                            created: modified_chrono,
-                            modified: modified_chrono,
-                            git_branch: None,
-                            is_active,
-                            jsonl_state,
-                        });
+            for projects_dir in self.projects_dirs() {
+                let project_dir = projects_dir.join(&encoded);
+                if !project_dir.is_dir() {
+                    continue;
+                }
+                for session in self.read_project_dir(&project_dir, &normalized_wp, now) {
+                    if !sessions.iter().any(|s| s.session_id == session.session_id) {
+                        sessions.push(session);
                     }
                 }
             }
@@ -713,17 +680,131 @@ impl ClaudeSessionsFetcher {
         results
     }
 
+    /// Read the sessions of one project directory
+    ///
+    /// Uses a hybrid approach:
+    /// 1. Reads sessions-index.json for metadata (summary, branch, etc.)
+    /// 2. Scans JSONL files directly for recent activity (sessions-index.json may be stale)
+    /// 3. Merges both sources, preferring JSONL file scan for activity detection
+    fn read_project_dir(&self, project_dir: &Path, normalized_wp: &str, now: SystemTime) -> Vec<ClaudeSession> {
+        // Build session metadata from sessions-index.json (if available)
+        let mut index_sessions: HashMap<String, ClaudeSession> = HashMap::new();
+        let index_path = project_dir.join("sessions-index.json");
+        if index_path.exists() {
+            if let Ok(index) = self.read_sessions_index(&index_path) {
+                for entry in &index.entries {
+                    if let Some(session) = self.entry_to_session(entry, now) {
+                        index_sessions.insert(session.session_id.clone(), session);
+                    }
+                }
+            }
+        }
+
+        // Scan JSONL files directly for recent sessions
+        // This catches sessions not yet in sessions-index.json
+        let mut sessions: Vec<ClaudeSession> = Vec::new();
+        let mut seen_ids: HashSet<String> = HashSet::new();
+
+        if let Ok(dir_entries) = std::fs::read_dir(project_dir) {
+            for entry in dir_entries.filter_map(|e| e.ok()) {
+                let file_path = entry.path();
+                // Only root-level .jsonl files (not subagent files in subdirectories)
+                if !file_path.is_file() {
+                    continue;
+                }
+                let file_name = match file_path.file_name().and_then(|n| n.to_str()) {
+                    Some(name) if name.ends_with(".jsonl") && name != "sessions-index.json" => name,
+                    _ => continue,
+                };
+
+                // Extract session ID from filename (UUID.jsonl)
+                let session_id = match file_name.strip_suffix(".jsonl") {
+                    Some(id) if id.len() >= 36 => id.to_string(),
+                    _ => continue,
+                };
+
+                if seen_ids.contains(&session_id) {
+                    continue;
+                }
+                seen_ids.insert(session_id.clone());
+
+                // Check actual file modification time
+                let file_mtime = match std::fs::metadata(&file_path).and_then(|m| m.modified()) {
+                    Ok(mtime) => mtime,
+                    Err(_) => continue,
+                };
+
+                let is_active = now
+                    .duration_since(file_mtime)
+                    .map(|d| d.as_secs() < self.config.inactivity_threshold_secs)
+                    .unwrap_or(false);
+
+                // Parse JSONL tail for active sessions to get rich status
+                let jsonl_state = if is_active {
+                    let state = parse_jsonl_tail(&file_path, JSONL_TAIL_MAX_BYTES);
+                    if let Some(ref s) = state {
+                        debug!(
+                            session_id = %session_id,
+                            state_detail = ?s.state_detail,
+                            last_tool = ?s.last_tool_name,
+                            last_text = ?s.last_assistant_text,
+                            "Parsed JSONL tail"
+                        );
+                    }
+                    state
+                } else {
+                    None
+                };
+                // The TodoWrite call may be older than the parsed tail; fall back to the todo file
+                let jsonl_state = jsonl_state.map(|mut s| {
+                    if s.todos.is_none() {
+                        s.todos = self.read_todo_file(project_dir, &session_id);
+                    }
+                    s
+                });
+
+                // Use metadata from index if available, otherwise create minimal entry
+                if let Some(mut indexed) = index_sessions.remove(&session_id) {
+                    // Update is_active based on actual file mtime (more reliable)
+                    indexed.is_active = is_active;
+                    indexed.jsonl_state = jsonl_state;
+                    sessions.push(indexed);
+                } else {
+                    // Session not in index - create minimal entry from file info
+                    let modified_chrono = chrono::DateTime::<Utc>::from(file_mtime);
+                    let external_id = crate::workspace::claude_external_id(&session_id);
+                    sessions.push(ClaudeSession {
+                        session_id,
+                        external_id,
+                        project_path: normalized_wp.to_string(),
+                        summary: None,
+                        message_count: 0,
+                        created: modified_chrono,
+                        modified: modified_chrono,
+                        git_branch: None,
+                        is_active,
+                        jsonl_state,
+                    });
+                }
+            }
+        }
+
+        sessions
+    }
+
     /// Get all sessions (no filtering by workspace paths)
     pub fn get_all_sessions(&self) -> Vec<ClaudeSession> {
         if !self.is_available() {
             return Vec::new();
         }
 
-        let projects_dir = self.config.claude_dir.join("projects");
         let mut results = Vec::new();
         let now = SystemTime::now();
 
-        if let Ok(entries) = std::fs::read_dir(&projects_dir) {
+        for projects_dir in self.projects_dirs() {
+            let Ok(entries) = std::fs::read_dir(projects_dir) else {
+                continue;
+            };
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
                 if !path.is_dir() {
@@ -798,16 +879,6 @@ impl Default for ClaudeSessionsFetcher {
     }
 }
 
-/// Normalize a path by expanding ~ to home directory
-fn normalize_path(path: &str) -> String {
-    if path.starts_with("~/") {
-        if let Some(home) = std::env::var_os("HOME") {
-            return format!("{}{}", home.to_string_lossy(), &path[1..]);
-        }
-    }
-    path.to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -815,14 +886,33 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = ClaudeSessionsConfig::default();
-        assert!(config.claude_dir.to_string_lossy().contains(".claude"));
+        assert!(config.claude_dirs[0].to_string_lossy().contains(".claude"));
         assert_eq!(config.inactivity_threshold_secs, 60);
     }
 
     #[test]
     fn test_fetcher_creation() {
         let fetcher = ClaudeSessionsFetcher::new();
-        assert!(!fetcher.claude_dir().as_os_str().is_empty());
+        assert_eq!(fetcher.claude_dirs().len(), 1);
+    }
+
+    #[test]
+    fn test_get_sessions_merges_claude_dirs() {
+        let workspace = "/work/repo";
+        let dirs: Vec<tempfile::TempDir> = (0..2).map(|_| tempfile::tempdir().unwrap()).collect();
+        for (i, dir) in dirs.iter().enumerate() {
+            let project_dir = dir.path().join("projects").join(encode_project_path(workspace));
+            std::fs::create_dir_all(&project_dir).unwrap();
+            let id = format!("0000000{}-0000-0000-0000-000000000000", i);
+            std::fs::write(project_dir.join(format!("{}.jsonl", id)), "").unwrap();
+        }
+
+        let fetcher = ClaudeSessionsFetcher::with_config(ClaudeSessionsConfig {
+            claude_dirs: dirs.iter().map(|d| d.path().to_path_buf()).collect(),
+            ..Default::default()
+        });
+        let sessions = fetcher.get_sessions(&[workspace.to_string()]);
+        assert_eq!(sessions[workspace].len(), 2);
     }
 
     #[test]
@@ -980,6 +1070,74 @@ mod tests {
         assert_eq!(state.last_user_input.as_deref(), Some("Add authentication"));
     }
 
+    #[test]
+    fn test_parse_jsonl_tail_model_and_usage() {
+        let f = write_jsonl(&[
+            r#"{"type":"assistant","message":{"role":"assistant","model":"claude-sonnet-4-5","usage":{"input_tokens":10,"output_tokens":5},"content":[{"type":"text","text":"Earlier"}]}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","model":"claude-opus-4-1","usage":{"input_tokens":4,"cache_creation_input_tokens":100,"cache_read_input_tokens":20000,"output_tokens":250},"content":[{"type":"text","text":"Done"}]}}"#,
+        ]);
+        let state = parse_jsonl_tail(f.path(), 32768).unwrap();
+        assert_eq!(state.model.as_deref(), Some("claude-opus-4-1"));
+        assert_eq!(
+            state.usage,
+            Some(TokenUsage {
+                context_tokens: 20104,
+                output_tokens: 250
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_todo_file_cached_by_mtime() {
+        let claude_dir = tempfile::tempdir().unwrap();
+        let project_dir = claude_dir.path().join("projects").join("-src-app");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::create_dir_all(claude_dir.path().join("todos")).unwrap();
+        let path = todo_file_path(&project_dir, "s1").unwrap();
+        let fetcher = ClaudeSessionsFetcher::new();
+
+        assert!(fetcher.read_todo_file(&project_dir, "s1").is_none());
+
+        std::fs::write(&path, r#"[{"content":"Fix bug","status":"pending"}]"#).unwrap();
+        let mtime = std::fs::metadata(&path).unwrap().modified().unwrap();
+        assert_eq!(fetcher.read_todo_file(&project_dir, "s1").unwrap().len(), 1);
+
+        // Same mtime: the cached parse is reused even though the contents changed
+        std::fs::write(&path, "[]").unwrap();
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+        assert_eq!(fetcher.read_todo_file(&project_dir, "s1").unwrap().len(), 1);
+
+        // A new mtime re-reads the (now cleared) list
+        let later = mtime + Duration::from_secs(5);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert_eq!(fetcher.read_todo_file(&project_dir, "s1"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_parse_jsonl_tail_todos() {
+        let f = write_jsonl(&[
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"TodoWrite","id":"t1","input":{"todos":[{"content":"Old plan","status":"pending"}]}}]}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"TodoWrite","id":"t2","input":{"todos":[{"content":"Write tests","status":"completed","activeForm":"Writing tests"},{"content":"Fix bug","status":"in_progress","activeForm":"Fixing bug"}]}}]}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","id":"t3","input":{"command":"cargo test"}}]}}"#,
+        ]);
+        let state = parse_jsonl_tail(f.path(), 32768).unwrap();
+        let todos = state.todos.unwrap();
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[1].status, super::super::TodoState::InProgress);
+    }
+
+    #[test]
+    fn test_parse_jsonl_tail_last_tool_file() {
+        let f = write_jsonl(&[
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Edit","id":"t1","input":{"file_path":"/work/app/src/lib.rs"}}]}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","id":"t2","input":{"command":"cargo test"}}]}}"#,
+        ]);
+        let state = parse_jsonl_tail(f.path(), 32768).unwrap();
+        assert_eq!(state.last_tool_name.as_deref(), Some("Bash"));
+        // The latest call has no file, so the earlier Edit is used
+        assert_eq!(state.last_tool_file.as_deref(), Some("/work/app/src/lib.rs"));
+    }
+
     #[test]
     fn test_parse_jsonl_tail_assistant_text() {
         let f = write_jsonl(&[
@@ -1005,6 +1163,7 @@ mod tests {
         // Last entry is tool_result → assistant is processing (thinking)
         assert_eq!(state.state_detail, super::super::StatusDetail::Thinking);
         assert_eq!(state.last_tool_name.as_deref(), Some("Read"));
+        assert!(state.last_tool_file.is_none());
         assert_eq!(state.last_user_input.as_deref(), Some("Build the project"));
     }
 
@@ -1074,15 +1233,6 @@ mod tests {
         assert!(result.is_none());
     }
 
-    #[test]
-    fn test_truncate_text() {
-        assert_eq!(truncate_text("short", 50), "short");
-        let long = "a".repeat(60);
-        let truncated = truncate_text(&long, 50);
-        assert!(truncated.ends_with("..."));
-        assert!(truncated.chars().count() <= 50);
-    }
-
     #[test]
     fn test_to_session_status_with_jsonl_tool_use() {
         let session = ClaudeSession {
@@ -1099,6 +1249,10 @@ mod tests {
                 last_assistant_text: Some("Working on auth".to_string()),
                 last_user_input: Some("Add login".to_string()),
                 last_tool_name: Some("Bash".to_string()),
+                last_tool_file: None,
+                todos: None,
+                model: None,
+                usage: None,
                 state_detail: super::super::StatusDetail::ExecutingTool,
             }),
         };
@@ -1125,6 +1279,10 @@ mod tests {
                 last_assistant_text: Some("Adding authentication module".to_string()),
                 last_user_input: Some("Add auth".to_string()),
                 last_tool_name: None,
+                last_tool_file: None,
+                todos: None,
+                model: None,
+                usage: None,
                 state_detail: super::super::StatusDetail::Thinking,
             }),
         };
@@ -1150,6 +1308,10 @@ mod tests {
                 last_assistant_text: Some("Old text".to_string()),
                 last_user_input: None,
                 last_tool_name: None,
+                last_tool_file: None,
+                todos: None,
+                model: None,
+                usage: None,
                 state_detail: super::super::StatusDetail::Thinking,
             }),
         };
@@ -0,0 +1,514 @@
+//! Codex session status fetcher
+//!
+//! Codex writes each session as a JSONL "rollout" file under its home directory
+//! (`~/.codex`):
+//!
+//! - `sessions/YYYY/MM/DD/rollout-<timestamp>-<session_id>.jsonl`
+//! - the first line is a `session_meta` record (session ID and working directory)
+//! - later lines are `response_item` / `event_msg` records, appended as the session runs
+//!
+//! The newest records decide the state. Like OpenCode, sessions are only reported
+//! for workspaces with a running `codex` process.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{BufRead as _, BufReader, Read as _, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tracing::debug;
+
+use super::poller::ProcessMatchedFetcher;
+use super::process::{
+    count_by_cwd, normalize_path, workspace_limits, ProcessProvider, SystemProcessProvider,
+    DEFAULT_MAX_SESSIONS_PER_WORKSPACE,
+};
+use super::schema::{SessionStatus, StatusDetail, StatusState};
+
+/// Codex home directory, relative to the home directory
+const CODEX_HOME_DIR: &str = ".codex";
+
+/// Bytes read from the end of a rollout file to find its latest records
+const ROLLOUT_TAIL_MAX_BYTES: u64 = 32768;
+
+/// Configuration for Codex sessions fetcher
+#[derive(Debug, Clone)]
+pub struct CodexSessionsConfig {
+    /// Codex home directory (contains `sessions/`)
+    pub codex_home: PathBuf,
+    /// Upper bound on sessions returned per workspace, regardless of process count (0 = no cap)
+    pub max_sessions_per_workspace: usize,
+}
+
+impl Default for CodexSessionsConfig {
+    fn default() -> Self {
+        Self {
+            codex_home: default_codex_home(),
+            max_sessions_per_workspace: DEFAULT_MAX_SESSIONS_PER_WORKSPACE,
+        }
+    }
+}
+
+/// Default Codex home directory (`~/.codex`)
+pub fn default_codex_home() -> PathBuf {
+    directories::BaseDirs::new()
+        .map(|d| d.home_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join(CODEX_HOME_DIR)
+}
+
+/// One line of a rollout file
+#[derive(Debug, Deserialize)]
+struct RolloutLine {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    payload: serde_json::Value,
+}
+
+impl RolloutLine {
+    /// `payload.type` (the record subtype)
+    fn payload_type(&self) -> &str {
+        self.payload_str("type").unwrap_or_default()
+    }
+
+    fn payload_str(&self, key: &str) -> Option<&str> {
+        self.payload.get(key).and_then(|v| v.as_str())
+    }
+}
+
+/// `session_meta` payload (first line of a rollout)
+#[derive(Debug, Clone, Deserialize)]
+struct SessionMeta {
+    id: String,
+    cwd: String,
+}
+
+/// Codex session
+#[derive(Debug, Clone)]
+pub struct CodexSession {
+    /// Session ID (UUID)
+    pub session_id: String,
+    /// External ID (codex:{session_id})
+    pub external_id: String,
+    /// Project path (the directory Codex was started in)
+    pub project_path: String,
+    /// Last user prompt seen in the rollout tail
+    pub last_prompt: Option<String>,
+    /// Last update timestamp (rollout mtime)
+    pub modified: DateTime<Utc>,
+    /// State derived from the latest records
+    pub state: StatusState,
+    /// Detailed state derived from the latest records
+    pub state_detail: StatusDetail,
+    /// Error message, if the latest turn failed
+    pub error: Option<String>,
+}
+
+impl CodexSession {
+    /// Convert to SessionStatus for unified handling
+    pub fn to_session_status(&self) -> SessionStatus {
+        SessionStatus {
+            session_id: Some(self.session_id.clone()),
+            project_path: Some(self.project_path.clone()),
+            tool: Some("codex".to_string()),
+            status: self.state,
+            state_detail: self.state_detail.clone(),
+            summary: self.last_prompt.clone(),
+            last_activity: Some(self.modified),
+            error: self.error.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Fetches Codex session status from its rollout files
+pub struct CodexSessionsFetcher {
+    config: CodexSessionsConfig,
+    processes: Arc<dyn ProcessProvider>,
+    /// `session_meta` of each rollout file; it never changes, so every file is parsed once
+    meta_cache: Mutex<HashMap<PathBuf, SessionMeta>>,
+}
+
+impl CodexSessionsFetcher {
+    /// Create a new fetcher with default configuration
+    pub fn new() -> Self {
+        Self::with_config(CodexSessionsConfig::default())
+    }
+
+    /// Create a new fetcher with custom configuration
+    pub fn with_config(config: CodexSessionsConfig) -> Self {
+        Self {
+            config,
+            processes: Arc::new(SystemProcessProvider::default()),
+            meta_cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Use a different source of running processes
+    pub fn with_process_provider(mut self, processes: Arc<dyn ProcessProvider>) -> Self {
+        self.processes = processes;
+        self
+    }
+
+    /// Check if the Codex sessions directory exists
+    pub fn is_available(&self) -> bool {
+        self.sessions_dir().is_dir()
+    }
+
+    /// Get the Codex home directory
+    pub fn codex_home(&self) -> &PathBuf {
+        &self.config.codex_home
+    }
+
+    fn sessions_dir(&self) -> PathBuf {
+        self.config.codex_home.join("sessions")
+    }
+
+    /// Get running Codex workspaces with process count
+    pub fn get_running_codex_workspaces(&self) -> HashMap<String, usize> {
+        count_by_cwd(self.processes.codex_process_cwds())
+    }
+
+    /// Get statuses for multiple workspaces
+    ///
+    /// Each workspace with running `codex` processes gets its N most recently
+    /// written sessions (N = process count, capped by `max_sessions_per_workspace` unless it is 0).
+    pub fn get_statuses(&self, workspaces: &[String]) -> Vec<(String, CodexSession)> {
+        if !self.is_available() {
+            return Vec::new();
+        }
+
+        let running = self.get_running_codex_workspaces();
+        let limits = workspace_limits(workspaces, &running, self.config.max_sessions_per_workspace);
+        if limits.is_empty() {
+            return Vec::new();
+        }
+
+        let mut by_workspace: HashMap<String, Vec<Rollout>> = HashMap::new();
+        for rollout in self.read_rollouts() {
+            let cwd = normalize_path(&rollout.meta.cwd);
+            if limits.contains_key(&cwd) {
+                by_workspace.entry(cwd).or_default().push(rollout);
+            }
+        }
+
+        let mut results = Vec::new();
+        for workspace in workspaces {
+            let key = normalize_path(workspace);
+            let Some(&(_, limit)) = limits.get(&key) else {
+                continue;
+            };
+            let Some(mut rollouts) = by_workspace.remove(&key) else {
+                debug!("No Codex sessions found for workspace: {}", workspace);
+                continue;
+            };
+            rollouts.sort_by_key(|rollout| std::cmp::Reverse(rollout.modified));
+            for rollout in rollouts.into_iter().take(limit) {
+                results.push((workspace.clone(), rollout.into_session()));
+            }
+        }
+        results
+    }
+
+    /// Every rollout file with its `session_meta` (unreadable files are skipped)
+    fn read_rollouts(&self) -> Vec<Rollout> {
+        let Ok(mut cache) = self.meta_cache.lock() else {
+            return Vec::new();
+        };
+
+        let mut rollouts = Vec::new();
+        let mut fresh = HashMap::new();
+        for path in rollout_files(&self.sessions_dir()) {
+            let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+                continue;
+            };
+            let Some(meta) = cache.remove(&path).or_else(|| read_session_meta(&path)) else {
+                debug!("Failed to read Codex session_meta: {:?}", path);
+                continue;
+            };
+            fresh.insert(path.clone(), meta.clone());
+            rollouts.push(Rollout { path, modified, meta });
+        }
+        // Deleted rollouts drop out of the cache here
+        *cache = fresh;
+        rollouts
+    }
+}
+
+impl ProcessMatchedFetcher for CodexSessionsFetcher {
+    const NAME: &'static str = "Codex";
+
+    fn is_available(&self) -> bool {
+        self.is_available()
+    }
+
+    fn store_path(&self) -> PathBuf {
+        self.sessions_dir()
+    }
+
+    fn live_sessions(&self, workspaces: &[String]) -> Vec<(String, String, SessionStatus)> {
+        self.get_statuses(workspaces)
+            .into_iter()
+            .map(|(path, session)| {
+                let status = session.to_session_status();
+                (path, session.external_id, status)
+            })
+            .collect()
+    }
+}
+
+impl Default for CodexSessionsFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A rollout file found on disk
+struct Rollout {
+    path: PathBuf,
+    modified: SystemTime,
+    meta: SessionMeta,
+}
+
+impl Rollout {
+    /// Read the tail of the file and derive the session state from it
+    fn into_session(self) -> CodexSession {
+        let tail = read_tail(&self.path, ROLLOUT_TAIL_MAX_BYTES);
+        let (state, state_detail, error) = rollout_state(&tail);
+
+        CodexSession {
+            external_id: crate::workspace::codex_external_id(&self.meta.id),
+            session_id: self.meta.id,
+            project_path: normalize_path(&self.meta.cwd),
+            last_prompt: last_prompt(&tail),
+            modified: DateTime::<Utc>::from(self.modified),
+            state,
+            state_detail,
+            error,
+        }
+    }
+}
+
+/// `rollout-*.jsonl` files under `sessions/YYYY/MM/DD`
+fn rollout_files(sessions_dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![(sessions_dir.to_path_buf(), 0)];
+    while let Some((dir, depth)) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if depth < 3 {
+                    dirs.push((path, depth + 1));
+                }
+            } else if path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(".jsonl"))
+            {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// First line of a rollout, if it is a `session_meta` record
+fn read_session_meta(path: &Path) -> Option<SessionMeta> {
+    let mut first_line = String::new();
+    BufReader::new(std::fs::File::open(path).ok()?)
+        .read_line(&mut first_line)
+        .ok()?;
+    let line: RolloutLine = serde_json::from_str(&first_line).ok()?;
+    if line.kind != "session_meta" {
+        return None;
+    }
+    serde_json::from_value(line.payload).ok()
+}
+
+/// Complete records in the last `max_bytes` of a rollout (oldest first)
+fn read_tail(path: &Path, max_bytes: u64) -> Vec<RolloutLine> {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let start = len.saturating_sub(max_bytes);
+    let mut buf = Vec::new();
+    if file.seek(SeekFrom::Start(start)).is_err() || file.read_to_end(&mut buf).is_err() {
+        return Vec::new();
+    }
+    // Skip the first, partial line when the read started mid-file
+    String::from_utf8_lossy(&buf)
+        .lines()
+        .skip(usize::from(start > 0))
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// State of a session from its latest records (the newest recognised record wins)
+///
+/// - task started / user prompt / reasoning -> the model is working on it
+/// - tool call -> running a tool (`apply_patch` -> writing code)
+/// - approval request -> waiting for confirmation
+/// - task complete -> done, waiting for the next prompt
+/// - turn aborted -> stopped part way
+/// - error event -> API error
+fn rollout_state(lines: &[RolloutLine]) -> (StatusState, StatusDetail, Option<String>) {
+    for line in lines.iter().rev() {
+        let (state, detail) = match (line.kind.as_str(), line.payload_type()) {
+            ("event_msg", "task_complete") => (StatusState::Completed, StatusDetail::Success),
+            ("event_msg", "turn_aborted") => (StatusState::Completed, StatusDetail::Partial),
+            ("event_msg", "error") => {
+                let message = line.payload_str("message").map(str::to_string);
+                return (StatusState::Error, StatusDetail::ApiError, message);
+            }
+            ("event_msg", "exec_approval_request" | "apply_patch_approval_request") => {
+                (StatusState::Waiting, StatusDetail::Confirmation)
+            }
+            ("event_msg", "task_started" | "user_message" | "agent_reasoning")
+            | ("response_item", "reasoning") => (StatusState::Working, StatusDetail::Thinking),
+            ("response_item", "function_call" | "custom_tool_call" | "local_shell_call") => {
+                if line.payload_str("name") == Some("apply_patch") {
+                    (StatusState::Working, StatusDetail::WritingCode)
+                } else {
+                    (StatusState::Working, StatusDetail::ExecutingTool)
+                }
+            }
+            _ => continue,
+        };
+        return (state, detail, None);
+    }
+    (StatusState::Idle, StatusDetail::Inactive, None)
+}
+
+/// Last user prompt in the records
+fn last_prompt(lines: &[RolloutLine]) -> Option<String> {
+    lines
+        .iter()
+        .rev()
+        .filter(|line| line.kind == "event_msg" && line.payload_type() == "user_message")
+        .find_map(|line| line.payload_str("message"))
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logwatch::process::ScriptedProcessProvider;
+    use serde_json::json;
+
+    fn write_rollout(
+        home: &Path,
+        day: &str,
+        id: &str,
+        cwd: &str,
+        records: &[serde_json::Value],
+    ) -> PathBuf {
+        let dir = home.join("sessions").join(day);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("rollout-{}.jsonl", id));
+        let meta = json!({"type": "session_meta", "payload": {"id": id, "cwd": cwd}});
+        let lines: Vec<String> = std::iter::once(&meta)
+            .chain(records)
+            .map(|r| r.to_string())
+            .collect();
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+        path
+    }
+
+    fn event(payload: serde_json::Value) -> serde_json::Value {
+        json!({"type": "event_msg", "payload": payload})
+    }
+
+    fn fetcher(home: &Path, processes: Arc<ScriptedProcessProvider>) -> CodexSessionsFetcher {
+        CodexSessionsFetcher::with_config(CodexSessionsConfig {
+            codex_home: home.to_path_buf(),
+            ..Default::default()
+        })
+        .with_process_provider(processes)
+    }
+
+    #[test]
+    fn test_get_statuses_follows_running_processes() {
+        let home = tempfile::tempdir().unwrap();
+        let old = write_rollout(home.path(), "2025/01/01", "s-old", "/work/repo", &[]);
+        write_rollout(home.path(), "2025/01/02", "s-new", "/work/repo", &[]);
+        write_rollout(home.path(), "2025/01/02", "s-other", "/work/other", &[]);
+        let past = SystemTime::now() - std::time::Duration::from_secs(600);
+        std::fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+
+        let processes = Arc::new(ScriptedProcessProvider::default());
+        let fetcher = fetcher(home.path(), processes.clone());
+        let workspaces = vec!["/work/repo".to_string(), "/work/other".to_string()];
+
+        // No process: nothing is reported
+        assert!(fetcher.get_statuses(&workspaces).is_empty());
+
+        // One process: the most recently written session of that workspace
+        processes.set_codex(&["/work/repo"]);
+        let statuses = fetcher.get_statuses(&workspaces);
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].0, "/work/repo");
+        assert_eq!(statuses[0].1.external_id, "codex:s-new");
+
+        processes.set_codex(&["/work/repo", "/work/repo"]);
+        assert_eq!(fetcher.get_statuses(&workspaces).len(), 2);
+    }
+
+    #[test]
+    fn test_state_from_latest_records() {
+        let home = tempfile::tempdir().unwrap();
+        let processes = Arc::new(ScriptedProcessProvider::default());
+        processes.set_codex(&["/work/repo"]);
+        let fetcher = fetcher(home.path(), processes);
+        let workspaces = vec!["/work/repo".to_string()];
+        let status = |records: &[serde_json::Value]| {
+            write_rollout(home.path(), "2025/01/01", "s1", "/work/repo", records);
+            fetcher.get_statuses(&workspaces)[0].1.to_session_status()
+        };
+
+        assert_eq!(status(&[]).status, StatusState::Idle);
+
+        let prompt = event(json!({"type": "user_message", "message": "Fix the build"}));
+        let working = status(&[event(json!({"type": "task_started"})), prompt.clone()]);
+        assert_eq!(working.status, StatusState::Working);
+        assert_eq!(working.summary.as_deref(), Some("Fix the build"));
+
+        let patch = json!({"type": "response_item", "payload": {"type": "custom_tool_call", "name": "apply_patch"}});
+        assert_eq!(
+            status(&[prompt.clone(), patch]).state_detail,
+            StatusDetail::WritingCode
+        );
+
+        let approval = event(json!({"type": "exec_approval_request"}));
+        assert_eq!(
+            status(&[prompt.clone(), approval]).status,
+            StatusState::Waiting
+        );
+
+        // Records the state doesn't depend on (token counts) are skipped
+        let done = status(&[
+            prompt.clone(),
+            event(json!({"type": "task_complete"})),
+            event(json!({"type": "token_count"})),
+        ]);
+        assert_eq!(done.status, StatusState::Completed);
+
+        let error = status(&[
+            prompt,
+            event(json!({"type": "error", "message": "stream disconnected"})),
+        ]);
+        assert_eq!(error.status, StatusState::Error);
+        assert_eq!(error.error.as_deref(), Some("stream disconnected"));
+    }
+}
@@ -7,9 +7,13 @@ use anyhow::{Context, Result};
 use rusqlite::{Connection, OpenFlags};
 use serde::Deserialize;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
 
+use super::process::{
+    count_by_cwd, session_limit, ProcessProvider, SystemProcessProvider, DEFAULT_MAX_SESSIONS_PER_WORKSPACE,
+};
 use super::schema::{SessionStatus, StatusDetail, StatusState};
 
 /// Kiro SQLite database path on macOS
@@ -22,6 +26,12 @@ pub struct KiroSqliteConfig {
     pub db_path: PathBuf,
     /// Connection timeout in seconds
     pub timeout_secs: u64,
+    /// Open with `SQLITE_OPEN_READ_ONLY` instead of read-write + `PRAGMA query_only`
+    pub read_only: bool,
+    /// Upper bound on sessions returned per workspace, regardless of process count (0 = no cap)
+    pub max_sessions_per_workspace: usize,
+    /// Also return the latest conversation of workspaces without a running process (as idle)
+    pub show_idle_sessions: bool,
 }
 
 impl Default for KiroSqliteConfig {
@@ -33,6 +43,9 @@ impl Default for KiroSqliteConfig {
         Self {
             db_path,
             timeout_secs: 5,
+            read_only: false,
+            max_sessions_per_workspace: DEFAULT_MAX_SESSIONS_PER_WORKSPACE,
+            show_idle_sessions: false,
         }
     }
 }
@@ -76,7 +89,7 @@ impl KiroStatus {
 
     /// Generate external session ID for this Kiro session
     pub fn external_id(&self, project_path: &str) -> String {
-        format!("kiro:{}:{}", project_path, self.conversation_id)
+        crate::workspace::kiro_external_id(project_path, &self.conversation_id)
     }
 }
 
@@ -131,19 +144,27 @@ struct ToolUseResultsContent {
 /// Fetches Kiro CLI status from SQLite database
 pub struct KiroSqliteFetcher {
     config: KiroSqliteConfig,
+    processes: Arc<dyn ProcessProvider>,
 }
 
 impl KiroSqliteFetcher {
     /// Create a new fetcher with default configuration
     pub fn new() -> Self {
-        Self {
-            config: KiroSqliteConfig::default(),
-        }
+        Self::with_config(KiroSqliteConfig::default())
     }
 
     /// Create a new fetcher with custom configuration
     pub fn with_config(config: KiroSqliteConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            processes: Arc::new(SystemProcessProvider::default()),
+        }
+    }
+
+    /// Use a different source of running processes
+    pub fn with_process_provider(mut self, processes: Arc<dyn ProcessProvider>) -> Self {
+        self.processes = processes;
+        self
     }
 
     /// Check if the Kiro database exists and is accessible
@@ -156,52 +177,36 @@ impl KiroSqliteFetcher {
         &self.config.db_path
     }
 
-    /// Open a read-only connection to the database
+    /// Open a connection that never writes to the database
+    ///
+    /// Kiro keeps its database in WAL mode. A `SQLITE_OPEN_READ_ONLY` handle can't
+    /// set up the shared-memory index when it is missing, and may then read a stale
+    /// snapshot that lacks uncheckpointed writes. By default the database is therefore
+    /// opened read-write (never created) and locked down with `PRAGMA query_only`.
     fn open_connection(&self) -> Result<Connection> {
+        let mode = if self.config.read_only {
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+        } else {
+            OpenFlags::SQLITE_OPEN_READ_WRITE
+        };
         let conn = Connection::open_with_flags(
             &self.config.db_path,
-            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            mode | OpenFlags::SQLITE_OPEN_NO_MUTEX,
         )
         .context("Failed to open Kiro database")?;
 
         conn.busy_timeout(std::time::Duration::from_secs(self.config.timeout_secs))?;
+        if !self.config.read_only {
+            conn.pragma_update(None, "query_only", true)
+                .context("Failed to enable query_only")?;
+        }
 
         Ok(conn)
     }
 
     /// Get running Kiro workspaces with process count
     pub fn get_running_kiro_workspaces(&self) -> std::collections::HashMap<String, usize> {
-        let mut running: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-
-        // Find kiro-cli processes and get their cwd
-        let script = r#"
-            for pid in $(pgrep -x 'kiro-cli' 2>/dev/null); do
-                state=$(ps -p $pid -o state= 2>/dev/null | tr -d ' ')
-                if [ "$state" != "T" ] && [ -n "$state" ]; then
-                    lsof -p $pid 2>/dev/null | grep cwd | awk '{print $NF}'
-                fi
-            done
-        "#;
-
-        let output = std::process::Command::new("sh")
-            .arg("-c")
-            .arg(script)
-            .output();
-
-        match output {
-            Ok(out) => {
-                let stdout = String::from_utf8_lossy(&out.stdout);
-                for line in stdout.lines() {
-                    let cwd = line.trim();
-                    if !cwd.is_empty() {
-                        *running.entry(cwd.to_string()).or_insert(0) += 1;
-                    }
-                }
-            }
-            Err(_) => {}
-        }
-
-        running
+        count_by_cwd(self.processes.kiro_process_cwds())
     }
 
     /// Check if a Kiro CLI process is running for the given workspace
@@ -252,32 +257,64 @@ impl KiroSqliteFetcher {
     }
 
     /// Get statuses for multiple workspaces (returns all active sessions)
+    ///
+    /// Database errors are logged and yield an empty result; use
+    /// [`Self::try_get_statuses`] to observe them.
     pub fn get_statuses(&self, workspaces: &[String]) -> Vec<(String, KiroStatus)> {
+        match self.try_get_statuses(workspaces) {
+            Ok(results) => results,
+            Err(e) => {
+                warn!("Failed to open Kiro database: {:#}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Get statuses for multiple workspaces, failing if the database can't be read
+    ///
+    /// A missing database is not an error (Kiro simply isn't installed).
+    pub fn try_get_statuses(&self, workspaces: &[String]) -> Result<Vec<(String, KiroStatus)>> {
         if !self.is_available() {
-            return Vec::new();
+            return Ok(Vec::new());
         }
 
-        let conn = match self.open_connection() {
-            Ok(c) => c,
-            Err(e) => {
-                warn!("Failed to open Kiro database: {}", e);
-                return Vec::new();
-            }
-        };
+        let conn = self.open_connection()?;
+        // Opening is lazy: probe the schema so locked/corrupt files fail here
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+            .context("Kiro database is unreadable")?;
 
         // Get all running Kiro workspaces with process counts
         let running = self.get_running_kiro_workspaces();
 
+        Ok(self.collect_statuses_with_conn(&conn, workspaces, &running))
+    }
+
+    /// Collect statuses for the given workspaces from known process counts
+    ///
+    /// Separated from process discovery (`pgrep`/`lsof`) so the query path can be tested.
+    fn collect_statuses_with_conn(
+        &self,
+        conn: &Connection,
+        workspaces: &[String],
+        running: &std::collections::HashMap<String, usize>,
+    ) -> Vec<(String, KiroStatus)> {
         let mut results = Vec::new();
 
         for workspace in workspaces {
             // Get process count for this workspace
             let process_count = running.get(workspace).copied().unwrap_or(0);
             if process_count == 0 {
+                if self.config.show_idle_sessions {
+                    match self.get_idle_status_with_conn(conn, workspace) {
+                        Ok(Some(status)) => results.push((workspace.clone(), status)),
+                        Ok(None) => {}
+                        Err(e) => debug!("Failed to get idle status for {}: {}", workspace, e),
+                    }
+                }
                 continue;
             }
 
-            match self.get_all_statuses_with_conn(&conn, workspace, process_count) {
+            match self.get_all_statuses_with_conn(conn, workspace, process_count) {
                 Ok(statuses) => {
                     for status in statuses {
                         results.push((workspace.clone(), status));
@@ -298,8 +335,21 @@ impl KiroSqliteFetcher {
         Ok(statuses.into_iter().next())
     }
 
-    /// Get the N most recently updated sessions for a workspace (N = process_count)
+    /// Get the most recent conversation of a workspace with no running process, marked idle
+    fn get_idle_status_with_conn(&self, conn: &Connection, workspace_path: &str) -> Result<Option<KiroStatus>> {
+        let statuses = self.get_all_statuses_with_conn(conn, workspace_path, 1)?;
+        Ok(statuses.into_iter().next().map(|status| KiroStatus {
+            state: StatusState::Idle,
+            state_detail: StatusDetail::Inactive,
+            ..status
+        }))
+    }
+
+    /// Get the N most recently updated sessions for a workspace
+    /// (N = process_count, capped by `max_sessions_per_workspace` unless it is 0)
     fn get_all_statuses_with_conn(&self, conn: &Connection, workspace_path: &str, process_count: usize) -> Result<Vec<KiroStatus>> {
+        let limit = session_limit(process_count, self.config.max_sessions_per_workspace);
+
         // Get the most recent N sessions sorted by updated_at
         // When a session is resumed and a message is sent, updated_at is updated,
         // so it will appear in the most recent sessions
@@ -308,7 +358,7 @@ impl KiroSqliteFetcher {
         )?;
 
         let mut results = Vec::new();
-        let rows = stmt.query_map(rusqlite::params![workspace_path, process_count as i64], |row| {
+        let rows = stmt.query_map(rusqlite::params![workspace_path, limit as i64], |row| {
             Ok((
                 row.get::<_, String>(0)?,  // conversation_id
                 row.get::<_, String>(1)?,  // value
@@ -410,8 +460,8 @@ impl KiroSqliteFetcher {
                         }
                         if let Some(prompt) = &s.prompt {
                             // Processing user prompt
-                            let summary = prompt.prompt.as_ref()
-                                .map(|p| truncate_str(p, 30))
+                            let summary = prompt.prompt.as_deref()
+                                .map(first_line)
                                 .unwrap_or_else(|| "Thinking...".to_string());
                             return Ok((
                                 StatusState::Working,
@@ -434,7 +484,7 @@ impl KiroSqliteFetcher {
         // Try to get the content message first
         if let Some(content) = tool_use.get("content").and_then(|c| c.as_str()) {
             if !content.is_empty() {
-                return truncate_str(content, 40);
+                return first_line(content);
             }
         }
 
@@ -458,13 +508,11 @@ impl KiroSqliteFetcher {
         }
     }
 
-    /// Extract summary from Response (shows truncated response content)
+    /// Extract summary from Response (shows the first line of the response)
     fn extract_response_summary(&self, response: &serde_json::Value) -> String {
         if let Some(content) = response.get("content").and_then(|c| c.as_str()) {
             if !content.is_empty() {
-                // Get first line and truncate
-                let first_line = content.lines().next().unwrap_or(content);
-                return truncate_str(first_line, 40);
+                return first_line(content);
             }
         }
         "Done".to_string()
@@ -477,15 +525,9 @@ impl Default for KiroSqliteFetcher {
     }
 }
 
-/// Truncate string to max characters (not bytes) with ellipsis
-fn truncate_str(s: &str, max_chars: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_chars {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max_chars.saturating_sub(3)).collect();
-        format!("{}...", truncated)
-    }
+/// First line of a message (summaries are single-line; length is capped when displayed)
+fn first_line(s: &str) -> String {
+    s.lines().next().unwrap_or(s).to_string()
 }
 
 /// Helper module for home directory
@@ -512,9 +554,214 @@ mod tests {
     }
 
     #[test]
-    fn test_truncate_str() {
-        assert_eq!(truncate_str("hello", 10), "hello");
-        assert_eq!(truncate_str("hello world", 8), "hello...");
+    fn test_try_get_statuses_reports_unreadable_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("data.sqlite3");
+        std::fs::write(&db_path, b"this is not a sqlite database").unwrap();
+
+        let fetcher = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            db_path,
+            timeout_secs: 1,
+            read_only: false,
+            ..Default::default()
+        });
+        assert!(fetcher.try_get_statuses(&["/tmp".to_string()]).is_err());
+        assert!(fetcher.get_statuses(&["/tmp".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_connection_sees_uncheckpointed_wal_writes_and_is_query_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("data.sqlite3");
+
+        // Writer stays open so the WAL is not checkpointed into the main file
+        let writer = Connection::open(&db_path).unwrap();
+        writer.pragma_update(None, "journal_mode", "WAL").unwrap();
+        writer.pragma_update(None, "wal_autocheckpoint", 0).unwrap();
+        writer
+            .execute_batch(
+                "CREATE TABLE conversations_v2 (key TEXT, conversation_id TEXT, value TEXT, updated_at INTEGER);
+                 INSERT INTO conversations_v2 VALUES ('/work/repo', 'conv-1', '{\"history\": []}', 1000);",
+            )
+            .unwrap();
+
+        let fetcher = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            db_path,
+            timeout_secs: 1,
+            read_only: false,
+            ..Default::default()
+        });
+        let conn = fetcher.open_connection().unwrap();
+        let statuses = fetcher.get_all_statuses_with_conn(&conn, "/work/repo", 1).unwrap();
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].conversation_id, "conv-1");
+
+        // query_only must reject writes
+        assert!(conn.execute("DELETE FROM conversations_v2", []).is_err());
+    }
+
+    #[test]
+    fn test_session_limit_caps_process_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("data.sqlite3");
+        let writer = Connection::open(&db_path).unwrap();
+        writer
+            .execute_batch(
+                "CREATE TABLE conversations_v2 (key TEXT, conversation_id TEXT, value TEXT, updated_at INTEGER);
+                 INSERT INTO conversations_v2 VALUES ('/work/repo', 'a', '{}', 1), ('/work/repo', 'b', '{}', 2),
+                                                     ('/work/repo', 'c', '{}', 3), ('/work/repo', 'd', '{}', 4);",
+            )
+            .unwrap();
+
+        let fetcher = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            db_path,
+            timeout_secs: 1,
+            read_only: false,
+            max_sessions_per_workspace: 2,
+            show_idle_sessions: false,
+        });
+        let conn = fetcher.open_connection().unwrap();
+        let statuses = fetcher.get_all_statuses_with_conn(&conn, "/work/repo", 10).unwrap();
+        let ids: Vec<&str> = statuses.iter().map(|s| s.conversation_id.as_str()).collect();
+        assert_eq!(ids, vec!["d", "c"]);
+
+        // Fewer processes than the cap still limits the result
+        assert_eq!(fetcher.get_all_statuses_with_conn(&conn, "/work/repo", 1).unwrap().len(), 1);
+
+        // 0 removes the cap: one session per process
+        let uncapped = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            max_sessions_per_workspace: 0,
+            ..fetcher.config.clone()
+        });
+        assert_eq!(uncapped.get_all_statuses_with_conn(&conn, "/work/repo", 3).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_idle_status_keeps_summary_but_marks_idle() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("data.sqlite3");
+        let writer = Connection::open(&db_path).unwrap();
+        writer
+            .execute_batch(
+                r#"CREATE TABLE conversations_v2 (key TEXT, conversation_id TEXT, value TEXT, updated_at INTEGER);
+                   INSERT INTO conversations_v2 VALUES
+                     ('/work/repo', 'old', '{"history": []}', 1),
+                     ('/work/repo', 'new', '{"history": [{"assistant": {"Response": {"content": "All done"}}}]}', 2);"#,
+            )
+            .unwrap();
+
+        let fetcher = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            db_path,
+            show_idle_sessions: true,
+            ..Default::default()
+        });
+        let conn = fetcher.open_connection().unwrap();
+        let status = fetcher.get_idle_status_with_conn(&conn, "/work/repo").unwrap().unwrap();
+        assert_eq!(status.conversation_id, "new");
+        assert_eq!(status.state, StatusState::Idle);
+        assert_eq!(status.state_detail, StatusDetail::Inactive);
+        assert_eq!(status.summary.as_deref(), Some("All done"));
+
+        assert!(fetcher.get_idle_status_with_conn(&conn, "/work/other").unwrap().is_none());
+    }
+
+    /// Temp DB with one conversation per workspace, covering each status pattern
+    fn create_test_db(dir: &std::path::Path) -> PathBuf {
+        let db_path = dir.join("data.sqlite3");
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE conversations_v2 (key TEXT NOT NULL, conversation_id TEXT NOT NULL, value TEXT NOT NULL, created_at INTEGER, updated_at INTEGER NOT NULL);",
+        )
+        .unwrap();
+
+        let rows = [
+            ("/work/waiting", "conv-waiting", r#"{"history": [{"user": {"content": {"Prompt": {"prompt": "fix it"}}}, "assistant": {"ToolUse": {"tool_uses": [{"name": "fs_write"}]}}}]}"#),
+            ("/work/done", "conv-done", r#"{"history": [{"user": {"content": {"Prompt": {"prompt": "hi"}}}, "assistant": {"Response": {"content": "Hello\nthere"}}}]}"#),
+            ("/work/tools", "conv-tools", r#"{"history": [{"user": {"content": {"ToolUseResults": {"tool_use_results": []}}}}]}"#),
+            ("/work/thinking", "conv-thinking", r#"{"history": [{"user": {"content": {"Prompt": {"prompt": "refactor the parser"}}}}]}"#),
+            ("/work/empty", "conv-empty", r#"{"history": []}"#),
+            ("/work/broken", "conv-broken", "not json"),
+        ];
+        for (i, (key, id, value)) in rows.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO conversations_v2 (key, conversation_id, value, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+                rusqlite::params![key, id, value, i as i64, 1_700_000_000_000i64 + i as i64],
+            )
+            .unwrap();
+        }
+        db_path
+    }
+
+    #[test]
+    fn test_get_all_statuses_with_conn_parses_states() {
+        let dir = tempfile::tempdir().unwrap();
+        let fetcher = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            db_path: create_test_db(dir.path()),
+            ..Default::default()
+        });
+        let conn = fetcher.open_connection().unwrap();
+
+        let cases = [
+            ("/work/waiting", StatusState::Waiting, StatusDetail::Confirmation, Some("Confirm: fs_write")),
+            ("/work/done", StatusState::Completed, StatusDetail::Success, Some("Hello")),
+            ("/work/tools", StatusState::Working, StatusDetail::ExecutingTool, Some("Running tools...")),
+            ("/work/thinking", StatusState::Working, StatusDetail::Thinking, Some("refactor the parser")),
+            ("/work/empty", StatusState::Idle, StatusDetail::Inactive, None),
+        ];
+        for (workspace, state, detail, summary) in cases {
+            let statuses = fetcher.get_all_statuses_with_conn(&conn, workspace, 1).unwrap();
+            assert_eq!(statuses.len(), 1, "{}", workspace);
+            assert_eq!(statuses[0].state, state, "{}", workspace);
+            assert_eq!(statuses[0].state_detail, detail, "{}", workspace);
+            assert_eq!(statuses[0].summary.as_deref(), summary, "{}", workspace);
+        }
+
+        // Unparseable JSON is skipped rather than failing the query
+        assert!(fetcher.get_all_statuses_with_conn(&conn, "/work/broken", 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_collect_statuses_matches_running_workspaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let fetcher = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            db_path: create_test_db(dir.path()),
+            ..Default::default()
+        });
+        let conn = fetcher.open_connection().unwrap();
+
+        let workspaces: Vec<String> = ["/work/waiting", "/work/done", "/work/missing"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let running = std::collections::HashMap::from([
+            ("/work/waiting".to_string(), 1),
+            ("/work/missing".to_string(), 2),
+            // Not in the workspace list: must be ignored
+            ("/work/tools".to_string(), 1),
+        ]);
+
+        let results = fetcher.collect_statuses_with_conn(&conn, &workspaces, &running);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "/work/waiting");
+        assert_eq!(results[0].1.conversation_id, "conv-waiting");
+        assert_eq!(results[0].1.external_id(&results[0].0), "kiro:/work/waiting:conv-waiting");
+    }
+
+    #[test]
+    fn test_try_get_statuses_missing_db_is_ok() {
+        let fetcher = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            db_path: PathBuf::from("/nonexistent/kiro/data.sqlite3"),
+            timeout_secs: 1,
+            read_only: false,
+            ..Default::default()
+        });
+        assert!(fetcher.try_get_statuses(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_first_line() {
+        assert_eq!(first_line("hello\nworld"), "hello");
+        assert_eq!(first_line(""), "");
     }
 
     #[test]
@@ -1,21 +1,38 @@
 //! Log-based CLI status tracking via AI analysis
 //!
-//! This module provides functionality to monitor CLI tool logs (Claude Code, Kiro-CLI)
+//! This module provides functionality to monitor CLI tool logs (Claude Code, Kiro-CLI, OpenCode, Codex)
 //! and analyze them using AI to extract structured status information.
 //!
 //! ## Architecture
 //!
 //! - **Claude Code**: Uses sessions-index.json polling to read session status
 //! - **Kiro CLI**: Uses SQLite polling to read status from database
+//! - **OpenCode**: Polls the JSON session store under `~/.local/share/opencode/storage`
+//! - **Codex**: Polls the JSONL rollout files under `~/.codex/sessions`
+//! - **tmux** (opt-in): Captures windows opened by workspace-manager and applies the log heuristic
 
 pub mod analyzer;
 pub mod claude_sessions;
+pub mod codex;
 pub mod collector;
 pub mod kiro_sqlite;
+pub mod opencode;
+pub mod poller;
+pub mod process;
 pub mod schema;
+pub mod tmux_capture;
 
 pub use analyzer::LogAnalyzer;
 pub use claude_sessions::{ClaudeProcessInfo, ClaudeSession, ClaudeSessionsConfig, ClaudeSessionsFetcher};
+pub use codex::{CodexSession, CodexSessionsConfig, CodexSessionsFetcher};
 pub use collector::LogCollector;
 pub use kiro_sqlite::{KiroSqliteConfig, KiroSqliteFetcher, KiroStatus};
-pub use schema::{AnalysisProgress, SessionStatus, StatusDetail, StatusState};
+pub use opencode::{OpenCodeSession, OpenCodeSessionsConfig, OpenCodeSessionsFetcher};
+pub use poller::{ClaudePoller, KiroPoller, ProcessMatchedFetcher, SessionPoller};
+pub use process::{ProcessProvider, SystemProcessProvider};
+pub use tmux_capture::TmuxCapturePoller;
+pub use schema::{
+    todo_progress, truncate_text, AnalysisContext, AnalysisProgress, SessionStatus, StatusDetail, StatusState,
+    TextLimits, TodoItem, TodoState, DEFAULT_SUMMARY_MAX_LEN,
+    DEFAULT_TASK_MAX_LEN,
+};
@@ -0,0 +1,502 @@
+//! OpenCode session status fetcher
+//!
+//! OpenCode keeps its sessions as plain JSON files under its storage directory
+//! (`~/.local/share/opencode/storage`):
+//!
+//! - `session/<project_id>/<session_id>.json`: directory, title and timestamps
+//! - `message/<session_id>/<message_id>.json`: one file per message (role, completion time, error)
+//!
+//! Message IDs sort in creation order, so the last file of a session decides its state.
+//! Like Kiro, sessions are only reported for workspaces with a running `opencode` process.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tracing::debug;
+
+use super::poller::ProcessMatchedFetcher;
+use super::process::{
+    count_by_cwd, normalize_path, workspace_limits, ProcessProvider, SystemProcessProvider,
+    DEFAULT_MAX_SESSIONS_PER_WORKSPACE,
+};
+use super::schema::{SessionStatus, StatusDetail, StatusState};
+
+/// OpenCode storage directory, relative to the home directory
+const OPENCODE_STORAGE_DIR: &str = ".local/share/opencode/storage";
+
+/// Configuration for OpenCode sessions fetcher
+#[derive(Debug, Clone)]
+pub struct OpenCodeSessionsConfig {
+    /// OpenCode storage directory (contains `session/` and `message/`)
+    pub storage_dir: PathBuf,
+    /// Upper bound on sessions returned per workspace, regardless of process count (0 = no cap)
+    pub max_sessions_per_workspace: usize,
+}
+
+impl Default for OpenCodeSessionsConfig {
+    fn default() -> Self {
+        Self {
+            storage_dir: default_storage_dir(),
+            max_sessions_per_workspace: DEFAULT_MAX_SESSIONS_PER_WORKSPACE,
+        }
+    }
+}
+
+/// Default OpenCode storage directory (`~/.local/share/opencode/storage`)
+pub fn default_storage_dir() -> PathBuf {
+    directories::BaseDirs::new()
+        .map(|d| d.home_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join(OPENCODE_STORAGE_DIR)
+}
+
+/// Session file (`session/<project_id>/<session_id>.json`)
+#[derive(Debug, Clone, Deserialize)]
+struct SessionFile {
+    id: String,
+    directory: String,
+    #[serde(default)]
+    title: Option<String>,
+    /// Set on sessions spawned by a subagent (task tool)
+    #[serde(rename = "parentID", default)]
+    parent_id: Option<String>,
+    #[serde(default)]
+    time: SessionTime,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SessionTime {
+    /// Milliseconds since the epoch
+    #[serde(default)]
+    created: i64,
+    /// Milliseconds since the epoch
+    #[serde(default)]
+    updated: i64,
+}
+
+/// Message file (`message/<session_id>/<message_id>.json`)
+#[derive(Debug, Deserialize)]
+struct MessageFile {
+    role: String,
+    #[serde(default)]
+    time: MessageTime,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessageTime {
+    /// Set once the assistant has finished the message
+    #[serde(default)]
+    completed: Option<i64>,
+}
+
+/// OpenCode session
+#[derive(Debug, Clone)]
+pub struct OpenCodeSession {
+    /// Session ID (`ses_...`)
+    pub session_id: String,
+    /// External ID (opencode:{session_id})
+    pub external_id: String,
+    /// Project path (the directory OpenCode was started in)
+    pub project_path: String,
+    /// Session title
+    pub title: Option<String>,
+    /// Created timestamp
+    pub created: DateTime<Utc>,
+    /// Last update timestamp
+    pub modified: DateTime<Utc>,
+    /// State derived from the latest message
+    pub state: StatusState,
+    /// Detailed state derived from the latest message
+    pub state_detail: StatusDetail,
+    /// Error message of the latest message, if it failed
+    pub error: Option<String>,
+}
+
+impl OpenCodeSession {
+    /// Convert to SessionStatus for unified handling
+    pub fn to_session_status(&self) -> SessionStatus {
+        SessionStatus {
+            session_id: Some(self.session_id.clone()),
+            project_path: Some(self.project_path.clone()),
+            tool: Some("opencode".to_string()),
+            status: self.state,
+            state_detail: self.state_detail.clone(),
+            summary: self.title.clone(),
+            last_activity: Some(self.modified),
+            error: self.error.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Fetches OpenCode session status from its storage directory
+pub struct OpenCodeSessionsFetcher {
+    config: OpenCodeSessionsConfig,
+    processes: Arc<dyn ProcessProvider>,
+    /// Parsed session files with the mtime they were read at; unchanged files aren't re-parsed
+    session_cache: Mutex<HashMap<PathBuf, (SystemTime, SessionFile)>>,
+}
+
+impl OpenCodeSessionsFetcher {
+    /// Create a new fetcher with default configuration
+    pub fn new() -> Self {
+        Self::with_config(OpenCodeSessionsConfig::default())
+    }
+
+    /// Create a new fetcher with custom configuration
+    pub fn with_config(config: OpenCodeSessionsConfig) -> Self {
+        Self {
+            config,
+            processes: Arc::new(SystemProcessProvider::default()),
+            session_cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Use a different source of running processes
+    pub fn with_process_provider(mut self, processes: Arc<dyn ProcessProvider>) -> Self {
+        self.processes = processes;
+        self
+    }
+
+    /// Check if the OpenCode session store exists
+    pub fn is_available(&self) -> bool {
+        self.config.storage_dir.join("session").is_dir()
+    }
+
+    /// Get the storage directory
+    pub fn storage_dir(&self) -> &PathBuf {
+        &self.config.storage_dir
+    }
+
+    /// Get running OpenCode workspaces with process count
+    pub fn get_running_opencode_workspaces(&self) -> HashMap<String, usize> {
+        count_by_cwd(self.processes.opencode_process_cwds())
+    }
+
+    /// Get statuses for multiple workspaces
+    ///
+    /// Each workspace with running `opencode` processes gets its N most recently
+    /// updated sessions (N = process count, capped by `max_sessions_per_workspace` unless it is 0).
+    pub fn get_statuses(&self, workspaces: &[String]) -> Vec<(String, OpenCodeSession)> {
+        if !self.is_available() {
+            return Vec::new();
+        }
+
+        let running = self.get_running_opencode_workspaces();
+        let limits = workspace_limits(workspaces, &running, self.config.max_sessions_per_workspace);
+        if limits.is_empty() {
+            return Vec::new();
+        }
+
+        let mut by_workspace: HashMap<&String, Vec<SessionFile>> = HashMap::new();
+        for file in self.read_session_files() {
+            if file.parent_id.is_some() {
+                continue;
+            }
+            if let Some((workspace, _)) = limits.get(&normalize_path(&file.directory)) {
+                by_workspace.entry(workspace).or_default().push(file);
+            }
+        }
+
+        let mut results = Vec::new();
+        for workspace in workspaces {
+            let Some(&(_, limit)) = limits.get(&normalize_path(workspace)) else {
+                continue;
+            };
+            let Some(mut files) = by_workspace.remove(workspace) else {
+                debug!("No OpenCode sessions found for workspace: {}", workspace);
+                continue;
+            };
+            files.sort_by_key(|file| std::cmp::Reverse(file.time.updated));
+            for file in files.into_iter().take(limit) {
+                results.push((workspace.clone(), self.to_session(file)));
+            }
+        }
+        results
+    }
+
+    /// All session files in the store (unreadable files are skipped)
+    ///
+    /// Only files whose mtime changed since the last call are parsed again.
+    fn read_session_files(&self) -> Vec<SessionFile> {
+        let Ok(projects) = std::fs::read_dir(self.config.storage_dir.join("session")) else {
+            return Vec::new();
+        };
+        let Ok(mut cache) = self.session_cache.lock() else {
+            return Vec::new();
+        };
+
+        let mut fresh = HashMap::new();
+        for project in projects.flatten() {
+            let Ok(entries) = std::fs::read_dir(project.path()) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().is_none_or(|ext| ext != "json") {
+                    continue;
+                }
+                let Ok(mtime) = entry.metadata().and_then(|m| m.modified()) else {
+                    continue;
+                };
+                match cache.remove(&path) {
+                    Some((cached_mtime, file)) if cached_mtime == mtime => {
+                        fresh.insert(path, (mtime, file));
+                    }
+                    _ => match read_json::<SessionFile>(&path) {
+                        Some(file) => {
+                            fresh.insert(path, (mtime, file));
+                        }
+                        None => debug!("Failed to parse OpenCode session file: {:?}", path),
+                    },
+                }
+            }
+        }
+        // Deleted sessions drop out of the cache here
+        *cache = fresh;
+        cache.values().map(|(_, file)| file.clone()).collect()
+    }
+
+    fn to_session(&self, file: SessionFile) -> OpenCodeSession {
+        let (state, state_detail, error) = match self.latest_message(&file.id) {
+            Some(message) => {
+                let (state, detail) = message_state(&message);
+                (state, detail, message.error.as_ref().and_then(error_message))
+            }
+            None => (StatusState::Idle, StatusDetail::Inactive, None),
+        };
+        let title = file.title.filter(|t| !t.trim().is_empty());
+
+        OpenCodeSession {
+            external_id: crate::workspace::opencode_external_id(&file.id),
+            session_id: file.id,
+            project_path: normalize_path(&file.directory),
+            title,
+            created: from_millis(file.time.created),
+            modified: from_millis(file.time.updated),
+            state,
+            state_detail,
+            error,
+        }
+    }
+
+    /// Latest message of a session (message IDs sort in creation order)
+    fn latest_message(&self, session_id: &str) -> Option<MessageFile> {
+        let dir = self.config.storage_dir.join("message").join(session_id);
+        let latest = std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .max()?;
+        read_json(&latest)
+    }
+}
+
+impl ProcessMatchedFetcher for OpenCodeSessionsFetcher {
+    const NAME: &'static str = "OpenCode";
+
+    fn is_available(&self) -> bool {
+        self.is_available()
+    }
+
+    fn store_path(&self) -> PathBuf {
+        self.config.storage_dir.clone()
+    }
+
+    fn live_sessions(&self, workspaces: &[String]) -> Vec<(String, String, SessionStatus)> {
+        self.get_statuses(workspaces)
+            .into_iter()
+            .map(|(path, session)| {
+                let status = session.to_session_status();
+                (path, session.external_id, status)
+            })
+            .collect()
+    }
+}
+
+impl Default for OpenCodeSessionsFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of a session whose latest message is `message`
+///
+/// - user message -> the model is working on it
+/// - assistant message without completion time -> still generating / running tools
+/// - assistant message aborted by the user -> stopped part way
+/// - assistant message with any other error (API / provider failure) -> API error
+/// - completed assistant message -> done, waiting for the next prompt
+fn message_state(message: &MessageFile) -> (StatusState, StatusDetail) {
+    if message.role != "assistant" {
+        return (StatusState::Working, StatusDetail::Thinking);
+    }
+    if let Some(error) = &message.error {
+        if error.get("name").and_then(|n| n.as_str()) == Some("MessageAbortedError") {
+            return (StatusState::Completed, StatusDetail::Partial);
+        }
+        return (StatusState::Error, StatusDetail::ApiError);
+    }
+    match message.time.completed {
+        Some(_) => (StatusState::Completed, StatusDetail::Success),
+        None => (StatusState::Working, StatusDetail::ExecutingTool),
+    }
+}
+
+/// Human-readable text of a message error (`data.message`, else the error name)
+fn error_message(error: &serde_json::Value) -> Option<String> {
+    error
+        .pointer("/data/message")
+        .or_else(|| error.get("name"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn from_millis(ms: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(ms).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logwatch::process::ScriptedProcessProvider;
+
+    fn write_session(storage: &Path, id: &str, directory: &str, updated: i64, parent: Option<&str>) {
+        let dir = storage.join("session").join("proj");
+        std::fs::create_dir_all(&dir).unwrap();
+        let json = serde_json::json!({
+            "id": id,
+            "directory": directory,
+            "title": format!("Title of {}", id),
+            "parentID": parent,
+            "time": { "created": 1, "updated": updated },
+        });
+        std::fs::write(dir.join(format!("{}.json", id)), json.to_string()).unwrap();
+    }
+
+    fn write_message(storage: &Path, session_id: &str, message_id: &str, json: serde_json::Value) {
+        let dir = storage.join("message").join(session_id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{}.json", message_id)), json.to_string()).unwrap();
+    }
+
+    fn fetcher(storage: &Path, processes: Arc<ScriptedProcessProvider>) -> OpenCodeSessionsFetcher {
+        OpenCodeSessionsFetcher::with_config(OpenCodeSessionsConfig {
+            storage_dir: storage.to_path_buf(),
+            ..Default::default()
+        })
+        .with_process_provider(processes)
+    }
+
+    #[test]
+    fn test_get_statuses_follows_running_processes() {
+        let storage = tempfile::tempdir().unwrap();
+        write_session(storage.path(), "ses_old", "/work/repo", 100, None);
+        write_session(storage.path(), "ses_new", "/work/repo", 200, None);
+        write_session(storage.path(), "ses_child", "/work/repo", 300, Some("ses_new"));
+        write_session(storage.path(), "ses_other", "/work/other", 400, None);
+
+        let processes = Arc::new(ScriptedProcessProvider::default());
+        let fetcher = fetcher(storage.path(), processes.clone());
+        let workspaces = vec!["/work/repo".to_string(), "/work/other".to_string()];
+
+        // No process: nothing is reported
+        assert!(fetcher.get_statuses(&workspaces).is_empty());
+
+        // One process: the newest top-level session of that workspace
+        processes.set_opencode(&["/work/repo"]);
+        let statuses = fetcher.get_statuses(&workspaces);
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].0, "/work/repo");
+        assert_eq!(statuses[0].1.external_id, "opencode:ses_new");
+        assert_eq!(statuses[0].1.title.as_deref(), Some("Title of ses_new"));
+
+        // Two processes: two sessions
+        processes.set_opencode(&["/work/repo", "/work/repo"]);
+        assert_eq!(fetcher.get_statuses(&workspaces).len(), 2);
+    }
+
+    #[test]
+    fn test_session_files_reparsed_only_when_modified() {
+        let storage = tempfile::tempdir().unwrap();
+        write_session(storage.path(), "ses_1", "/work/repo", 100, None);
+        let fetcher = fetcher(storage.path(), Arc::new(ScriptedProcessProvider::default()));
+        assert_eq!(fetcher.read_session_files()[0].time.updated, 100);
+
+        // Rewritten with the same mtime: the cached parse is used
+        let path = storage.path().join("session/proj/ses_1.json");
+        let mtime = std::fs::metadata(&path).unwrap().modified().unwrap();
+        write_session(storage.path(), "ses_1", "/work/repo", 200, None);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+        assert_eq!(fetcher.read_session_files()[0].time.updated, 100);
+
+        let later = mtime + std::time::Duration::from_secs(5);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert_eq!(fetcher.read_session_files()[0].time.updated, 200);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(fetcher.read_session_files().is_empty());
+    }
+
+    #[test]
+    fn test_state_from_latest_message() {
+        let storage = tempfile::tempdir().unwrap();
+        write_session(storage.path(), "ses_1", "/work/repo", 100, None);
+        let processes = Arc::new(ScriptedProcessProvider::default());
+        processes.set_opencode(&["/work/repo"]);
+        let fetcher = fetcher(storage.path(), processes);
+        let workspaces = vec!["/work/repo".to_string()];
+        let state = || fetcher.get_statuses(&workspaces)[0].1.to_session_status().status;
+
+        // No messages yet
+        assert_eq!(state(), StatusState::Idle);
+
+        write_message(storage.path(), "ses_1", "msg_01", serde_json::json!({"role": "user", "time": {}}));
+        assert_eq!(state(), StatusState::Working);
+
+        write_message(storage.path(), "ses_1", "msg_02", serde_json::json!({"role": "assistant", "time": {"created": 1}}));
+        assert_eq!(state(), StatusState::Working);
+
+        write_message(
+            storage.path(),
+            "ses_1",
+            "msg_03",
+            serde_json::json!({"role": "assistant", "time": {"created": 1, "completed": 2}}),
+        );
+        assert_eq!(state(), StatusState::Completed);
+
+        write_message(
+            storage.path(),
+            "ses_1",
+            "msg_04",
+            serde_json::json!({
+                "role": "assistant",
+                "time": {"created": 3},
+                "error": {"name": "APIError", "data": {"message": "Overloaded", "isRetryable": true}},
+            }),
+        );
+        let status = fetcher.get_statuses(&workspaces)[0].1.to_session_status();
+        assert_eq!(status.status, StatusState::Error);
+        assert_eq!(status.state_detail, StatusDetail::ApiError);
+        assert_eq!(status.error.as_deref(), Some("Overloaded"));
+
+        write_message(
+            storage.path(),
+            "ses_1",
+            "msg_05",
+            serde_json::json!({"role": "assistant", "time": {"created": 4}, "error": {"name": "MessageAbortedError"}}),
+        );
+        assert_eq!(state(), StatusState::Completed);
+    }
+}
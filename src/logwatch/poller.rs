@@ -0,0 +1,403 @@
+//! Poll-cycle logic shared by the Claude, Kiro, OpenCode and Codex polling tasks
+//!
+//! Each poller turns one snapshot of running processes + session data into
+//! [`AppEvent`]s, remembering which sessions were active on the previous cycle
+//! so sessions whose process went away are unregistered. A cycle can also be
+//! scoped to a single workspace (on-demand refresh) without touching sessions
+//! elsewhere.
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::app::AppEvent;
+
+use super::claude_sessions::{ClaudeProcessInfo, ClaudeSession, ClaudeSessionsFetcher};
+use super::kiro_sqlite::KiroSqliteFetcher;
+use super::schema::{SessionStatus, StatusState};
+
+/// Claude Code poll state (sessions-index.json / JSONL + running processes)
+pub struct ClaudePoller {
+    fetcher: ClaudeSessionsFetcher,
+    /// Sessions reported active on the previous cycle (external_id -> workspace path)
+    prev_active_sessions: HashMap<String, String>,
+}
+
+impl ClaudePoller {
+    pub fn new(fetcher: ClaudeSessionsFetcher) -> Self {
+        Self {
+            fetcher,
+            prev_active_sessions: HashMap::new(),
+        }
+    }
+
+    pub fn fetcher(&self) -> &ClaudeSessionsFetcher {
+        &self.fetcher
+    }
+
+    /// Run one poll cycle: status updates for live sessions, then unregistrations
+    pub fn poll(&mut self, workspaces: &[String]) -> Vec<AppEvent> {
+        self.poll_scoped(workspaces, None)
+    }
+
+    /// Run a poll cycle for a single workspace, leaving other sessions alone
+    pub fn poll_workspace(&mut self, path: &str) -> Vec<AppEvent> {
+        self.poll_scoped(&[path.to_string()], Some(path))
+    }
+
+    fn poll_scoped(&mut self, workspaces: &[String], scope: Option<&str>) -> Vec<AppEvent> {
+        // Get running Claude processes with their session IDs
+        let running_processes = self.fetcher.get_running_processes();
+
+        // Fetch sessions from Claude sessions-index.json
+        let sessions_by_path = self.fetcher.get_sessions(workspaces);
+        let mut current_active_sessions: HashMap<String, String> = HashMap::new();
+        let mut events = Vec::new();
+
+        for (path, sessions) in &sessions_by_path {
+            let normalized_path = normalize_path_for_comparison(path);
+
+            // Get workspace processes (already subagent-filtered)
+            let workspace_processes: Vec<&ClaudeProcessInfo> = running_processes.iter()
+                .filter(|p| p.cwd == normalized_path)
+                .collect();
+
+            // Unique session IDs from running processes
+            let unique_session_ids: HashSet<&str> = workspace_processes.iter()
+                .filter_map(|p| p.session_id.as_deref())
+                .collect();
+
+            // Count processes without session ID (cap at 1 - unlikely to have multiple new sessions simultaneously)
+            let processes_without_id = workspace_processes.iter()
+                .filter(|p| p.session_id.is_none())
+                .count()
+                .min(1);
+
+            let effective_session_count = unique_session_ids.len() + processes_without_id;
+
+            tracing::debug!(
+                "Claude session matching for {}: total_processes={}, unique_ids={}, without_id={}, effective={}",
+                normalized_path,
+                workspace_processes.len(),
+                unique_session_ids.len(),
+                processes_without_id,
+                effective_session_count,
+            );
+
+            if effective_session_count == 0 {
+                continue;
+            }
+
+            // Two-pass matching: first exact ID matches, then fill remaining slots with newest
+            let mut id_matched: Vec<&ClaudeSession> = Vec::new();
+            let mut unmatched: Vec<&ClaudeSession> = Vec::new();
+
+            for session in sessions {
+                if unique_session_ids.contains(session.session_id.as_str()) {
+                    id_matched.push(session);
+                } else {
+                    unmatched.push(session);
+                }
+            }
+
+            // Fill remaining slots (for processes without --resume) with newest unmatched sessions
+            let remaining_slots = effective_session_count.saturating_sub(id_matched.len());
+            let fallback_sessions = unmatched.into_iter().take(remaining_slots);
+
+            for session in id_matched.into_iter().chain(fallback_sessions) {
+                current_active_sessions.insert(session.external_id.clone(), path.clone());
+                // Override status to Working since process detection confirmed it's running.
+                // session.to_session_status() may return Idle if fileMtime in
+                // sessions-index.json is stale, but we trust process detection.
+                let mut session_status = session.to_session_status();
+                session_status.status = StatusState::Working;
+                events.push(AppEvent::SessionStatusAnalyzed {
+                    external_id: session.external_id.clone(),
+                    project_path: path.clone(),
+                    status: session_status,
+                });
+            }
+        }
+
+        // Remove sessions that are no longer active (immediate removal)
+        for external_id in retire_sessions(&mut self.prev_active_sessions, current_active_sessions, scope) {
+            tracing::debug!("Claude session removed: {}", external_id);
+            events.push(AppEvent::SessionUnregister { external_id });
+        }
+
+        events
+    }
+}
+
+/// Kiro CLI poll state (SQLite + running processes)
+pub struct KiroPoller {
+    fetcher: KiroSqliteFetcher,
+    /// Sessions reported active on the previous cycle (external_id -> workspace path)
+    prev_active_sessions: HashMap<String, String>,
+}
+
+impl KiroPoller {
+    pub fn new(fetcher: KiroSqliteFetcher) -> Self {
+        Self {
+            fetcher,
+            prev_active_sessions: HashMap::new(),
+        }
+    }
+
+    pub fn fetcher(&self) -> &KiroSqliteFetcher {
+        &self.fetcher
+    }
+
+    /// Run one poll cycle: status updates for live sessions, then unregistrations
+    ///
+    /// On a database error nothing is unregistered; the previous state is kept
+    /// until the next successful read.
+    pub fn poll(&mut self, workspaces: &[String]) -> Result<Vec<AppEvent>> {
+        self.poll_scoped(workspaces, None)
+    }
+
+    /// Run a poll cycle for a single workspace, leaving other sessions alone
+    pub fn poll_workspace(&mut self, path: &str) -> Result<Vec<AppEvent>> {
+        self.poll_scoped(&[path.to_string()], Some(path))
+    }
+
+    fn poll_scoped(&mut self, workspaces: &[String], scope: Option<&str>) -> Result<Vec<AppEvent>> {
+        // Fetch sessions from Kiro SQLite (limited to process_count, capped per workspace)
+        let statuses = self.fetcher.try_get_statuses(workspaces)?;
+
+        let mut current_active_sessions: HashMap<String, String> = HashMap::new();
+        let mut events = Vec::new();
+
+        for (path, status) in statuses {
+            let external_id = status.external_id(&path);
+            current_active_sessions.insert(external_id.clone(), path.clone());
+
+            let session_status = status.to_session_status(&path);
+            events.push(AppEvent::SessionStatusAnalyzed {
+                external_id,
+                project_path: path,
+                status: session_status,
+            });
+        }
+
+        // Remove sessions that are no longer active (Kiro: immediate removal)
+        for external_id in retire_sessions(&mut self.prev_active_sessions, current_active_sessions, scope) {
+            events.push(AppEvent::SessionUnregister { external_id });
+        }
+
+        Ok(events)
+    }
+}
+
+/// Session store whose live sessions are picked by running process count (OpenCode, Codex)
+pub trait ProcessMatchedFetcher {
+    /// Tool name used in log messages
+    const NAME: &'static str;
+
+    /// Check if the session store exists
+    fn is_available(&self) -> bool;
+
+    /// Session store location (for log messages)
+    fn store_path(&self) -> PathBuf;
+
+    /// Live sessions of the given workspaces: (workspace path, external_id, status)
+    fn live_sessions(&self, workspaces: &[String]) -> Vec<(String, String, SessionStatus)>;
+}
+
+/// Poll state of a [`ProcessMatchedFetcher`]
+pub struct SessionPoller<F> {
+    fetcher: F,
+    /// Sessions reported active on the previous cycle (external_id -> workspace path)
+    prev_active_sessions: HashMap<String, String>,
+}
+
+impl<F: ProcessMatchedFetcher> SessionPoller<F> {
+    pub fn new(fetcher: F) -> Self {
+        Self {
+            fetcher,
+            prev_active_sessions: HashMap::new(),
+        }
+    }
+
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    /// Run one poll cycle: status updates for live sessions, then unregistrations
+    pub fn poll(&mut self, workspaces: &[String]) -> Vec<AppEvent> {
+        self.poll_scoped(workspaces, None)
+    }
+
+    /// Run a poll cycle for a single workspace, leaving other sessions alone
+    pub fn poll_workspace(&mut self, path: &str) -> Vec<AppEvent> {
+        self.poll_scoped(&[path.to_string()], Some(path))
+    }
+
+    fn poll_scoped(&mut self, workspaces: &[String], scope: Option<&str>) -> Vec<AppEvent> {
+        let mut current_active_sessions: HashMap<String, String> = HashMap::new();
+        let mut events = Vec::new();
+
+        for (path, external_id, status) in self.fetcher.live_sessions(workspaces) {
+            current_active_sessions.insert(external_id.clone(), path.clone());
+            events.push(AppEvent::SessionStatusAnalyzed {
+                external_id,
+                project_path: path,
+                status,
+            });
+        }
+
+        for external_id in retire_sessions(&mut self.prev_active_sessions, current_active_sessions, scope) {
+            tracing::debug!("{} session removed: {}", F::NAME, external_id);
+            events.push(AppEvent::SessionUnregister { external_id });
+        }
+
+        events
+    }
+}
+
+/// Record this cycle's active sessions and return those that went away
+///
+/// With a `scope`, only sessions previously seen in that workspace can go away;
+/// the rest are kept for the next full cycle.
+fn retire_sessions(
+    prev: &mut HashMap<String, String>,
+    current: HashMap<String, String>,
+    scope: Option<&str>,
+) -> Vec<String> {
+    let removed: Vec<String> = prev
+        .iter()
+        .filter(|(external_id, path)| {
+            scope.is_none_or(|scope| scope == path.as_str()) && !current.contains_key(*external_id)
+        })
+        .map(|(external_id, _)| external_id.clone())
+        .collect();
+
+    for external_id in &removed {
+        prev.remove(external_id);
+    }
+    prev.extend(current);
+    removed
+}
+
+/// Normalize path for comparison (expand ~ and resolve)
+fn normalize_path_for_comparison(path: &str) -> String {
+    path.replace("~", &std::env::var("HOME").unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logwatch::claude_sessions::ClaudeSessionsConfig;
+    use crate::logwatch::collector::encode_project_path;
+    use crate::logwatch::kiro_sqlite::KiroSqliteConfig;
+    use crate::logwatch::process::ScriptedProcessProvider;
+    use std::sync::Arc;
+
+    const SESSION_ID: &str = "0b6c2d3e-1111-4222-8333-444455556666";
+
+    fn registered(events: &[AppEvent]) -> Vec<String> {
+        events.iter()
+            .filter_map(|e| match e {
+                AppEvent::SessionStatusAnalyzed { external_id, .. } => Some(external_id.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn unregistered(events: &[AppEvent]) -> Vec<String> {
+        events.iter()
+            .filter_map(|e| match e {
+                AppEvent::SessionUnregister { external_id } => Some(external_id.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn claude_process(pid: u32, cwd: &str) -> ClaudeProcessInfo {
+        ClaudeProcessInfo { pid, cwd: cwd.to_string(), session_id: None, ppid: None }
+    }
+
+    #[test]
+    fn test_claude_session_removed_when_process_gone() {
+        let claude_dir = tempfile::tempdir().unwrap();
+        let workspace = "/work/repo";
+        let project_dir = claude_dir.path().join("projects").join(encode_project_path(workspace));
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::write(project_dir.join(format!("{}.jsonl", SESSION_ID)), "").unwrap();
+
+        let processes = Arc::new(ScriptedProcessProvider::default());
+        let fetcher = ClaudeSessionsFetcher::with_config(ClaudeSessionsConfig {
+            claude_dirs: vec![claude_dir.path().to_path_buf()],
+            ..Default::default()
+        })
+        .with_process_provider(processes.clone());
+        let mut poller = ClaudePoller::new(fetcher);
+        let workspaces = vec![workspace.to_string()];
+        let external_id = crate::workspace::claude_external_id(SESSION_ID);
+
+        // No process: the session on disk is not reported
+        assert!(poller.poll(&workspaces).is_empty());
+
+        processes.set_claude(vec![claude_process(10, workspace)]);
+        let events = poller.poll(&workspaces);
+        assert_eq!(registered(&events), vec![external_id.clone()]);
+        assert!(unregistered(&events).is_empty());
+
+        // A process in another directory doesn't keep the session alive
+        processes.set_claude(vec![claude_process(11, "/work/other")]);
+        let events = poller.poll(&workspaces);
+        assert!(registered(&events).is_empty());
+        assert_eq!(unregistered(&events), vec![external_id]);
+
+        // Already removed: nothing more to report
+        assert!(poller.poll(&workspaces).is_empty());
+    }
+
+    #[test]
+    fn test_kiro_session_removed_when_process_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("data.sqlite3");
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE conversations_v2 (key TEXT, conversation_id TEXT, value TEXT, updated_at INTEGER);
+             INSERT INTO conversations_v2 VALUES ('/work/repo', 'conv-1', '{\"history\": []}', 1);",
+        )
+        .unwrap();
+
+        let processes = Arc::new(ScriptedProcessProvider::default());
+        let fetcher = KiroSqliteFetcher::with_config(KiroSqliteConfig {
+            db_path,
+            ..Default::default()
+        })
+        .with_process_provider(processes.clone());
+        let mut poller = KiroPoller::new(fetcher);
+        let workspaces = vec!["/work/repo".to_string()];
+
+        processes.set_kiro(&["/work/repo"]);
+        let events = poller.poll(&workspaces).unwrap();
+        assert_eq!(registered(&events), vec!["kiro:/work/repo:conv-1".to_string()]);
+
+        processes.set_kiro(&[]);
+        let events = poller.poll(&workspaces).unwrap();
+        assert!(registered(&events).is_empty());
+        assert_eq!(unregistered(&events), vec!["kiro:/work/repo:conv-1".to_string()]);
+    }
+
+    #[test]
+    fn test_poll_workspace_leaves_other_sessions_alone() {
+        let mut prev = HashMap::from([
+            ("a".to_string(), "/work/a".to_string()),
+            ("b".to_string(), "/work/b".to_string()),
+        ]);
+
+        // Scoped to /work/a: "b" is neither reported nor forgotten
+        let removed = retire_sessions(&mut prev, HashMap::new(), Some("/work/a"));
+        assert_eq!(removed, vec!["a".to_string()]);
+        assert!(prev.contains_key("b"));
+
+        // A full cycle retires everything not seen
+        let removed = retire_sessions(&mut prev, HashMap::new(), None);
+        assert_eq!(removed, vec!["b".to_string()]);
+        assert!(prev.is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+//! Running AI process discovery
+//!
+//! The Claude, Kiro, OpenCode and Codex fetchers decide which sessions are live from the set of
+//! running CLI processes. Enumeration goes through [`ProcessProvider`] so the
+//! matching logic can be exercised with scripted process lists.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tracing::{debug, warn};
+
+use super::claude_sessions::ClaudeProcessInfo;
+
+/// Source of running AI CLI processes
+pub trait ProcessProvider: Send + Sync {
+    /// Running Claude processes attached to a TTY (before subagent filtering)
+    fn claude_processes(&self) -> Vec<ClaudeProcessInfo>;
+
+    /// Working directory of each running Kiro process (one entry per process)
+    fn kiro_process_cwds(&self) -> Vec<String>;
+
+    /// Working directory of each running OpenCode process (one entry per process)
+    fn opencode_process_cwds(&self) -> Vec<String>;
+
+    /// Working directory of each running Codex process (one entry per process)
+    fn codex_process_cwds(&self) -> Vec<String>;
+}
+
+/// Default cap on sessions shown per workspace (Kiro, OpenCode and Codex)
+pub const DEFAULT_MAX_SESSIONS_PER_WORKSPACE: usize = 3;
+
+/// Default process names matched for Claude Code
+pub const DEFAULT_CLAUDE_PROCESS_NAMES: &[&str] = &["claude"];
+/// Default process names matched for Kiro CLI
+pub const DEFAULT_KIRO_PROCESS_NAMES: &[&str] = &["kiro-cli"];
+/// Default process names matched for OpenCode
+pub const DEFAULT_OPENCODE_PROCESS_NAMES: &[&str] = &["opencode"];
+/// Default process names matched for Codex
+pub const DEFAULT_CODEX_PROCESS_NAMES: &[&str] = &["codex"];
+
+/// Runtimes that may host the Claude CLI script (`node .../cli.js`)
+const WRAPPER_RUNTIMES: &[&str] = &["node", "nodejs", "bun"];
+/// Script paths identifying the Claude CLI when run through a wrapper runtime
+const CLAUDE_CLI_SCRIPT_SUFFIXES: &[&str] = &[
+    "@anthropic-ai/claude-code/cli.js",
+    "@anthropic-ai/claude-code/cli.mjs",
+];
+
+/// [`ProcessProvider`] backed by `pgrep` / `ps` / `lsof`
+#[derive(Debug, Clone)]
+pub struct SystemProcessProvider {
+    /// Exact process names treated as Claude Code
+    claude_names: Vec<String>,
+    /// Exact process names treated as Kiro CLI
+    kiro_names: Vec<String>,
+    /// Exact process names treated as OpenCode
+    opencode_names: Vec<String>,
+    /// Exact process names treated as Codex
+    codex_names: Vec<String>,
+}
+
+impl SystemProcessProvider {
+    /// Match the given process names (empty lists fall back to the defaults)
+    pub fn new(
+        claude_names: Vec<String>,
+        kiro_names: Vec<String>,
+        opencode_names: Vec<String>,
+        codex_names: Vec<String>,
+    ) -> Self {
+        Self {
+            claude_names: names_or_default(claude_names, DEFAULT_CLAUDE_PROCESS_NAMES),
+            kiro_names: names_or_default(kiro_names, DEFAULT_KIRO_PROCESS_NAMES),
+            opencode_names: names_or_default(opencode_names, DEFAULT_OPENCODE_PROCESS_NAMES),
+            codex_names: names_or_default(codex_names, DEFAULT_CODEX_PROCESS_NAMES),
+        }
+    }
+}
+
+impl Default for SystemProcessProvider {
+    fn default() -> Self {
+        Self::new(Vec::new(), Vec::new(), Vec::new(), Vec::new())
+    }
+}
+
+impl ProcessProvider for SystemProcessProvider {
+    fn claude_processes(&self) -> Vec<ClaudeProcessInfo> {
+        // Find claude processes, get their pid, cwd, --resume argument, and ppid
+        // Only include processes with a TTY (not background/subprocess with tty=??)
+        // Installs running as `node .../cli.js` are not found by name, so add them by pid
+        let wrapper_pids: Vec<String> = wrapped_claude_pids().iter().map(|p| p.to_string()).collect();
+        let script = cwd_function() + &r#"
+            for pid in $( { pgrep -x __PATTERN__; echo __EXTRA_PIDS__; } 2>/dev/null | tr ' ' '\n' | sort -un); do
+                tty=$(ps -p $pid -o tty= 2>/dev/null | tr -d ' ')
+                # Skip background processes (tty is ?? or empty)
+                if [ "$tty" = "??" ] || [ -z "$tty" ]; then
+                    continue
+                fi
+                state=$(ps -p $pid -o state= 2>/dev/null | tr -d ' ')
+                if [ "$state" != "T" ] && [ -n "$state" ]; then
+                    cwd=$(cwd_of $pid)
+                    args=$(ps -p $pid -o args= 2>/dev/null)
+                    # Extract session ID from --resume argument
+                    session_id=$(echo "$args" | grep -oE '\-\-resume [a-f0-9-]+' | awk '{print $2}')
+                    ppid=$(ps -p $pid -o ppid= 2>/dev/null | tr -d ' ')
+                    if [ -n "$cwd" ]; then
+                        echo "${pid}|${cwd}|${session_id}|${ppid}"
+                    fi
+                fi
+            done
+        "#
+        .replace("__PATTERN__", &pgrep_pattern(&self.claude_names))
+        .replace("__EXTRA_PIDS__", &wrapper_pids.join(" "));
+
+        match run_script(&script) {
+            Some(stdout) => stdout.lines().filter_map(parse_claude_process_line).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn kiro_process_cwds(&self) -> Vec<String> {
+        process_cwds(&self.kiro_names)
+    }
+
+    fn opencode_process_cwds(&self) -> Vec<String> {
+        process_cwds(&self.opencode_names)
+    }
+
+    fn codex_process_cwds(&self) -> Vec<String> {
+        process_cwds(&self.codex_names)
+    }
+}
+
+/// Working directory of each running (not stopped) process named one of `names`
+fn process_cwds(names: &[String]) -> Vec<String> {
+    let script = cwd_function() + &r#"
+        for pid in $(pgrep -x __PATTERN__ 2>/dev/null); do
+            state=$(ps -p $pid -o state= 2>/dev/null | tr -d ' ')
+            if [ "$state" != "T" ] && [ -n "$state" ]; then
+                cwd_of $pid
+            fi
+        done
+    "#
+    .replace("__PATTERN__", &pgrep_pattern(names));
+
+    match run_script(&script) {
+        Some(stdout) => stdout
+            .lines()
+            .map(str::trim)
+            .filter(|cwd| !cwd.is_empty())
+            .map(str::to_string)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn names_or_default(names: Vec<String>, default: &[&str]) -> Vec<String> {
+    let names: Vec<String> = names.into_iter().filter(|n| !n.trim().is_empty()).collect();
+    if names.is_empty() {
+        default.iter().map(|n| n.to_string()).collect()
+    } else {
+        names
+    }
+}
+
+/// Build a shell-quoted `pgrep -x` pattern matching any of `names` literally
+fn pgrep_pattern(names: &[String]) -> String {
+    let alternatives: Vec<String> = names
+        .iter()
+        .map(|name| {
+            name.chars()
+                .map(|c| {
+                    if "\\.^$|?*+()[]{}".contains(c) {
+                        format!("\\{}", c)
+                    } else {
+                        c.to_string()
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    format!("'{}'", alternatives.join("|").replace('\'', "'\\''"))
+}
+
+/// PIDs of Claude CLIs launched through a node/bun wrapper
+fn wrapped_claude_pids() -> Vec<u32> {
+    let Some(stdout) = run_script("ps -Ao pid=,args= 2>/dev/null") else {
+        return Vec::new();
+    };
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (pid, args) = line.trim().split_once(' ')?;
+            if is_wrapped_claude_cmdline(args) {
+                pid.parse::<u32>().ok()
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Whether a command line is `<node|bun> [runtime flags] .../@anthropic-ai/claude-code/cli.js ...`
+///
+/// Only the script the runtime executes is considered, so processes that merely
+/// mention the CLI path in a later argument (editors, grep, ...) don't match.
+fn is_wrapped_claude_cmdline(args: &str) -> bool {
+    let mut tokens = args.split_whitespace();
+    let Some(runtime) = tokens.next() else {
+        return false;
+    };
+    let runtime = runtime.rsplit('/').next().unwrap_or(runtime);
+    if !WRAPPER_RUNTIMES.contains(&runtime) {
+        return false;
+    }
+    tokens
+        .find(|t| !t.starts_with('-'))
+        .is_some_and(|script| CLAUDE_CLI_SCRIPT_SUFFIXES.iter().any(|s| script.ends_with(s)))
+}
+
+/// Shell function `cwd_of <pid>` printing a process's working directory
+///
+/// Reads `/proc/<pid>/cwd` where it exists (Linux) and falls back to `lsof`,
+/// which is left out entirely when it isn't installed.
+fn cwd_function() -> String {
+    let lsof = if lsof_available() {
+        "lsof -a -b -w -p \"$1\" -d cwd -Fn 2>/dev/null | sed -n 's/^n//p' | head -n 1"
+    } else {
+        ":"
+    };
+    format!(
+        r#"
+            cwd_of() {{
+                readlink "/proc/$1/cwd" 2>/dev/null || {lsof}
+            }}
+        "#
+    )
+}
+
+/// Whether `lsof` is on PATH (checked once per process)
+fn lsof_available() -> bool {
+    static LSOF_AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *LSOF_AVAILABLE.get_or_init(|| {
+        let available = std::process::Command::new("sh")
+            .args(["-c", "command -v lsof"])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if !available {
+            let proc_fs = std::path::Path::new("/proc/self/cwd").exists();
+            if proc_fs {
+                debug!("lsof not found; reading process cwd from /proc");
+            } else {
+                warn!("lsof not found and /proc is unavailable; AI process working directories can't be resolved");
+            }
+        }
+        available
+    })
+}
+
+/// Run a process-discovery shell script and return its stdout
+fn run_script(script: &str) -> Option<String> {
+    match std::process::Command::new("sh").arg("-c").arg(script).output() {
+        Ok(output) => Some(String::from_utf8_lossy(&output.stdout).into_owned()),
+        Err(e) => {
+            debug!("Failed to list AI processes: {}", e);
+            None
+        }
+    }
+}
+
+/// Parse a `pid|cwd|session_id|ppid` line emitted by the Claude discovery script
+fn parse_claude_process_line(line: &str) -> Option<ClaudeProcessInfo> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    let parts: Vec<&str> = line.splitn(4, '|').collect();
+    let pid = parts.first().and_then(|s| s.parse::<u32>().ok())?;
+    let cwd = normalize_path(parts.get(1).unwrap_or(&""));
+    if cwd.is_empty() {
+        return None;
+    }
+    let session_id = parts
+        .get(2)
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+    let ppid = parts.get(3).and_then(|s| s.trim().parse::<u32>().ok());
+    Some(ClaudeProcessInfo { pid, cwd, session_id, ppid })
+}
+
+/// Normalize a path by expanding ~ to home directory
+pub(crate) fn normalize_path(path: &str) -> String {
+    if path.starts_with("~/") {
+        if let Some(home) = std::env::var_os("HOME") {
+            return format!("{}{}", home.to_string_lossy(), &path[1..]);
+        }
+    }
+    path.to_string()
+}
+
+/// Number of running processes per working directory
+pub(crate) fn count_by_cwd(cwds: Vec<String>) -> HashMap<String, usize> {
+    let mut running: HashMap<String, usize> = HashMap::new();
+    for cwd in cwds {
+        *running.entry(cwd).or_insert(0) += 1;
+    }
+    running
+}
+
+/// Sessions reported for a workspace: one per running process, capped by `max` (0 = no cap)
+pub(crate) fn session_limit(process_count: usize, max: usize) -> usize {
+    match max {
+        0 => process_count,
+        max => process_count.min(max),
+    }
+}
+
+/// Session limits of the workspaces that have running processes
+///
+/// Keyed by normalized path, so session stores recording `~/...` or absolute
+/// directories both match.
+pub(crate) fn workspace_limits<'a>(
+    workspaces: &'a [String],
+    running: &HashMap<String, usize>,
+    max: usize,
+) -> HashMap<String, (&'a String, usize)> {
+    workspaces
+        .iter()
+        .filter_map(|workspace| {
+            let count = running.get(workspace).copied().unwrap_or(0);
+            let limit = session_limit(count, max);
+            (limit > 0).then(|| (normalize_path(workspace), (workspace, limit)))
+        })
+        .collect()
+}
+
+/// [`ProcessProvider`] returning a scripted process list (for tests)
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct ScriptedProcessProvider {
+    claude: std::sync::Mutex<Vec<ClaudeProcessInfo>>,
+    kiro: std::sync::Mutex<Vec<String>>,
+    opencode: std::sync::Mutex<Vec<String>>,
+    codex: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl ScriptedProcessProvider {
+    pub(crate) fn set_claude(&self, processes: Vec<ClaudeProcessInfo>) {
+        *self.claude.lock().unwrap() = processes;
+    }
+
+    pub(crate) fn set_kiro(&self, cwds: &[&str]) {
+        *self.kiro.lock().unwrap() = cwds.iter().map(|s| s.to_string()).collect();
+    }
+
+    pub(crate) fn set_opencode(&self, cwds: &[&str]) {
+        *self.opencode.lock().unwrap() = cwds.iter().map(|s| s.to_string()).collect();
+    }
+
+    pub(crate) fn set_codex(&self, cwds: &[&str]) {
+        *self.codex.lock().unwrap() = cwds.iter().map(|s| s.to_string()).collect();
+    }
+}
+
+#[cfg(test)]
+impl ProcessProvider for ScriptedProcessProvider {
+    fn claude_processes(&self) -> Vec<ClaudeProcessInfo> {
+        self.claude.lock().unwrap().clone()
+    }
+
+    fn kiro_process_cwds(&self) -> Vec<String> {
+        self.kiro.lock().unwrap().clone()
+    }
+
+    fn opencode_process_cwds(&self) -> Vec<String> {
+        self.opencode.lock().unwrap().clone()
+    }
+
+    fn codex_process_cwds(&self) -> Vec<String> {
+        self.codex.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_claude_process_line() {
+        let info = parse_claude_process_line("123|/work/repo|abc-def|45").unwrap();
+        assert_eq!(info.pid, 123);
+        assert_eq!(info.cwd, "/work/repo");
+        assert_eq!(info.session_id.as_deref(), Some("abc-def"));
+        assert_eq!(info.ppid, Some(45));
+
+        let info = parse_claude_process_line("123|/work/repo||").unwrap();
+        assert!(info.session_id.is_none());
+        assert!(info.ppid.is_none());
+
+        assert!(parse_claude_process_line("").is_none());
+        assert!(parse_claude_process_line("123||abc|1").is_none());
+        assert!(parse_claude_process_line("x|/work/repo||").is_none());
+    }
+
+    #[test]
+    fn test_pgrep_pattern_escapes_and_joins() {
+        let names = vec!["claude".to_string(), "claude.sh".to_string()];
+        assert_eq!(pgrep_pattern(&names), "'claude|claude\\.sh'");
+        assert_eq!(pgrep_pattern(&["it's".to_string()]), "'it'\\''s'");
+    }
+
+    #[test]
+    fn test_workspace_limits_follow_process_count() {
+        let workspaces = vec!["/work/a".to_string(), "/work/b".to_string(), "/work/c".to_string()];
+        let running = count_by_cwd(vec![
+            "/work/a".to_string(),
+            "/work/b".to_string(),
+            "/work/b".to_string(),
+            "/work/b".to_string(),
+        ]);
+
+        let limits = workspace_limits(&workspaces, &running, 2);
+        assert_eq!(limits.get("/work/a").map(|(_, limit)| *limit), Some(1));
+        assert_eq!(limits.get("/work/b").map(|(_, limit)| *limit), Some(2));
+        assert!(!limits.contains_key("/work/c"));
+
+        // 0 = no cap
+        let limits = workspace_limits(&workspaces, &running, 0);
+        assert_eq!(limits.get("/work/b").map(|(_, limit)| *limit), Some(3));
+    }
+
+    #[test]
+    fn test_is_wrapped_claude_cmdline() {
+        assert!(is_wrapped_claude_cmdline(
+            "node /usr/local/lib/node_modules/@anthropic-ai/claude-code/cli.js --resume abc"
+        ));
+        assert!(is_wrapped_claude_cmdline(
+            "/opt/homebrew/bin/node --no-warnings /opt/lib/node_modules/@anthropic-ai/claude-code/cli.js"
+        ));
+        assert!(is_wrapped_claude_cmdline("bun /home/u/.bun/install/global/node_modules/@anthropic-ai/claude-code/cli.js"));
+
+        // Not the script being run
+        assert!(!is_wrapped_claude_cmdline("node server.js /x/@anthropic-ai/claude-code/cli.js"));
+        assert!(!is_wrapped_claude_cmdline("vim /x/node_modules/@anthropic-ai/claude-code/cli.js"));
+        assert!(!is_wrapped_claude_cmdline("node"));
+        assert!(!is_wrapped_claude_cmdline(""));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_cwd_function_resolves_own_cwd() {
+        let script = cwd_function() + "cwd_of $$";
+        let dir = tempfile::tempdir().unwrap();
+        let output = std::process::Command::new("sh")
+            .args(["-c", &script])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        let cwd = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(
+            std::path::Path::new(cwd.trim()),
+            dir.path().canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_empty_names_fall_back_to_defaults() {
+        let provider = SystemProcessProvider::new(Vec::new(), vec!["kiro".to_string()], Vec::new(), Vec::new());
+        assert_eq!(provider.claude_names, vec!["claude".to_string()]);
+        assert_eq!(provider.kiro_names, vec!["kiro".to_string()]);
+        assert_eq!(provider.opencode_names, vec!["opencode".to_string()]);
+        assert_eq!(provider.codex_names, vec!["codex".to_string()]);
+    }
+}
@@ -3,6 +3,38 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Default maximum length (chars) of a session summary
+pub const DEFAULT_SUMMARY_MAX_LEN: usize = 50;
+/// Default maximum length (chars) of a session's current task
+pub const DEFAULT_TASK_MAX_LEN: usize = 80;
+
+/// Maximum lengths (chars) of session text shown in the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextLimits {
+    pub summary: usize,
+    pub task: usize,
+}
+
+impl Default for TextLimits {
+    fn default() -> Self {
+        Self {
+            summary: DEFAULT_SUMMARY_MAX_LEN,
+            task: DEFAULT_TASK_MAX_LEN,
+        }
+    }
+}
+
+/// Truncate text to max characters, appending "..." if truncated
+pub fn truncate_text(s: &str, max_chars: usize) -> String {
+    let char_count = s.chars().count();
+    if char_count > max_chars {
+        let truncated: String = s.chars().take(max_chars.saturating_sub(3)).collect();
+        format!("{}...", truncated)
+    } else {
+        s.to_string()
+    }
+}
+
 /// Main status states
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -137,18 +169,81 @@ pub struct AnalysisProgress {
     pub pending_steps: Vec<String>,
 }
 
+impl AnalysisProgress {
+    /// Completed and total step counts (None when no steps are known)
+    pub fn counts(&self) -> Option<(usize, usize)> {
+        let done = self.completed_steps.len();
+        let total = done + usize::from(self.current_step.is_some()) + self.pending_steps.len();
+        (total > 0).then_some((done, total))
+    }
+}
+
 /// Additional context from analysis
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnalysisContext {
     /// Files that have been modified
     #[serde(default)]
     pub files_modified: Vec<String>,
+    /// File the most recent tool call worked on
+    #[serde(default)]
+    pub last_file: Option<String>,
     /// Approximate tokens used
     #[serde(default)]
     pub tokens_used: Option<u64>,
     /// Model being used
     #[serde(default)]
     pub model: Option<String>,
+    /// Tokens in the context window as of the latest turn (input + cached input)
+    #[serde(default)]
+    pub context_tokens: Option<u64>,
+    /// Output tokens of the latest turn
+    #[serde(default)]
+    pub output_tokens: Option<u64>,
+}
+
+impl AnalysisContext {
+    /// Whether there is anything worth showing
+    pub fn is_empty(&self) -> bool {
+        self.files_modified.is_empty()
+            && self.last_file.is_none()
+            && self.tokens_used.is_none()
+            && self.model.is_none()
+            && self.context_tokens.is_none()
+            && self.output_tokens.is_none()
+    }
+}
+
+/// State of one entry in an agent's todo list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoState {
+    InProgress,
+    Completed,
+    /// Also used for states this version doesn't know
+    #[default]
+    #[serde(other)]
+    Pending,
+}
+
+/// One entry of an agent's todo list (Claude Code's TodoWrite tool)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TodoItem {
+    /// What needs to be done
+    pub content: String,
+    #[serde(default)]
+    pub status: TodoState,
+    /// Present-tense label shown while the item is in progress
+    #[serde(default, rename = "activeForm", skip_serializing_if = "Option::is_none")]
+    pub active_form: Option<String>,
+}
+
+/// Completed and total counts of a todo list (None when the list is empty)
+pub fn todo_progress(todos: &[TodoItem]) -> Option<(usize, usize)> {
+    if todos.is_empty() {
+        return None;
+    }
+    let completed = todos.iter().filter(|t| t.status == TodoState::Completed).count();
+    Some((completed, todos.len()))
 }
 
 /// Complete session status from AI analysis
@@ -168,7 +263,7 @@ pub struct SessionStatus {
     /// Detailed state
     #[serde(default)]
     pub state_detail: StatusDetail,
-    /// Brief summary of current work (max 50 chars)
+    /// Brief summary of current work (truncated with [`Self::display_summary`])
     #[serde(default)]
     pub summary: Option<String>,
     /// Current task description
@@ -186,6 +281,10 @@ pub struct SessionStatus {
     /// Additional context
     #[serde(default)]
     pub context: Option<AnalysisContext>,
+    /// The agent's own todo list: `None` when this update didn't see one,
+    /// `Some(vec![])` when the list was cleared
+    #[serde(default)]
+    pub todos: Option<Vec<TodoItem>>,
 }
 
 impl Default for SessionStatus {
@@ -202,6 +301,7 @@ impl Default for SessionStatus {
             progress: None,
             error: None,
             context: None,
+            todos: None,
         }
     }
 }
@@ -233,17 +333,14 @@ impl SessionStatus {
         }
     }
 
-    /// Get a truncated summary for display (max 50 chars)
-    pub fn display_summary(&self) -> Option<String> {
-        self.summary.as_ref().map(|s| {
-            let char_count = s.chars().count();
-            if char_count > 50 {
-                let truncated: String = s.chars().take(47).collect();
-                format!("{}...", truncated)
-            } else {
-                s.clone()
-            }
-        })
+    /// Get a truncated summary for display
+    pub fn display_summary(&self, max_chars: usize) -> Option<String> {
+        self.summary.as_deref().map(|s| truncate_text(s, max_chars))
+    }
+
+    /// Get a truncated current task for display
+    pub fn display_task(&self, max_chars: usize) -> Option<String> {
+        self.current_task.as_deref().map(|s| truncate_text(s, max_chars))
     }
 
     /// Get time since last activity as human-readable string
@@ -294,6 +391,33 @@ mod tests {
         assert_eq!(status.summary.as_deref(), Some("Implementing feature X"));
     }
 
+    #[test]
+    fn test_progress_counts() {
+        let progress = AnalysisProgress {
+            completed_steps: vec!["plan".to_string(), "code".to_string()],
+            current_step: Some("test".to_string()),
+            pending_steps: vec!["release".to_string()],
+        };
+        assert_eq!(progress.counts(), Some((2, 4)));
+        assert_eq!(AnalysisProgress::default().counts(), None);
+    }
+
+    #[test]
+    fn test_todo_items_parse_claude_format() {
+        let json = r#"[
+            {"content": "Add tests", "status": "completed", "activeForm": "Adding tests"},
+            {"content": "Fix lint", "status": "in_progress", "activeForm": "Fixing lint"},
+            {"content": "Release", "status": "blocked"}
+        ]"#;
+        let todos: Vec<TodoItem> = serde_json::from_str(json).unwrap();
+        assert_eq!(todos[1].status, TodoState::InProgress);
+        assert_eq!(todos[1].active_form.as_deref(), Some("Fixing lint"));
+        // Unknown states count as pending
+        assert_eq!(todos[2].status, TodoState::Pending);
+        assert_eq!(todo_progress(&todos), Some((1, 3)));
+        assert_eq!(todo_progress(&[]), None);
+    }
+
     #[test]
     fn test_display_summary_truncation() {
         let status = SessionStatus {
@@ -301,8 +425,22 @@ mod tests {
             ..Default::default()
         };
 
-        let display = status.display_summary().unwrap();
+        let display = status.display_summary(DEFAULT_SUMMARY_MAX_LEN).unwrap();
         assert!(display.len() <= 50);
         assert!(display.ends_with("..."));
+
+        let wide = status.display_summary(200).unwrap();
+        assert_eq!(wide, status.summary.unwrap());
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("short", 50), "short");
+        let long = "a".repeat(60);
+        let truncated = truncate_text(&long, 50);
+        assert!(truncated.ends_with("..."));
+        assert!(truncated.chars().count() <= 50);
+        // multibyte characters are counted as chars, not bytes
+        assert_eq!(truncate_text("日本語のテキスト", 5), "日本...");
     }
 }
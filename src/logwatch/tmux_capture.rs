@@ -0,0 +1,205 @@
+//! Status detection for tmux windows opened by workspace-manager
+//!
+//! Windows created by the tool carry the `@workspace-name` user option. For
+//! each of them the active pane is captured and run through
+//! [`extract_status_heuristic`], so AI CLIs without a log or database we can
+//! read still get a status. Sessions are keyed by `tmux:{session}:{window}`.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::SystemTime;
+
+use crate::app::AppEvent;
+use crate::multiplexer::tmux::TmuxMultiplexer;
+use crate::multiplexer::Multiplexer;
+use crate::workspace::tmux_external_id;
+
+use super::analyzer::extract_status_heuristic;
+use super::collector::LogContent;
+
+/// A tmux window tagged with `@workspace-name`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmuxWindow {
+    pub session: String,
+    pub window_index: String,
+    pub workspace_name: String,
+    /// Directory the window was opened in (falls back to the active pane's cwd)
+    pub path: String,
+}
+
+/// Captures tagged tmux windows and turns their contents into status events
+pub struct TmuxCapturePoller {
+    mux: TmuxMultiplexer,
+    /// External IDs reported by the previous poll
+    known: HashSet<String>,
+}
+
+impl Default for TmuxCapturePoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TmuxCapturePoller {
+    pub fn new() -> Self {
+        // capture-pane targets are fully qualified, so no session is needed
+        Self {
+            mux: TmuxMultiplexer::new_external(String::new()),
+            known: HashSet::new(),
+        }
+    }
+
+    /// Run one poll cycle over the given workspace paths
+    ///
+    /// Windows reported by the previous cycle that are gone now are
+    /// unregistered.
+    pub fn poll(&mut self, workspaces: &[String]) -> Result<Vec<AppEvent>> {
+        let output = Command::new("tmux")
+            .args([
+                "list-windows",
+                "-a",
+                "-F",
+                "#{session_name}\t#{window_index}\t#{@workspace-name}\t#{pane_start_path}\t#{pane_current_path}",
+            ])
+            .output()
+            .context("Failed to run tmux")?;
+        if !output.status.success() {
+            // No server running: every window we knew of is gone
+            return Ok(self.unregister_missing(&HashSet::new()));
+        }
+
+        let mut events = Vec::new();
+        let mut current = HashSet::new();
+        for window in parse_window_list(&String::from_utf8_lossy(&output.stdout)) {
+            let Some(project_path) = workspace_for_path(&window.path, workspaces) else {
+                continue;
+            };
+            let external_id = tmux_external_id(&window.session, &window.window_index);
+            current.insert(external_id.clone());
+            let target = format!("{}:{}", window.session, window.window_index);
+            let captured = match self.mux.capture_pane(&target) {
+                Ok(captured) => captured,
+                Err(e) => {
+                    tracing::debug!("Failed to capture {}: {}", target, e);
+                    continue;
+                }
+            };
+            events.push(AppEvent::SessionStatusAnalyzed {
+                external_id,
+                project_path: project_path.to_string(),
+                status: status_from_capture(&captured, project_path),
+            });
+        }
+        events.extend(self.unregister_missing(&current));
+        Ok(events)
+    }
+
+    /// Unregister events for known windows missing from `current`, which becomes the known set
+    fn unregister_missing(&mut self, current: &HashSet<String>) -> Vec<AppEvent> {
+        let events = self
+            .known
+            .difference(current)
+            .map(|external_id| AppEvent::SessionUnregister { external_id: external_id.clone() })
+            .collect();
+        self.known = current.clone();
+        events
+    }
+}
+
+/// Run the log heuristic over a captured screen (trailing blank lines dropped)
+pub fn status_from_capture(captured: &str, project_path: &str) -> super::SessionStatus {
+    let lines: Vec<String> = captured.trim_end().lines().map(str::to_string).collect();
+    extract_status_heuristic(&LogContent {
+        source: PathBuf::new(),
+        project_path: Some(project_path.to_string()),
+        tool: "tmux".to_string(),
+        lines,
+        collected_at: SystemTime::now(),
+    })
+}
+
+/// Parse `list-windows -F` output, keeping only windows with `@workspace-name`
+fn parse_window_list(output: &str) -> Vec<TmuxWindow> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let session = fields.next()?;
+            let window_index = fields.next()?;
+            let workspace_name = fields.next()?;
+            let start_path = fields.next().unwrap_or_default();
+            let current_path = fields.next().unwrap_or_default();
+            if workspace_name.is_empty() {
+                return None;
+            }
+            let path = if start_path.is_empty() { current_path } else { start_path };
+            Some(TmuxWindow {
+                session: session.to_string(),
+                window_index: window_index.to_string(),
+                workspace_name: workspace_name.to_string(),
+                path: path.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The deepest workspace containing `path`
+fn workspace_for_path<'a>(path: &str, workspaces: &'a [String]) -> Option<&'a str> {
+    let path = std::path::Path::new(path);
+    workspaces
+        .iter()
+        .filter(|ws| path.starts_with(ws.as_str()))
+        .max_by_key(|ws| ws.len())
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logwatch::StatusState;
+
+    #[test]
+    fn test_parse_window_list_keeps_tagged_windows() {
+        let output = "main\t1\tapp/main\t/src/app\t/src/app/sub\n\
+                      main\t2\t\t/tmp\t/tmp\n\
+                      work\t3\tlib/dev\t\t/src/lib\n";
+        let windows = parse_window_list(output);
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].path, "/src/app");
+        assert_eq!(windows[1].path, "/src/lib");
+        assert_eq!(tmux_external_id(&windows[1].session, &windows[1].window_index), "tmux:work:3");
+    }
+
+    #[test]
+    fn test_workspace_for_path_prefers_deepest() {
+        let workspaces = vec!["/src/app".to_string(), "/src/app/nested".to_string(), "/src/apple".to_string()];
+        assert_eq!(workspace_for_path("/src/app/nested/x", &workspaces), Some("/src/app/nested"));
+        assert_eq!(workspace_for_path("/src/app", &workspaces), Some("/src/app"));
+        assert_eq!(workspace_for_path("/src/other", &workspaces), None);
+    }
+
+    #[test]
+    fn test_unregister_missing_windows() {
+        let mut poller = TmuxCapturePoller::new();
+        let ids = |list: &[&str]| list.iter().map(|id| id.to_string()).collect::<HashSet<_>>();
+
+        assert!(poller.unregister_missing(&ids(&["tmux:main:1", "tmux:main:2"])).is_empty());
+        let events = poller.unregister_missing(&ids(&["tmux:main:2"]));
+        assert!(matches!(
+            events.as_slice(),
+            [AppEvent::SessionUnregister { external_id }] if external_id == "tmux:main:1"
+        ));
+        assert_eq!(poller.unregister_missing(&HashSet::new()).len(), 1);
+        assert!(poller.known.is_empty());
+    }
+
+    #[test]
+    fn test_status_from_capture() {
+        let status = status_from_capture("$ make\nbuild failed\n\n\n", "/src/app");
+        assert_eq!(status.status, StatusState::Error);
+        assert_eq!(status.project_path.as_deref(), Some("/src/app"));
+        assert_eq!(status_from_capture("$ \n", "/src/app").status, StatusState::Idle);
+    }
+}
@@ -1,26 +1,35 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+use clap_complete::env::CompleteEnv;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent},
+    event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use std::io::{self, Write as _};
 use std::path::Path;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-use workspace_manager::app::{Action, AppEvent, AppState, Config, FocusedPane, mouse_action, poll_event, ViewMode};
-use workspace_manager::logwatch::{ClaudeProcessInfo, ClaudeSession, ClaudeSessionsFetcher, KiroSqliteConfig, KiroSqliteFetcher, StatusState};
+use workspace_manager::app::log_buffer::{LogBuffer, RingBufferLayer, DEFAULT_LOG_BUFFER_CAPACITY};
+use workspace_manager::app::messages::MessageLevel;
+use workspace_manager::app::terminal;
+use workspace_manager::app::state::PollTimestamp;
+use workspace_manager::app::config::{expand_tilde, ExistingWorktreeBehavior};
+use workspace_manager::app::{Action, AppEvent, AppState, chord_action, Config, FocusedPane, mouse_action, PersistedState, poll_event, TreeItem, ViewMode};
+use workspace_manager::logwatch::{ClaudePoller, ClaudeSessionsConfig, ClaudeSessionsFetcher, KiroPoller, KiroSqliteConfig, KiroSqliteFetcher, OpenCodeSessionsConfig, OpenCodeSessionsFetcher, CodexSessionsConfig, CodexSessionsFetcher, ProcessMatchedFetcher, ProcessProvider, SessionPoller, StatusState, SystemProcessProvider, TmuxCapturePoller};
 use workspace_manager::workspace::SessionStatus;
 use workspace_manager::multiplexer::{self, Multiplexer, WindowActionResult};
 use workspace_manager::notify::{self, NotifyMessage};
 use workspace_manager::ui;
 use workspace_manager::ui::input_dialog::{InputDialog, InputDialogKind};
 use workspace_manager::ui::selection_dialog::{SelectionContext, SelectionDialogKind};
-use workspace_manager::workspace::{AiTool, WorktreeManager};
+use workspace_manager::workspace::{count_recent_commits, parse_external_id, AiTool, BranchCheckedOut, Workspace, WorktreeManager};
+
 
 /// Workspace Manager - TUI for managing Claude Code workspaces
 #[derive(Parser)]
@@ -38,7 +47,11 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Start the TUI (default)
-    Tui,
+    Tui {
+        /// Scan only this directory instead of search_paths (repeatable; not saved to the config)
+        #[arg(long = "path", value_name = "DIR")]
+        paths: Vec<std::path::PathBuf>,
+    },
     /// Start the MCP daemon server (Phase 2)
     Daemon,
     /// Send a notification to the daemon (Phase 2)
@@ -50,6 +63,82 @@ enum Commands {
     SetupPlugin,
     /// Setup tmux hook for tab-focus notifications
     SetupTmuxHook,
+    /// Print JSON Schema for config.toml (for editor completion/validation)
+    #[command(hide = true)]
+    Schema,
+    /// Print a shell completion script (e.g. `source <(workspace-manager completions bash)`)
+    #[command(hide = true)]
+    Completions {
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
+    /// Print version (with environment info when --verbose)
+    Version {
+        /// Also report multiplexer, AI CLI directories and config path
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Print the workspaces found under search_paths (without starting the TUI)
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ListFormat::Tsv)]
+        format: ListFormat,
+    },
+    /// Open a workspace (multiplexer tab, or the editor outside one) and exit
+    Open {
+        /// Repository name (worktree directories like repo__branch also match)
+        #[arg(add = ArgValueCandidates::new(repo_candidates))]
+        repo: String,
+        /// Branch; defaults to the repository's main checkout
+        #[arg(add = ArgValueCandidates::new(branch_candidates))]
+        branch: Option<String>,
+    },
+    /// Print the absolute path of a workspace (e.g. `cd "$(workspace-manager path repo branch)"`)
+    Path {
+        /// Repository name (worktree directories like repo__branch also match)
+        #[arg(add = ArgValueCandidates::new(repo_candidates))]
+        repo: String,
+        /// Branch; defaults to the repository's main checkout
+        #[arg(add = ArgValueCandidates::new(branch_candidates))]
+        branch: Option<String>,
+    },
+}
+
+/// `list` の出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ListFormat {
+    /// repo_name<TAB>branch<TAB>project_path per line
+    Tsv,
+    /// Array of full workspace objects
+    Json,
+}
+
+/// 補完スクリプトの対象シェル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Elvish,
+    #[value(name = "powershell")]
+    PowerShell,
+    Nushell,
+}
+
+impl CompletionShell {
+    /// 補完スクリプトを `out` に書き出す
+    fn generate(self, command: &mut clap::Command, out: &mut dyn std::io::Write) {
+        use clap_complete::Shell;
+        let name = "workspace-manager";
+        match self {
+            CompletionShell::Bash => clap_complete::generate(Shell::Bash, command, name, out),
+            CompletionShell::Zsh => clap_complete::generate(Shell::Zsh, command, name, out),
+            CompletionShell::Fish => clap_complete::generate(Shell::Fish, command, name, out),
+            CompletionShell::Elvish => clap_complete::generate(Shell::Elvish, command, name, out),
+            CompletionShell::PowerShell => clap_complete::generate(Shell::PowerShell, command, name, out),
+            CompletionShell::Nushell => clap_complete::generate(clap_complete_nushell::Nushell, command, name, out),
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -59,12 +148,18 @@ enum NotifyAction {
         /// Session ID (defaults to CLAUDE_SESSION_ID env var)
         #[arg(long, env = "CLAUDE_SESSION_ID")]
         session_id: String,
-        /// Project path (defaults to current directory)
+        /// Project path (defaults to current directory; resolved to its git worktree root)
         #[arg(long, default_value = ".")]
         project_path: String,
+        /// Register the path as given instead of its enclosing worktree root
+        #[arg(long)]
+        exact_path: bool,
         /// AI CLI tool name (claude, kiro, opencode, codex)
         #[arg(long)]
         tool: Option<String>,
+        /// Print the result as JSON (`{"ok":true,"session_id":...,"delivered":...}`)
+        #[arg(long)]
+        json: bool,
     },
     /// Update workspace status
     Status {
@@ -76,12 +171,24 @@ enum NotifyAction {
         /// Optional status message
         #[arg(short, long)]
         message: Option<String>,
+        /// Print the result as JSON (`{"ok":true,"session_id":...,"delivered":...}`)
+        #[arg(long)]
+        json: bool,
     },
     /// Unregister a workspace session
     Unregister {
         /// Session ID (defaults to CLAUDE_SESSION_ID env var)
         #[arg(long, env = "CLAUDE_SESSION_ID")]
         session_id: String,
+        /// Print the result as JSON (`{"ok":true,"session_id":...,"delivered":...}`)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Keep a session alive without changing its status
+    Heartbeat {
+        /// Session ID (defaults to CLAUDE_SESSION_ID env var)
+        #[arg(long, env = "CLAUDE_SESSION_ID")]
+        session_id: String,
     },
     /// Notify tab focus change (from multiplexer plugin/hook)
     TabFocus {
@@ -91,10 +198,13 @@ enum NotifyAction {
 }
 
 fn main() -> Result<()> {
+    // `COMPLETE=<shell>` で呼ばれたら補完候補を出力して終了する
+    CompleteEnv::with_factory(Cli::command).complete();
+
     let cli = Cli::parse();
 
     // ログ初期化
-    init_logging(&cli.log_level)?;
+    let (log_file_path, log_buffer) = init_logging(&cli.log_level)?;
 
     match cli.command {
         Some(Commands::Daemon) => {
@@ -105,7 +215,17 @@ fn main() -> Result<()> {
         Some(Commands::Notify { action }) => handle_notify(action),
         Some(Commands::SetupPlugin) => handle_setup_plugin(),
         Some(Commands::SetupTmuxHook) => handle_setup_tmux_hook(),
-        Some(Commands::Tui) | None => run_tui(),
+        Some(Commands::Schema) => handle_schema(),
+        Some(Commands::Completions { shell }) => {
+            shell.generate(&mut Cli::command(), &mut std::io::stdout());
+            Ok(())
+        }
+        Some(Commands::Version { verbose }) => handle_version(verbose),
+        Some(Commands::List { format }) => handle_list(format),
+        Some(Commands::Open { repo, branch }) => handle_open(&repo, branch.as_deref()),
+        Some(Commands::Path { repo, branch }) => handle_path(&repo, branch.as_deref()),
+        Some(Commands::Tui { paths }) => run_tui(log_file_path, log_buffer, &paths),
+        None => run_tui(log_file_path, log_buffer, &[]),
     }
 }
 
@@ -113,43 +233,63 @@ fn handle_notify(action: NotifyAction) -> Result<()> {
     let socket_path = notify::socket_path();
 
     // Canonicalize project_path for register action
-    let message = match action {
+    let (message, json) = match action {
         NotifyAction::Register {
             session_id,
             project_path,
+            exact_path,
             tool,
+            json,
         } => {
-            let project_path = std::fs::canonicalize(&project_path)
-                .unwrap_or_else(|_| std::path::PathBuf::from(&project_path))
-                .to_string_lossy()
-                .to_string();
-            NotifyMessage::Register {
+            let path = std::fs::canonicalize(&project_path)
+                .unwrap_or_else(|_| std::path::PathBuf::from(&project_path));
+            // サブディレクトリから呼ばれてもワークスペース（worktreeルート）に紐付ける
+            let path = if exact_path {
+                path
+            } else {
+                workspace_manager::workspace::worktree_root(&path).unwrap_or(path)
+            };
+            let project_path = path.to_string_lossy().to_string();
+            let message = NotifyMessage::Register {
                 session_id,
                 project_path,
                 tool,
-            }
+            };
+            (message, json)
         }
         NotifyAction::Status {
             session_id,
             status,
             message,
-        } => NotifyMessage::Status {
-            session_id,
-            status,
-            message,
-        },
-        NotifyAction::Unregister { session_id } => NotifyMessage::Unregister { session_id },
-        NotifyAction::TabFocus { tab_name } => NotifyMessage::TabFocus { tab_name },
+            json,
+        } => {
+            let message = NotifyMessage::Status {
+                session_id,
+                status,
+                message,
+            };
+            (message, json)
+        }
+        NotifyAction::Unregister { session_id, json } => (NotifyMessage::Unregister { session_id }, json),
+        NotifyAction::Heartbeat { session_id } => (NotifyMessage::Heartbeat { session_id }, false),
+        NotifyAction::TabFocus { tab_name } => (NotifyMessage::TabFocus { tab_name }, false),
     };
 
-    match notify::send_notification(&socket_path, &message) {
+    let result = notify::send_notification(&socket_path, &message);
+    if json {
+        let output = notify_result_json(message.session_id(), &result);
+        println!("{}", output);
+        return Ok(());
+    }
+
+    match result {
         Ok(()) => {
             info!("Notification sent successfully");
             Ok(())
         }
         Err(e) => {
-            // If socket doesn't exist, TUI is not running - silently succeed
-            if socket_path.exists() {
+            // If TUI is not running, silently succeed
+            if !is_tui_not_running(&e) {
                 eprintln!("Warning: Failed to send notification: {}", e);
             }
             Ok(())
@@ -157,6 +297,25 @@ fn handle_notify(action: NotifyAction) -> Result<()> {
     }
 }
 
+/// `notify --json` の出力
+///
+/// TUI が起動していない（ソケットがない・接続を拒否された）場合は成功扱いで `delivered: false`。
+/// それ以外の理由で送れなかった場合だけ `ok: false` とエラーを返す。
+fn notify_result_json(session_id: &str, result: &Result<()>) -> serde_json::Value {
+    match result {
+        Ok(()) => serde_json::json!({ "ok": true, "session_id": session_id, "delivered": true }),
+        Err(e) if is_tui_not_running(e) => serde_json::json!({ "ok": true, "session_id": session_id, "delivered": false }),
+        Err(e) => serde_json::json!({ "ok": false, "session_id": session_id, "error": format!("{:#}", e) }),
+    }
+}
+
+/// 送信エラーが TUI 未起動によるものか（ソケットがない、または古いソケットで接続を拒否された）
+fn is_tui_not_running(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<std::io::Error>().is_some_and(|e| {
+        matches!(e.kind(), std::io::ErrorKind::NotFound | std::io::ErrorKind::ConnectionRefused)
+    })
+}
+
 fn handle_setup_plugin() -> Result<()> {
     use std::process::Command;
 
@@ -241,6 +400,241 @@ fn handle_setup_tmux_hook() -> Result<()> {
     Ok(())
 }
 
+fn handle_schema() -> Result<()> {
+    let schema = schemars::schema_for!(Config);
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+fn handle_version(verbose: bool) -> Result<()> {
+    println!("workspace-manager {}", env!("CARGO_PKG_VERSION"));
+    if !verbose {
+        return Ok(());
+    }
+
+    let config_path = Config::config_path()?;
+    let config = read_config(&config_path);
+    let exists = |p: &Path| if p.exists() { "found" } else { "missing" };
+
+    println!();
+    println!("config:      {} ({})", config_path.display(), exists(&config_path));
+
+    let mux = multiplexer::create_multiplexer(config.multiplexer.as_ref(), &config.zellij);
+    let mode = if mux.is_internal() { "internal" } else { "external" };
+    println!(
+        "multiplexer: {:?} ({}, {})",
+        mux.backend(),
+        mode,
+        if mux.is_available() { "available" } else { "unavailable" }
+    );
+    if let Some(session) = mux.session_name() {
+        println!("session:     {}", session);
+    }
+    println!("tmux:        {}", tool_version("tmux", "-V"));
+    println!("zellij:      {}", tool_version("zellij", "--version"));
+
+    let logwatch = &config.logwatch;
+    for dir in &logwatch.claude_homes {
+        let dir = expand_tilde(dir);
+        println!("claude home: {} ({})", dir.display(), exists(&dir));
+    }
+    println!("kiro db:     {} ({})", logwatch.kiro_db_path.display(), exists(&logwatch.kiro_db_path));
+    let opencode_dir = expand_tilde(&logwatch.opencode_storage_dir);
+    println!("opencode:    {} ({})", opencode_dir.display(), exists(&opencode_dir));
+    let codex_home = expand_tilde(&logwatch.codex_home);
+    println!("codex home:  {} ({})", codex_home.display(), exists(&codex_home));
+    println!("socket:      {}", notify::socket_path().display());
+
+    Ok(())
+}
+
+/// 設定ファイルを読むだけ（Config::load は未作成時にデフォルトを書き出すため使わない）
+///
+/// 読めなければ標準エラーに理由を出してデフォルト設定を使う。
+fn read_config(config_path: &Path) -> Config {
+    if !config_path.exists() {
+        return Config::default();
+    }
+    let parsed = std::fs::read_to_string(config_path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| Config::parse(&content));
+    match parsed {
+        Ok((config, notices)) => {
+            for notice in &notices {
+                tracing::warn!("{}", notice);
+            }
+            config
+        }
+        Err(e) => {
+            eprintln!("Failed to read {}: {:#}", config_path.display(), e);
+            Config::default()
+        }
+    }
+}
+
+/// 補完用に設定を読む（シェルの補完を乱さないよう、エラーや通知は出さずに既定値を使う）
+fn read_config_silently(config_path: &Path) -> Config {
+    std::fs::read_to_string(config_path)
+        .ok()
+        .and_then(|content| Config::parse(&content).ok())
+        .map(|(config, _)| config)
+        .unwrap_or_default()
+}
+
+/// TUI と同じ条件でワークスペースを探して標準出力に書き出す
+fn handle_list(format: ListFormat) -> Result<()> {
+    let config = read_config(&Config::config_path()?);
+    // コミット数は JSON にしか出ないので TSV では数えない
+    let activity_days = match format {
+        ListFormat::Tsv => 0,
+        ListFormat::Json => config.activity_days,
+    };
+    let mut state = scan_with_config(&config, activity_days);
+    if let Some((paths, days)) = state.take_recent_commits_request() {
+        state.set_recent_commits(&recent_commit_counts(&paths, days));
+    }
+
+    let mut out = io::stdout().lock();
+    let written = match format {
+        ListFormat::Tsv => state
+            .workspaces
+            .iter()
+            .try_for_each(|ws| writeln!(out, "{}\t{}\t{}", ws.repo_name, ws.branch, ws.project_path)),
+        ListFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(&state.workspaces)?),
+    };
+    match written {
+        // `list | head` などで読み手が先に閉じても失敗にしない
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// TUI を起動せずに設定の search_paths をスキャンした状態を作る
+fn scan_with_config(config: &Config, activity_days: u32) -> AppState {
+    let mut state = AppState::new();
+    state.search_paths = config_search_paths(config);
+    state.max_scan_depth = config.max_scan_depth;
+    state.activity_days = activity_days;
+    state.tab_name_template = config.effective_multiplexer_config().tab_name_template;
+    state.path_display = config.path_display;
+    state.scan_workspaces();
+    state
+}
+
+/// 各ワークスペースの直近 `days` 日のコミット数（履歴を辿るので TUI では別スレッドで呼ぶ）
+fn recent_commit_counts(paths: &[String], days: u32) -> Vec<(String, usize)> {
+    paths
+        .iter()
+        .map(|path| (path.clone(), count_recent_commits(Path::new(path), days)))
+        .collect()
+}
+
+/// リポジトリ名（とブランチ）でワークスペースを探す（見つからなければエラー）
+fn find_named_workspace<'a>(state: &'a AppState, repo: &str, branch: Option<&str>) -> Result<&'a Workspace> {
+    state
+        .find_workspace_by_name(repo, branch)
+        .and_then(|idx| state.workspaces.get(idx))
+        .ok_or_else(|| match branch {
+            Some(branch) => anyhow::anyhow!("No workspace found for {} {}", repo, branch),
+            None => anyhow::anyhow!("No workspace found for {}", repo),
+        })
+}
+
+/// リポジトリ名の補完候補
+fn repo_candidates() -> Vec<CompletionCandidate> {
+    name_candidates(None)
+}
+
+/// ブランチ名の補完候補（入力済みのリポジトリのもの）
+fn branch_candidates() -> Vec<CompletionCandidate> {
+    let args: Vec<std::ffi::OsString> = std::env::args_os().collect();
+    let index = std::env::var("_CLAP_COMPLETE_INDEX").ok().and_then(|i| i.parse().ok());
+    match completion_repo(&args, index) {
+        Some(repo) => name_candidates(Some(&repo)),
+        None => Vec::new(),
+    }
+}
+
+fn name_candidates(repo: Option<&str>) -> Vec<CompletionCandidate> {
+    let Ok(config_path) = Config::config_path() else {
+        return Vec::new();
+    };
+    let state = scan_with_config(&read_config_silently(&config_path), 0);
+    state.name_candidates(repo).into_iter().map(CompletionCandidate::new).collect()
+}
+
+/// 補完中の語より前を clap で解析し、`open` / `path` のリポジトリ名を取り出す
+///
+/// `args` は補完スクリプトからの呼び出し（`bin -- words...`）そのまま。
+/// `index` は補完中の語の位置で、渡されないシェルでは最後の語とみなす。
+fn completion_repo(args: &[std::ffi::OsString], index: Option<usize>) -> Option<String> {
+    let words = &args[args.iter().position(|arg| arg == "--")? + 1..];
+    let index = index.unwrap_or(words.len().saturating_sub(1)).min(words.len());
+    match Cli::try_parse_from(&words[..index]).ok()?.command? {
+        Commands::Open { repo, .. } | Commands::Path { repo, .. } => Some(repo),
+        _ => None,
+    }
+}
+
+/// 名前で指定したワークスペースの絶対パスを出力する（シェルの cd 用）
+fn handle_path(repo: &str, branch: Option<&str>) -> Result<()> {
+    let config = read_config(&Config::config_path()?);
+    let state = scan_with_config(&config, 0);
+    println!("{}", find_named_workspace(&state, repo, branch)?.project_path);
+    Ok(())
+}
+
+/// 名前で指定したワークスペースを TUI の選択と同じ流れで開く
+fn handle_open(repo: &str, branch: Option<&str>) -> Result<()> {
+    let config = read_config(&Config::config_path()?);
+    let state = scan_with_config(&config, 0);
+    let ws = find_named_workspace(&state, repo, branch)?;
+
+    let mux = multiplexer::create_multiplexer(config.multiplexer.as_ref(), &config.zellij);
+    let path = Path::new(&ws.project_path);
+    if mux.is_available() && !mux.is_internal() && mux.session_name().is_some() {
+        let tab_name = config.generate_tab_name(&ws.repo_name, &ws.branch);
+        let layout = config.effective_default_layout();
+        match mux.open_workspace_window(&tab_name, path, layout.as_deref())? {
+            WindowActionResult::SwitchedToExisting(name) => println!("Switched to tab: {}", name),
+            WindowActionResult::CreatedNew(name) => println!("Created tab: {}", name),
+            WindowActionResult::SessionNotFound(session) => anyhow::bail!("Session '{}' not found", session),
+        }
+        run_post_select_command(&config);
+        return Ok(());
+    }
+
+    // マルチプレクサ外では端末エディタをそのままこの端末で動かす
+    let editor = terminal::resolve_editor(&config.editor)?;
+    if !mux.is_available() && terminal::is_terminal_editor(&editor, &config.terminal_editors) {
+        let mut parts = editor.split_whitespace();
+        let program = parts.next().unwrap_or("vi");
+        let status = std::process::Command::new(program)
+            .args(parts)
+            .arg(path)
+            .current_dir(path)
+            .status()?;
+        if !status.success() {
+            anyhow::bail!("{} exited with {}", editor, status);
+        }
+        return Ok(());
+    }
+    let editor = open_path_in_editor(&*mux, &config, path)?;
+    println!("Opened in {}: {}", editor, state.display_path(&ws.project_path));
+    Ok(())
+}
+
+/// 外部ツールのバージョン文字列を取得（未インストールなら "not found"）
+fn tool_version(program: &str, flag: &str) -> String {
+    std::process::Command::new(program)
+        .arg(flag)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_else(|| "not found".to_string())
+}
+
 fn dirs_plugin() -> std::path::PathBuf {
     directories::BaseDirs::new()
         .map(|d| d.home_dir().join(".config/zellij/plugins"))
@@ -273,37 +667,63 @@ fn find_workspace_root() -> Result<std::path::PathBuf> {
     }
 }
 
-fn init_logging(level: &str) -> Result<()> {
+/// ログを初期化し、ログファイルのパスとログビューア用のリングバッファを返す
+fn init_logging(level: &str) -> Result<(std::path::PathBuf, LogBuffer)> {
     let log_dir = directories::ProjectDirs::from("", "", "workspace-manager")
         .map(|d| d.data_dir().to_path_buf())
         .unwrap_or_else(|| std::env::temp_dir().join("workspace-manager"));
 
     std::fs::create_dir_all(&log_dir)?;
-    let log_file = std::fs::File::create(log_dir.join("workspace-manager.log"))?;
+    let log_path = log_dir.join("workspace-manager.log");
+    let log_file = std::fs::File::create(&log_path)?;
 
     let filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(level));
 
+    let ring_layer = RingBufferLayer::new(DEFAULT_LOG_BUFFER_CAPACITY);
+    let log_buffer = ring_layer.buffer();
+
     tracing_subscriber::registry()
         .with(filter)
-        .with(tracing_subscriber::fmt::layer().with_writer(log_file))
+        .with(tracing_subscriber::fmt::layer().with_writer(log_file).with_ansi(false))
+        .with(ring_layer)
         .init();
 
     info!("Workspace Manager starting");
-    Ok(())
+    Ok((log_path, log_buffer))
 }
 
-fn run_tui() -> Result<()> {
+/// `--path` で指定されたディレクトリのうち存在するものだけ返す（それ以外は警告して除く）
+///
+/// 指定があったのに一つも残らなければエラー（設定の search_paths には戻さない）。
+fn valid_scan_paths(paths: &[std::path::PathBuf]) -> Result<Vec<std::path::PathBuf>> {
+    let valid: Vec<std::path::PathBuf> = paths
+        .iter()
+        .map(|p| expand_tilde(p))
+        .filter(|p| {
+            let ok = p.is_dir();
+            if !ok {
+                eprintln!("warning: --path {} is not a directory, skipping", p.display());
+                tracing::warn!("Skipping --path {}: not a directory", p.display());
+            }
+            ok
+        })
+        .collect();
+    if !paths.is_empty() && valid.is_empty() {
+        anyhow::bail!("None of the --path directories exist");
+    }
+    Ok(valid)
+}
+
+fn run_tui(log_file_path: std::path::PathBuf, log_buffer: LogBuffer, scan_paths: &[std::path::PathBuf]) -> Result<()> {
     // 設定を先に読み込む（ファイルがなければ作成）
     let mut config = Config::load().unwrap_or_default();
+    // --path はこの起動だけ search_paths を置き換える（設定には保存しない）
+    let scan_paths = valid_scan_paths(scan_paths)?;
+    // 一部だけスキャンした起動では他のリポジトリの折りたたみ状態を消さないよう保存しない
+    let save_persisted_state = scan_paths.is_empty();
 
-    // 組み込みレイアウトを生成（Zellijバックエンド時のみ）
-    let eff_config = config.effective_multiplexer_config();
-    if eff_config.backend == "zellij" || eff_config.backend == "auto" {
-        if let Err(e) = config.zellij.generate_builtin_layouts() {
-            tracing::warn!("Failed to generate layouts: {}", e);
-        }
-    }
+    generate_layouts_for_backend(&config);
 
     // Create tokio runtime for async operations
     let runtime = tokio::runtime::Runtime::new()?;
@@ -323,16 +743,19 @@ fn run_tui() -> Result<()> {
     // Start log watcher if enabled (event-driven for Claude Code, polling for Kiro CLI)
     // Create watch channel to share workspace list with logwatch service
     let (workspace_watch_tx, workspace_watch_rx) = tokio::sync::watch::channel::<Vec<String>>(Vec::new());
-    let logwatch_trigger: Option<LogWatchTrigger> = if config.logwatch.enabled {
+    let last_poll = PollTimestamp::default();
+    let (logwatch_trigger, logwatch_refresh): (Option<LogWatchTrigger>, Option<LogWatchRefresh>) = if config.logwatch.enabled {
         let (trigger_tx, trigger_rx) = tokio::sync::mpsc::channel::<String>(100);
+        let (refresh_tx, refresh_rx) = tokio::sync::mpsc::channel::<String>(16);
         let logwatch_tx = notify_tx.clone();
         let logwatch_config = config.logwatch.clone();
+        let logwatch_last_poll = last_poll.clone();
         runtime.spawn(async move {
-            run_logwatch(logwatch_config, logwatch_tx, trigger_rx, workspace_watch_rx).await;
+            run_logwatch(logwatch_config, logwatch_tx, trigger_rx, refresh_rx, workspace_watch_rx, logwatch_last_poll).await;
         });
-        Some(trigger_tx)
+        (Some(trigger_tx), Some(refresh_tx))
     } else {
-        None
+        (None, None)
     };
     // Keep workspace_watch_tx for updating workspace list
     let workspace_watch_tx = if config.logwatch.enabled { Some(workspace_watch_tx) } else { None };
@@ -342,21 +765,41 @@ fn run_tui() -> Result<()> {
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
-    let mut state = AppState::new();
-    state.use_nerd_font = config.use_nerd_font;
-    state.tab_name_template = config.effective_multiplexer_config().tab_name_template;
-    state.favorite_repos = config.favorite_repos.iter().cloned().collect();
-    let yazi_config = config.yazi.clone();
+    let persisted_state_path = PersistedState::default_path();
+    let mut state = AppState::with_persisted_state(PersistedState::load(&persisted_state_path));
+    apply_config_to_state(&mut state, &config);
+    state.search_paths = if scan_paths.is_empty() {
+        config_search_paths(&config)
+    } else {
+        scan_paths
+    };
+    state.log_file_path = Some(log_file_path);
+    state.log_buffer = Some(log_buffer);
+    state.last_poll = last_poll;
     let mut mux = multiplexer::create_multiplexer(
         config.multiplexer.as_ref(),
         &config.zellij,
     );
-    let worktree_manager = WorktreeManager::new(config.worktree.clone());
+    let mut worktree_manager = WorktreeManager::new(config.worktree.clone());
 
     state.scan_workspaces();
     state.rebuild_tree_with_manager(Some(&worktree_manager));
 
-    let result = run_app(&mut terminal, &mut state, &mut *mux, &mut config, &worktree_manager, notify_rx, logwatch_trigger, workspace_watch_tx, &runtime, &yazi_config);
+    let channels = AppChannels {
+        event_tx: notify_tx,
+        notify_rx,
+        logwatch_trigger,
+        logwatch_refresh,
+        workspace_watch_tx,
+    };
+    let result = run_app(&mut terminal, &mut state, &mut mux, &mut config, &mut worktree_manager, channels, &runtime);
+
+    // 折りたたみ状態を次回起動用に保存
+    if save_persisted_state {
+        if let Err(e) = state.persisted_state().save(&persisted_state_path) {
+            tracing::warn!("Failed to save state: {}", e);
+        }
+    }
 
     // Clean up socket on exit
     let socket_path = notify::socket_path();
@@ -373,151 +816,290 @@ fn run_tui() -> Result<()> {
     result
 }
 
+/// 組み込みレイアウトを生成（Zellijバックエンド時のみ）
+fn generate_layouts_for_backend(config: &Config) {
+    let eff_config = config.effective_multiplexer_config();
+    if eff_config.backend == "zellij" || eff_config.backend == "auto" {
+        if let Err(e) = config.zellij.generate_builtin_layouts() {
+            tracing::warn!("Failed to generate layouts: {}", e);
+        }
+    }
+}
+
+/// 設定の search_paths（チルダ展開済み、空なら既定の検索パス）
+fn config_search_paths(config: &Config) -> Vec<std::path::PathBuf> {
+    if config.search_paths.is_empty() {
+        return workspace_manager::workspace::get_default_search_paths();
+    }
+    config.search_paths.iter().map(|p| expand_tilde(p)).collect()
+}
+
+/// 設定値を AppState に反映する（起動時と設定の再読み込み時）
+fn apply_config_to_state(state: &mut AppState, config: &Config) {
+    state.use_nerd_font = config.use_nerd_font;
+    state.theme = workspace_manager::ui::theme::Theme::from_config(&config.theme);
+    state.activity_days = config.activity_days;
+    state.max_scan_depth = config.max_scan_depth;
+    state.session_timeout_secs = config.session_timeout_secs;
+    state.error_confirm_polls = config.error_confirm_polls;
+    state.disconnected_session_retention = (config.disconnected_session_retention_secs > 0)
+        .then(|| Duration::from_secs(config.disconnected_session_retention_secs));
+    state.max_sessions = config.max_sessions;
+    state.hide_idle_sessions = config.hide_idle_sessions;
+    state.session_sort = config.session_sort;
+    state.path_display = config.path_display;
+    state.auto_expand_on_activity = config.auto_expand_on_activity;
+    state.auto_add_unknown_workspaces = config.auto_add_unknown_workspaces;
+    state.show_remote_host = config.show_remote_host;
+    state.text_limits = config.text_limits();
+    state.status_message_ttl =
+        (config.status_message_ttl_secs > 0).then(|| Duration::from_secs(config.status_message_ttl_secs));
+    state.sticky_error_messages = config.sticky_error_messages;
+    state.detail_pane_percent = config.detail_pane_percent.clamp(20, 80);
+    state.tab_name_template = config.effective_multiplexer_config().tab_name_template;
+    state.favorite_repos = config.favorite_repos.iter().cloned().collect();
+}
+
+/// 設定ファイルを読み直し、実行中の設定・worktree 管理・マルチプレクサに反映する
+///
+/// 読み込みに失敗したら今の設定のまま。マルチプレクサはバックエンドかセッション名が
+/// 変わったときだけ作り直す。ポーリング間隔など起動時に決まるものは再起動が必要。
+fn reload_config(
+    state: &mut AppState,
+    mux: &mut Box<dyn Multiplexer>,
+    config: &mut Config,
+    worktree_manager: &mut WorktreeManager,
+) {
+    let new_config = match Config::load() {
+        Ok(new_config) => new_config,
+        Err(e) => {
+            state.set_error(format!("Config not reloaded: {}", e));
+            return;
+        }
+    };
+
+    generate_layouts_for_backend(&new_config);
+    let old_mux = config.effective_multiplexer_config();
+    let new_mux = new_config.effective_multiplexer_config();
+    let mux_changed = old_mux.backend != new_mux.backend || old_mux.session_name != new_mux.session_name;
+    if mux_changed {
+        *mux = multiplexer::create_multiplexer(new_config.multiplexer.as_ref(), &new_config.zellij);
+    }
+    *worktree_manager = WorktreeManager::new(new_config.worktree.clone());
+
+    // --path で絞った起動ならそのまま、設定由来なら新しい search_paths を使う
+    if state.search_paths == config_search_paths(config) {
+        state.search_paths = config_search_paths(&new_config);
+    }
+    apply_config_to_state(state, &new_config);
+    *config = new_config;
+
+    state.invalidate_branch_stats();
+    state.scan_workspaces();
+    state.rebuild_tree_with_manager(Some(worktree_manager));
+    if mux_changed {
+        state.set_status(format!("Config reloaded (multiplexer: {:?})", mux.backend()));
+    } else {
+        state.set_status("Config reloaded".to_string());
+    }
+}
+
 /// Simple Claude Code status service (hooks-based, no AI analysis)
 /// Channel for triggering log analysis (used for shutdown signaling)
 type LogWatchTrigger = tokio::sync::mpsc::Sender<String>;
 
-/// Normalize path for comparison (expand ~ and resolve)
-fn normalize_path_for_comparison(path: &str) -> String {
-    path.replace("~", &std::env::var("HOME").unwrap_or_default())
+/// Channel for re-polling one workspace on demand (manual `R`)
+type LogWatchRefresh = tokio::sync::mpsc::Sender<String>;
+
+/// バックグラウンド処理からメインループへイベントを送るチャネル
+type EventSender = tokio::sync::mpsc::Sender<AppEvent>;
+
+/// メインループとバックグラウンド処理をつなぐチャネル一式
+struct AppChannels {
+    /// バックグラウンド処理からメインループへのイベント送信側
+    event_tx: EventSender,
+    /// 通知・ログ監視・バックグラウンド処理からのイベント
+    notify_rx: tokio::sync::mpsc::Receiver<AppEvent>,
+    logwatch_trigger: Option<LogWatchTrigger>,
+    /// 選択中ワークスペースの即時再取得（`R`）をログ監視へ依頼する
+    logwatch_refresh: Option<LogWatchRefresh>,
+    /// ログ監視に渡すワークスペースパス一覧
+    workspace_watch_tx: Option<tokio::sync::watch::Sender<Vec<String>>>,
+}
+
+/// タブ状態・ペイン・ワークスペースを再取得する間隔
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// `event_poll_ms` の許容範囲（ミリ秒）
+const MIN_EVENT_POLL_MS: u64 = 10;
+const MAX_EVENT_POLL_MS: u64 = 1000;
+
+/// Kiro DB read failures in a row before the user is told about it
+const KIRO_DB_FAILURE_THRESHOLD: u32 = 3;
+
+/// Spawn the polling task of a process-matched session store (OpenCode, Codex)
+fn spawn_session_poller<F>(
+    fetcher: F,
+    poll_interval: Duration,
+    poll_tx: tokio::sync::mpsc::Sender<AppEvent>,
+    mut poll_workspace_rx: tokio::sync::watch::Receiver<Vec<String>>,
+    poll_clock: PollTimestamp,
+    mut refresh_rx: tokio::sync::broadcast::Receiver<String>,
+) -> tokio::task::JoinHandle<()>
+where
+    F: ProcessMatchedFetcher + Send + 'static,
+{
+    let mut poller = SessionPoller::new(fetcher);
+
+    tokio::spawn(async move {
+        if !poller.fetcher().is_available() {
+            tracing::info!(
+                "{} session store not found at {:?}, polling disabled",
+                F::NAME,
+                poller.fetcher().store_path()
+            );
+            return;
+        }
+
+        tracing::info!(
+            "{} session polling started (interval: {}s, store: {:?})",
+            F::NAME,
+            poll_interval.as_secs(),
+            poller.fetcher().store_path()
+        );
+
+        loop {
+            // Wait for the next tick, or an on-demand refresh of one workspace
+            let refresh_path = tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => None,
+                Ok(path) = refresh_rx.recv() => Some(path),
+            };
+
+            let events = match refresh_path {
+                Some(path) => poller.poll_workspace(&path),
+                None => {
+                    // Get current workspace list
+                    let workspaces = poll_workspace_rx.borrow_and_update().clone();
+
+                    if workspaces.is_empty() {
+                        continue;
+                    }
+
+                    poller.poll(&workspaces)
+                }
+            };
+            poll_clock.mark();
+            for event in events {
+                if poll_tx.send(event).await.is_err() {
+                    tracing::warn!("{} poll receiver dropped", F::NAME);
+                    return;
+                }
+            }
+        }
+    })
 }
 
 /// Run log watcher service with new architecture:
 /// - Claude Code: sessions-index.json polling
 /// - Kiro CLI: SQLite polling (reads status from database)
+/// - OpenCode: session store polling (reads the JSON session files)
+/// - Codex: rollout polling (reads the JSONL session files)
 async fn run_logwatch(
     config: workspace_manager::app::LogWatchConfig,
     tx: tokio::sync::mpsc::Sender<AppEvent>,
     mut trigger_rx: tokio::sync::mpsc::Receiver<String>,
+    mut refresh_rx: tokio::sync::mpsc::Receiver<String>,
     workspace_rx: tokio::sync::watch::Receiver<Vec<String>>,
+    last_poll: PollTimestamp,
 ) {
+    for name in &config.tracked_tools {
+        if !AiTool::ALL.iter().any(|tool| name.trim().eq_ignore_ascii_case(tool.name())) {
+            tracing::warn!("Unknown tool in logwatch.tracked_tools: {}", name);
+        }
+    }
+
+    // All polling tasks match sessions to running processes; hook-driven setups
+    // can turn them off and rely on notify messages only
+    let claude_polling = config.tracks(AiTool::Claude) && config.process_matching_enabled;
+    let kiro_polling = config.tracks(AiTool::Kiro) && config.process_matching_enabled;
+    let opencode_polling = config.tracks(AiTool::OpenCode) && config.process_matching_enabled;
+    let codex_polling = config.tracks(AiTool::Codex) && config.process_matching_enabled;
+
     tracing::info!(
-        "Log watch service started (Claude polling: {}, Kiro polling: {})",
-        config.claude_hooks_enabled,  // Reusing this config flag for Claude polling
-        config.kiro_polling_enabled
+        "Log watch service started (tracked tools: {:?}, Claude polling: {}, Kiro polling: {}, OpenCode polling: {}, Codex polling: {}, tmux capture: {}, process matching: {})",
+        config.tracked_tools,
+        claude_polling,
+        kiro_polling,
+        opencode_polling,
+        codex_polling,
+        config.tmux_capture_enabled,
+        config.process_matching_enabled
     );
 
+    // Process discovery shared by all polling tasks
+    let processes: Arc<dyn ProcessProvider> = Arc::new(SystemProcessProvider::new(
+        config.claude_process_names.clone(),
+        config.kiro_process_names.clone(),
+        config.opencode_process_names.clone(),
+        config.codex_process_names.clone(),
+    ));
+
+    // On-demand refresh of a single workspace, fanned out to all polling tasks
+    let (refresh_tx, _) = tokio::sync::broadcast::channel::<String>(16);
+
     // Claude Code: sessions-index.json polling task
-    let claude_polling_handle = if config.claude_hooks_enabled {
-        let claude_fetcher = ClaudeSessionsFetcher::new();
+    let claude_polling_handle = if claude_polling {
+        let claude_fetcher = ClaudeSessionsFetcher::with_config(ClaudeSessionsConfig {
+            claude_dirs: config.claude_homes.iter().map(|dir| expand_tilde(dir)).collect(),
+            ..Default::default()
+        })
+        .with_process_provider(processes.clone());
+        let mut claude_poller = ClaudePoller::new(claude_fetcher);
         let poll_interval = Duration::from_secs(config.kiro_polling_interval_secs); // Use same interval
         let poll_tx = tx.clone();
         let mut poll_workspace_rx = workspace_rx.clone();
+        let poll_clock = last_poll.clone();
+        let mut refresh_rx = refresh_tx.subscribe();
 
         Some(tokio::spawn(async move {
-            if !claude_fetcher.is_available() {
+            if !claude_poller.fetcher().is_available() {
                 tracing::info!("Claude projects directory not found, polling disabled");
                 return;
             }
 
             tracing::info!(
-                "Claude sessions-index polling started (interval: {}s, dir: {:?})",
+                "Claude sessions-index polling started (interval: {}s, dirs: {:?})",
                 poll_interval.as_secs(),
-                claude_fetcher.claude_dir()
+                claude_poller.fetcher().claude_dirs()
             );
 
-            // Track previously active sessions to detect disconnections
-            let mut prev_active_sessions: std::collections::HashSet<String> = std::collections::HashSet::new();
-
             loop {
-                tokio::time::sleep(poll_interval).await;
-
-                // Get current workspace list
-                let workspaces = poll_workspace_rx.borrow_and_update().clone();
-
-                if workspaces.is_empty() {
-                    continue;
-                }
-
-                // Get running Claude processes with their session IDs
-                let running_processes = claude_fetcher.get_running_processes();
-
-                // Fetch sessions from Claude sessions-index.json
-                let sessions_by_path = claude_fetcher.get_sessions(&workspaces);
-                let mut current_active_sessions: std::collections::HashSet<String> = std::collections::HashSet::new();
-
-                for (path, sessions) in &sessions_by_path {
-                    let normalized_path = normalize_path_for_comparison(path);
-
-                    // Get workspace processes (already subagent-filtered)
-                    let workspace_processes: Vec<&ClaudeProcessInfo> = running_processes.iter()
-                        .filter(|p| p.cwd == normalized_path)
-                        .collect();
-
-                    // Unique session IDs from running processes
-                    let unique_session_ids: std::collections::HashSet<&str> = workspace_processes.iter()
-                        .filter_map(|p| p.session_id.as_deref())
-                        .collect();
-
-                    // Count processes without session ID (cap at 1 - unlikely to have multiple new sessions simultaneously)
-                    let processes_without_id = workspace_processes.iter()
-                        .filter(|p| p.session_id.is_none())
-                        .count()
-                        .min(1);
-
-                    let effective_session_count = unique_session_ids.len() + processes_without_id;
-
-                    tracing::debug!(
-                        "Claude session matching for {}: total_processes={}, unique_ids={}, without_id={}, effective={}",
-                        normalized_path,
-                        workspace_processes.len(),
-                        unique_session_ids.len(),
-                        processes_without_id,
-                        effective_session_count,
-                    );
-
-                    if effective_session_count == 0 {
-                        continue;
-                    }
+                // Wait for the next tick, or an on-demand refresh of one workspace
+                let refresh_path = tokio::select! {
+                    _ = tokio::time::sleep(poll_interval) => None,
+                    Ok(path) = refresh_rx.recv() => Some(path),
+                };
 
-                    // Two-pass matching: first exact ID matches, then fill remaining slots with newest
-                    let mut id_matched: Vec<&ClaudeSession> = Vec::new();
-                    let mut unmatched: Vec<&ClaudeSession> = Vec::new();
+                let events = match refresh_path {
+                    Some(path) => claude_poller.poll_workspace(&path),
+                    None => {
+                        // Get current workspace list
+                        let workspaces = poll_workspace_rx.borrow_and_update().clone();
 
-                    for session in sessions {
-                        if unique_session_ids.contains(session.session_id.as_str()) {
-                            id_matched.push(session);
-                        } else {
-                            unmatched.push(session);
+                        if workspaces.is_empty() {
+                            continue;
                         }
-                    }
 
-                    // Fill remaining slots (for processes without --resume) with newest unmatched sessions
-                    let remaining_slots = effective_session_count.saturating_sub(id_matched.len());
-                    let fallback_sessions = unmatched.into_iter().take(remaining_slots);
-
-                    for session in id_matched.into_iter().chain(fallback_sessions) {
-                        current_active_sessions.insert(session.external_id.clone());
-                        // Override status to Working since process detection confirmed it's running.
-                        // session.to_session_status() may return Idle if fileMtime in
-                        // sessions-index.json is stale, but we trust process detection.
-                        let mut session_status = session.to_session_status();
-                        session_status.status = workspace_manager::logwatch::StatusState::Working;
-                        let event = AppEvent::SessionStatusAnalyzed {
-                            external_id: session.external_id.clone(),
-                            project_path: path.clone(),
-                            status: session_status,
-                        };
-                        if poll_tx.send(event).await.is_err() {
-                            tracing::warn!("Claude poll receiver dropped");
-                            return;
-                        }
+                        claude_poller.poll(&workspaces)
                     }
-                }
-
-                // Remove sessions that are no longer active (immediate removal)
-                for external_id in prev_active_sessions.difference(&current_active_sessions) {
-                    tracing::debug!("Claude session removed: {}", external_id);
-                    let event = AppEvent::SessionUnregister {
-                        external_id: external_id.clone(),
-                    };
+                };
+                poll_clock.mark();
+                for event in events {
                     if poll_tx.send(event).await.is_err() {
                         tracing::warn!("Claude poll receiver dropped");
                         return;
                     }
                 }
-
-                prev_active_sessions = current_active_sessions;
             }
         }))
     } else {
@@ -525,81 +1107,175 @@ async fn run_logwatch(
     };
 
     // Kiro CLI: SQLite polling task
-    let kiro_polling_handle = if config.kiro_polling_enabled {
+    let kiro_polling_handle = if kiro_polling {
         let kiro_config = KiroSqliteConfig {
             db_path: config.kiro_db_path.clone(),
             timeout_secs: 5,
+            read_only: config.kiro_db_read_only,
+            max_sessions_per_workspace: config.max_kiro_sessions_per_workspace,
+            show_idle_sessions: config.show_idle_kiro_sessions,
         };
-        let kiro_fetcher = KiroSqliteFetcher::with_config(kiro_config);
+        let kiro_fetcher = KiroSqliteFetcher::with_config(kiro_config).with_process_provider(processes.clone());
+        let mut kiro_poller = KiroPoller::new(kiro_fetcher);
         let poll_interval = Duration::from_secs(config.kiro_polling_interval_secs);
         let poll_tx = tx.clone();
         let mut poll_workspace_rx = workspace_rx.clone();
+        let poll_clock = last_poll.clone();
+        let mut refresh_rx = refresh_tx.subscribe();
 
         Some(tokio::spawn(async move {
-            if !kiro_fetcher.is_available() {
-                tracing::info!("Kiro database not found at {:?}, polling disabled", kiro_fetcher.db_path());
+            if !kiro_poller.fetcher().is_available() {
+                tracing::info!("Kiro database not found at {:?}, polling disabled", kiro_poller.fetcher().db_path());
                 return;
             }
 
             tracing::info!(
                 "Kiro SQLite polling started (interval: {}s, db: {:?})",
                 poll_interval.as_secs(),
-                kiro_fetcher.db_path()
+                kiro_poller.fetcher().db_path()
             );
 
-            // Track active sessions to detect disconnections
-            let mut prev_active_sessions: std::collections::HashSet<String> = std::collections::HashSet::new();
+            // Consecutive DB read failures (reported once per failure streak)
+            let mut db_failures = 0u32;
 
             loop {
-                tokio::time::sleep(poll_interval).await;
-
-                // Get current workspace list
-                let workspaces = poll_workspace_rx.borrow_and_update().clone();
+                // Wait for the next tick, or an on-demand refresh of one workspace
+                let refresh_path = tokio::select! {
+                    _ = tokio::time::sleep(poll_interval) => None,
+                    Ok(path) = refresh_rx.recv() => Some(path),
+                };
 
-                if workspaces.is_empty() {
-                    continue;
-                }
+                let result = match refresh_path {
+                    Some(path) => kiro_poller.poll_workspace(&path),
+                    None => {
+                        // Get current workspace list
+                        let workspaces = poll_workspace_rx.borrow_and_update().clone();
 
-                // Fetch sessions from Kiro SQLite (already limited to process_count per workspace)
-                let mut current_active_sessions: std::collections::HashSet<String> = std::collections::HashSet::new();
+                        if workspaces.is_empty() {
+                            continue;
+                        }
 
-                for (path, status) in kiro_fetcher.get_statuses(&workspaces) {
-                    let external_id = status.external_id(&path);
-                    current_active_sessions.insert(external_id.clone());
+                        kiro_poller.poll(&workspaces)
+                    }
+                };
 
-                    let session_status = status.to_session_status(&path);
-                    let event = AppEvent::SessionStatusAnalyzed {
-                        external_id,
-                        project_path: path,
-                        status: session_status,
-                    };
+                let events = match result {
+                    Ok(events) => {
+                        db_failures = 0;
+                        events
+                    }
+                    Err(e) => {
+                        db_failures += 1;
+                        tracing::warn!("Failed to read Kiro database ({} in a row): {:#}", db_failures, e);
+                        if db_failures == KIRO_DB_FAILURE_THRESHOLD {
+                            let event = AppEvent::KiroDbError { message: format!("{:#}", e) };
+                            if poll_tx.send(event).await.is_err() {
+                                tracing::warn!("Kiro poll receiver dropped");
+                                return;
+                            }
+                        }
+                        continue;
+                    }
+                };
+                poll_clock.mark();
+                for event in events {
                     if poll_tx.send(event).await.is_err() {
                         tracing::warn!("Kiro poll receiver dropped");
                         return;
                     }
                 }
+            }
+        }))
+    } else {
+        None
+    };
 
-                // Remove sessions that are no longer active (Kiro: immediate removal)
-                for external_id in prev_active_sessions.difference(&current_active_sessions) {
-                    let event = AppEvent::SessionUnregister {
-                        external_id: external_id.clone(),
-                    };
+    // OpenCode: session store polling task
+    let opencode_polling_handle = opencode_polling.then(|| {
+        let fetcher = OpenCodeSessionsFetcher::with_config(OpenCodeSessionsConfig {
+            storage_dir: expand_tilde(&config.opencode_storage_dir),
+            ..Default::default()
+        })
+        .with_process_provider(processes.clone());
+        spawn_session_poller(
+            fetcher,
+            Duration::from_secs(config.opencode_polling_interval_secs.max(1)),
+            tx.clone(),
+            workspace_rx.clone(),
+            last_poll.clone(),
+            refresh_tx.subscribe(),
+        )
+    });
+
+    // Codex: rollout file polling task
+    let codex_polling_handle = codex_polling.then(|| {
+        let fetcher = CodexSessionsFetcher::with_config(CodexSessionsConfig {
+            codex_home: expand_tilde(&config.codex_home),
+            ..Default::default()
+        })
+        .with_process_provider(processes.clone());
+        spawn_session_poller(
+            fetcher,
+            Duration::from_secs(config.codex_polling_interval_secs.max(1)),
+            tx.clone(),
+            workspace_rx.clone(),
+            last_poll.clone(),
+            refresh_tx.subscribe(),
+        )
+    });
+
+    // tmux: capture windows opened by this tool
+    let tmux_capture_handle = if config.tmux_capture_enabled {
+        let mut tmux_poller = TmuxCapturePoller::new();
+        let poll_interval = Duration::from_secs(config.tmux_capture_interval_secs.max(1));
+        let poll_tx = tx.clone();
+        let mut poll_workspace_rx = workspace_rx.clone();
+        let poll_clock = last_poll.clone();
+
+        Some(tokio::spawn(async move {
+            tracing::info!("tmux capture polling started (interval: {}s)", poll_interval.as_secs());
+
+            loop {
+                tokio::time::sleep(poll_interval).await;
+                let workspaces = poll_workspace_rx.borrow_and_update().clone();
+                if workspaces.is_empty() {
+                    continue;
+                }
+
+                let events = match tmux_poller.poll(&workspaces) {
+                    Ok(events) => events,
+                    Err(e) => {
+                        tracing::warn!("tmux capture failed, polling disabled: {:#}", e);
+                        return;
+                    }
+                };
+                poll_clock.mark();
+                for event in events {
                     if poll_tx.send(event).await.is_err() {
-                        tracing::warn!("Kiro poll receiver dropped");
+                        tracing::warn!("tmux capture receiver dropped");
                         return;
                     }
                 }
-
-                prev_active_sessions = current_active_sessions;
             }
         }))
     } else {
         None
     };
 
-    // Wait for shutdown signal (trigger_rx closing)
-    while trigger_rx.recv().await.is_some() {
-        // Ignore triggers - we use polling now
+    // Forward manual refresh requests until shutdown (trigger_rx closing)
+    loop {
+        tokio::select! {
+            trigger = trigger_rx.recv() => {
+                // Ignore triggers - we use polling now
+                if trigger.is_none() {
+                    break;
+                }
+            }
+            Some(path) = refresh_rx.recv() => {
+                // No receivers when all polling tasks are disabled
+                let _ = refresh_tx.send(path);
+            }
+        }
     }
 
     // Cleanup
@@ -609,26 +1285,35 @@ async fn run_logwatch(
     if let Some(handle) = kiro_polling_handle {
         handle.abort();
     }
+    if let Some(handle) = opencode_polling_handle {
+        handle.abort();
+    }
+    if let Some(handle) = codex_polling_handle {
+        handle.abort();
+    }
+    if let Some(handle) = tmux_capture_handle {
+        handle.abort();
+    }
     tracing::info!("Log watch service stopped");
 }
 
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     state: &mut AppState,
-    mux: &mut dyn Multiplexer,
+    mux: &mut Box<dyn Multiplexer>,
     config: &mut Config,
-    worktree_manager: &WorktreeManager,
-    mut notify_rx: tokio::sync::mpsc::Receiver<AppEvent>,
-    logwatch_trigger: Option<LogWatchTrigger>,
-    workspace_watch_tx: Option<tokio::sync::watch::Sender<Vec<String>>>,
+    worktree_manager: &mut WorktreeManager,
+    channels: AppChannels,
     runtime: &tokio::runtime::Runtime,
-    yazi_config: &workspace_manager::app::config::YaziConfig,
 ) -> Result<()> {
-    // 起動直後に即座にポーリングするため10から開始
-    let mut tick_count = 10u8;
+    let AppChannels { event_tx, mut notify_rx, logwatch_trigger, logwatch_refresh, workspace_watch_tx } = channels;
+    // タブ状態・ペイン・ワークスペースの定期更新（None なので起動直後に即座に実行）
+    let mut last_refresh: Option<Instant> = None;
+    // 描画・イベントポーリングの間隔
+    let poll_interval = Duration::from_millis(config.event_poll_ms.clamp(MIN_EVENT_POLL_MS, MAX_EVENT_POLL_MS));
     // ダブルクリック検出用の前回クリック情報
     let mut last_click: Option<(Instant, u16)> = None;
-    const DOUBLE_CLICK_THRESHOLD: Duration = Duration::from_millis(300);
+    let double_click_threshold = Duration::from_millis(config.double_click_threshold_ms);
 
     // Send initial workspace list to logwatch service
     if let Some(ref tx) = workspace_watch_tx {
@@ -637,8 +1322,16 @@ fn run_app(
     }
 
     loop {
+        // 設定の再読み込み（マルチプレクサと worktree 管理を差し替えるのでここで行う）
+        if state.reload_config_requested {
+            state.reload_config_requested = false;
+            reload_config(state, mux, config, worktree_manager);
+            state.needs_redraw = true;
+        }
+
         // Check for notify events (non-blocking)
         while let Ok(event) = notify_rx.try_recv() {
+            state.needs_redraw = true;
             // Trigger log analysis for relevant events
             if let Some(ref trigger) = logwatch_trigger {
                 let path_to_analyze: Option<String> = match &event {
@@ -663,12 +1356,45 @@ fn run_app(
                     });
                 }
             }
+            let attention_before = state.auto_expand_on_activity.then(|| state.attention_workspaces());
+            let watch_transitions =
+                config.notify_on_complete || config.bell_on_transition || config.flash_on_transition;
+            let statuses_before = watch_transitions.then(|| state.session_statuses());
             handle_notify_event(state, event, worktree_manager);
+            if let Some(before) = attention_before {
+                if state.expand_on_activity(&before) {
+                    state.rebuild_tree_with_manager(Some(worktree_manager));
+                }
+            }
+            if let Some(before) = statuses_before {
+                let mut ring = false;
+                if config.notify_on_complete {
+                    let completions = state.take_completions(&before);
+                    for message in &completions {
+                        if let Err(e) = terminal::send_desktop_notification("Session completed", message) {
+                            tracing::warn!("Failed to notify completion: {}", e);
+                        }
+                    }
+                    ring = !completions.is_empty();
+                }
+                if let Some(status) = state.notable_transition(&before) {
+                    ring |= config.bell_on_transition;
+                    if config.flash_on_transition {
+                        state.flash_status(status);
+                    }
+                }
+                // 複数の設定が同時に反応してもベルは一度だけ
+                if ring {
+                    terminal::ring_bell();
+                }
+            }
         }
 
-        // 1秒ごとにZellijタブ状態とワークスペースリストを更新（100ms × 10回 = 1秒）
-        if tick_count >= 10 {
-            tick_count = 0;
+        // 1秒ごとにZellijタブ状態とワークスペースリストを更新（ポーリング間隔に依存しない）
+        if last_refresh.is_none_or(|at| at.elapsed() >= REFRESH_INTERVAL) {
+            last_refresh = Some(Instant::now());
+            // ステータスバーの時計もここで更新される
+            state.needs_redraw = true;
             if let Some(session) = mux.session_name() {
                 match mux.query_window_names(session) {
                     Ok(tabs) => {
@@ -697,7 +1423,7 @@ fn run_app(
                     let active_pane_changed = new_active_cwd != prev_active_cwd;
 
                     // yaziに通知
-                    if yazi_config.enabled && active_pane_changed {
+                    if config.yazi.enabled && active_pane_changed {
                         if let Some(ref cwd) = new_active_cwd {
                             if state.last_yazi_path.as_ref() != Some(cwd) {
                                 state.pending_yazi = Some((
@@ -721,52 +1447,135 @@ fn run_app(
                 }
             }
 
+            // 更新が途絶えたセッション（Unregister を送らずに落ちたツール）を切断扱いに
+            let expired = state.expire_stale_sessions();
+            if !expired.is_empty() {
+                tracing::info!("Sessions timed out without heartbeat: {:?}", expired);
+                state.rebuild_tree_with_manager(Some(worktree_manager));
+            }
+
+            // 古い切断済みセッションを破棄（長時間起動しっぱなしでも増え続けないように）
+            let removed = state.gc_sessions();
+            if removed > 0 {
+                tracing::debug!("Dropped {} disconnected sessions", removed);
+                state.rebuild_tree_with_manager(Some(worktree_manager));
+            }
+
+            // ログビューア表示中は末尾を追従
+            if state.view_mode == ViewMode::Logs {
+                state.reload_log_lines();
+            }
+
             // Update workspace list for Kiro SQLite polling
             if let Some(ref tx) = workspace_watch_tx {
                 let paths: Vec<String> = state.workspaces.iter().map(|w| w.project_path.clone()).collect();
                 let _ = tx.send(paths);
             }
         }
-        tick_count += 1;
 
-        // 描画前にdirtyなgit logをフェッチ（イベントバッチ後に1回だけ）
-        state.flush_git_log();
+        // 点滅中は毎回、終わった直後にもう一度描画して元の色に戻す
+        if state.status_flash.is_some() {
+            state.needs_redraw = true;
+            if state.active_status_flash().is_none() {
+                state.status_flash = None;
+            }
+        }
 
-        terminal.draw(|frame| {
-            ui::render(frame, state);
-        })?;
+        // 状態が変わったときだけ描画する
+        if state.needs_redraw {
+            state.needs_redraw = false;
+
+            // 描画前にdirtyなgit logをフェッチ（イベントバッチ後に1回だけ）
+            state.flush_git_log();
+
+            terminal.draw(|frame| {
+                ui::render(frame, state);
+            })?;
+        }
 
         // イベントをバッチ処理：溜まったイベントをすべて消化してから次の描画へ
         // 最初のpollだけタイムアウト付き（描画更新間隔）、以降は即座にチェック
-        let poll_timeout = if yazi_config.enabled {
+        let mut poll_timeout = if config.yazi.enabled {
             state.yazi_timeout()
-                .map(|d| d.min(Duration::from_millis(100)))
-                .unwrap_or(Duration::from_millis(100))
+                .map(|d| d.min(poll_interval))
+                .unwrap_or(poll_interval)
         } else {
-            Duration::from_millis(100)
+            poll_interval
         };
+        if let Some(d) = state.select_hook_timeout() {
+            poll_timeout = poll_timeout.min(d);
+        }
         let mut has_event = poll_event(poll_timeout)?;
-        if yazi_config.enabled {
-            state.fire_yazi_if_ready(yazi_config.client_id);
+        if config.yazi.enabled {
+            state.fire_yazi_if_ready(config.yazi.client_id);
+        }
+        if let Some((path, branch)) = state.take_ready_select_hook() {
+            run_workspace_select_command(config, &path, &branch);
+        }
+        if let Some((paths, days)) = state.take_recent_commits_request() {
+            let tx = event_tx.clone();
+            std::thread::spawn(move || {
+                let _ = tx.blocking_send(AppEvent::RecentCommitsCounted(recent_commit_counts(&paths, days)));
+            });
         }
         while let Some(event) = has_event {
+            // キー・マウス・リサイズのいずれも表示が変わりうる
+            state.needs_redraw = true;
             match state.view_mode {
+                // エラートーストは確認されるまで他の入力を受け付けない（Ctrl+C の終了だけは通す）
+                _ if state.error_toast.is_some() => {
+                    if let AppEvent::Key(key) = event {
+                        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                            state.dismiss_error_toast();
+                            handle_action(state, mux.as_mut(), config, worktree_manager, &event_tx, Action::Quit)?;
+                        } else if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char(' ') | KeyCode::Char('q')) {
+                            state.dismiss_error_toast();
+                        }
+                    }
+                }
                 ViewMode::Input => {
                     if let AppEvent::Key(key) = event {
-                        handle_input_event(state, key, worktree_manager)?;
+                        handle_input_event(state, key, mux.as_mut(), config, worktree_manager, &event_tx)?;
                     }
                 }
                 ViewMode::Selection => {
                     if let AppEvent::Key(key) = event {
-                        handle_selection_event(state, key, mux, config)?;
+                        handle_selection_event(state, key, mux.as_mut(), config, worktree_manager, &event_tx)?;
+                    }
+                }
+                ViewMode::Logs => {
+                    if let AppEvent::Key(key) = event {
+                        handle_log_view_event(state, key);
+                    }
+                }
+                ViewMode::Messages => {
+                    if let AppEvent::Key(key) = event {
+                        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('M')) {
+                            state.toggle_message_history();
+                        }
                     }
                 }
+                ViewMode::Detail => match event {
+                    AppEvent::Key(key) => handle_detail_view_event(state, key),
+                    AppEvent::Mouse(mouse) => match mouse.kind {
+                        crossterm::event::MouseEventKind::ScrollUp => state.detail_scroll_up(3),
+                        crossterm::event::MouseEventKind::ScrollDown => state.detail_scroll_down(3),
+                        _ => {}
+                    },
+                    _ => {}
+                },
                 _ => match event {
                     AppEvent::Key(key) => {
-                        let action = Action::from(key);
-                        handle_action(state, mux, config, worktree_manager, action)?;
-                        if yazi_config.enabled {
-                            state.schedule_yazi(yazi_config.debounce_ms);
+                        let action = match state.pending_key.take() {
+                            Some(prefix) => chord_action(prefix, key),
+                            None => Action::from(key),
+                        };
+                        handle_action(state, mux.as_mut(), config, worktree_manager, &event_tx, action)?;
+                        if config.yazi.enabled {
+                            state.schedule_yazi(config.yazi.debounce_ms);
+                        }
+                        if config.on_workspace_select_command.is_some() {
+                            state.schedule_select_hook(config.on_workspace_select_debounce_ms);
                         }
                     }
                     AppEvent::Mouse(mouse) => {
@@ -779,7 +1588,7 @@ fn run_app(
                             match mouse.kind {
                                 crossterm::event::MouseEventKind::Drag(crossterm::event::MouseButton::Left)
                                 | crossterm::event::MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
-                                    let total_h = terminal.size()?.height as f32;
+                                    let total_h = terminal.size()?.height.saturating_sub(1) as f32; // ステータスバー分を除く
                                     if total_h > 0.0 {
                                         let ratio = mouse.row as f32 / total_h;
                                         state.git_log_split_ratio = ratio.clamp(0.2, 0.9);
@@ -795,6 +1604,11 @@ fn run_app(
                             state.git_log_dragging = true;
                         } else {
 
+                        // 詳細サイドパネル内のマウスイベントは無視（リスト行の選択にしない）
+                        let in_detail_pane = state.detail_pane_area
+                            .map(|area| mouse.column >= area.x && mouse.column < area.x + area.width)
+                            .unwrap_or(false);
+
                         // Git logペイン内のマウスイベントかどうか判定
                         let in_git_log = state.git_log_area
                             .map(|area| {
@@ -805,7 +1619,13 @@ fn run_app(
                             })
                             .unwrap_or(false);
 
-                        if in_git_log {
+                        if in_detail_pane || in_git_log {
+                            state.hover_index = None;
+                        }
+
+                        if in_detail_pane {
+                            // 何もしない
+                        } else if in_git_log {
                             state.focused_pane = FocusedPane::GitLog;
                             match mouse.kind {
                                 crossterm::event::MouseEventKind::ScrollUp => {
@@ -844,7 +1664,7 @@ fn run_app(
                                     let now = Instant::now();
                                     let is_double_click = last_click
                                         .map(|(time, prev_row)| {
-                                            now.duration_since(time) < DOUBLE_CLICK_THRESHOLD
+                                            now.duration_since(time) < double_click_threshold
                                                 && prev_row == row
                                         })
                                         .unwrap_or(false);
@@ -857,9 +1677,24 @@ fn run_app(
                                 }
                                 other => other,
                             };
-                            handle_action(state, mux, config, worktree_manager, action)?;
-                            if yazi_config.enabled {
-                                state.schedule_yazi(yazi_config.debounce_ms);
+                            // セッション行のステータスアイコンをクリック → そのセッションの詳細を開く
+                            let open_session_detail = match action {
+                                Action::MouseSelect(row) => ui::workspace_list::is_session_status_icon(
+                                    state,
+                                    row as usize + state.table_state.offset(),
+                                    mouse.column,
+                                ),
+                                _ => false,
+                            };
+                            handle_action(state, mux.as_mut(), config, worktree_manager, &event_tx, action)?;
+                            if open_session_detail {
+                                state.open_detail_view();
+                            }
+                            if config.yazi.enabled {
+                                state.schedule_yazi(config.yazi.debounce_ms);
+                            }
+                            if config.on_workspace_select_command.is_some() {
+                                state.schedule_select_hook(config.on_workspace_select_debounce_ms);
                             }
                         }
 
@@ -876,6 +1711,18 @@ fn run_app(
             has_event = poll_event(Duration::from_millis(0))?;
         }
 
+        // 選択中ワークスペースのステータス即時再取得をlogwatchへ依頼
+        if let Some(path) = state.refresh_request.take() {
+            match logwatch_refresh {
+                Some(ref refresh) => {
+                    let _ = refresh.try_send(path);
+                }
+                None => {
+                    state.set_warning("Status polling is disabled".to_string());
+                }
+            }
+        }
+
         if state.should_quit {
             break;
         }
@@ -884,6 +1731,52 @@ fn run_app(
     Ok(())
 }
 
+/// post_create_command をバックグラウンドで実行（完了は PostCreateHookFinished で通知）
+fn spawn_post_create_hook(
+    state: &mut AppState,
+    worktree_manager: &WorktreeManager,
+    event_tx: &EventSender,
+    repo_path: &Path,
+    worktree_path: &Path,
+    branch_name: &str,
+) {
+    let Some(command) = worktree_manager.post_create_command(repo_path, worktree_path, branch_name) else {
+        return;
+    };
+
+    state.set_status(format!(
+        "Created worktree: {} (running post_create_command...)",
+        state.display_path(&worktree_path.to_string_lossy())
+    ));
+
+    let tx = event_tx.clone();
+    let worktree_path = worktree_path.to_path_buf();
+    std::thread::spawn(move || {
+        let result = WorktreeManager::run_post_create_command(&command, &worktree_path)
+            .map_err(|e| e.to_string());
+        let _ = tx.blocking_send(AppEvent::PostCreateHookFinished {
+            path: worktree_path.to_string_lossy().to_string(),
+            result,
+        });
+    });
+}
+
+/// レイアウトディレクトリ内の `*.kdl` を名前順に列挙（拡張子なし）
+fn list_layouts(dir: &Path) -> Vec<String> {
+    let mut layouts: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "kdl"))
+                .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    layouts.sort();
+    layouts
+}
+
 /// タブ切り替え/作成後にpost_select_commandを実行
 fn run_post_select_command(config: &Config) {
     if let Some(cmd) = config.effective_post_select_command() {
@@ -893,11 +1786,124 @@ fn run_post_select_command(config: &Config) {
     }
 }
 
+/// リストでワークスペースが選択されたら on_workspace_select_command を実行（完了は待たない）
+fn run_workspace_select_command(config: &Config, path: &str, branch: &str) {
+    let Some(template) = config.on_workspace_select_command.as_deref() else {
+        return;
+    };
+    let command = terminal::expand_quoted(template, &[("{path}", path), ("{branch}", branch)]);
+    let result = std::process::Command::new("sh")
+        .args(["-c", &command])
+        .current_dir(path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+    match result {
+        // 終了したプロセスがゾンビとして残らないよう回収する
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+        }
+        Err(e) => tracing::warn!("on_workspace_select_command failed: {}", e),
+    }
+}
+
+/// `path` をエディタで開き、使ったエディタコマンドを返す
+///
+/// 端末エディタ（`terminal_editors`）はマルチプレクサの新しいペインで、それ以外は別プロセスで起動する。
+fn open_path_in_editor(mux: &dyn Multiplexer, config: &Config, path: &Path) -> Result<String> {
+    let editor = terminal::resolve_editor(&config.editor)?;
+    if !terminal::is_terminal_editor(&editor, &config.terminal_editors) {
+        terminal::open_in_editor(&editor, path)?;
+        return Ok(editor);
+    }
+    if !mux.is_available() {
+        anyhow::bail!("{} needs a terminal; run inside zellij or tmux to open it in a pane", editor);
+    }
+    let target = path.to_string_lossy();
+    let mut command: Vec<&str> = editor.split_whitespace().collect();
+    command.push(&target);
+    // ファイルならその親ディレクトリをペインの作業ディレクトリにする
+    let cwd = if path.is_dir() { path } else { path.parent().unwrap_or(path) };
+    mux.launch_command(cwd, &command)?;
+    Ok(editor)
+}
+
+/// ログビューアでのキーイベント処理
+fn handle_log_view_event(state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('o') => state.toggle_log_view(),
+        KeyCode::Up | KeyCode::Char('k') => state.log_scroll_up(1),
+        KeyCode::Down | KeyCode::Char('j') => state.log_scroll_down(1),
+        KeyCode::PageUp => state.log_scroll_up(20),
+        KeyCode::PageDown => state.log_scroll_down(20),
+        KeyCode::Char('g') | KeyCode::Home => state.log_scroll_up(usize::MAX / 2),
+        KeyCode::Char('G') | KeyCode::End => state.log_scroll = 0,
+        KeyCode::Char('f') => {
+            state.log_level_filter = state.log_level_filter.next();
+            state.log_scroll = 0;
+        }
+        _ => {}
+    }
+}
+
+/// 詳細ビューでのキーイベント処理
+fn handle_detail_view_event(state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => state.view_mode = ViewMode::List,
+        KeyCode::Up | KeyCode::Char('k') => state.detail_scroll_up(1),
+        KeyCode::Down | KeyCode::Char('j') => state.detail_scroll_down(1),
+        KeyCode::PageUp => state.detail_scroll_up(10),
+        KeyCode::PageDown | KeyCode::Char(' ') => state.detail_scroll_down(10),
+        KeyCode::Char('g') | KeyCode::Home => state.detail_scroll = 0,
+        KeyCode::Char('G') | KeyCode::End => state.detail_scroll = u16::MAX,
+        _ => {}
+    }
+}
+
+/// 作ろうとしたブランチが既に別の worktree にある場合、設定に応じてそれを選択/オープンする
+fn handle_existing_worktree(
+    state: &mut AppState,
+    mux: &mut dyn Multiplexer,
+    config: &mut Config,
+    worktree_manager: &WorktreeManager,
+    event_tx: &EventSender,
+    existing: &BranchCheckedOut,
+) -> Result<()> {
+    let behavior = worktree_manager.config().on_existing_worktree;
+    if behavior == ExistingWorktreeBehavior::Error {
+        state.set_error(format!("Failed: {}", existing));
+        return Ok(());
+    }
+
+    let shown = state.display_path(&existing.path.to_string_lossy());
+    if !state.reveal_workspace(&existing.path, Some(worktree_manager)) {
+        // search_paths の外やフィルターで隠れている場合は場所だけ伝える
+        state.set_warning(format!("Branch '{}' is already checked out at {}", existing.branch, shown));
+        return Ok(());
+    }
+    match behavior {
+        ExistingWorktreeBehavior::Open => {
+            handle_action(state, mux, config, worktree_manager, event_tx, Action::Select)?;
+        }
+        _ => {
+            state.set_status(format!(
+                "Branch '{}' already has a worktree: {} (Enter to open)",
+                existing.branch, shown
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// 入力モードでのキーイベント処理
 fn handle_input_event(
     state: &mut AppState,
     key: KeyEvent,
+    mux: &mut dyn Multiplexer,
+    config: &mut Config,
     worktree_manager: &WorktreeManager,
+    event_tx: &EventSender,
 ) -> Result<()> {
     // 先に必要な情報を取得
     let repo_path = state.selected_repo_path();
@@ -911,7 +1917,12 @@ fn handle_input_event(
                 state.branch_filter = None;
                 state.close_input_dialog();
                 state.rebuild_tree_with_manager(Some(worktree_manager));
-                state.status_message = Some("Filter cleared".to_string());
+                state.set_status("Filter cleared".to_string());
+            } else if matches!(dialog_kind, Some(InputDialogKind::FilterTree)) {
+                state.tree_filter = None;
+                state.close_input_dialog();
+                state.rebuild_tree_with_manager(Some(worktree_manager));
+                state.set_status("Tree filter cleared".to_string());
             } else {
                 state.close_input_dialog();
             }
@@ -933,24 +1944,35 @@ fn handle_input_event(
                             start_point.as_deref(),
                         ) {
                             Ok(path) => {
-                                state.status_message = Some(format!(
+                                state.set_status(format!(
                                     "Created worktree: {}",
-                                    path.display()
+                                    state.display_path(&path.to_string_lossy())
                                 ));
+                                spawn_post_create_hook(state, worktree_manager, event_tx, Path::new(rp), &path, &branch_name);
                                 state.close_input_dialog();
                                 state.scan_workspaces();
                             }
-                            Err(e) => {
-                                if let Some(ref mut dialog) = state.input_dialog {
-                                    dialog.set_error(format!("Failed: {}", e));
+                            Err(e) => match e.downcast_ref::<BranchCheckedOut>() {
+                                Some(existing)
+                                    if worktree_manager.config().on_existing_worktree
+                                        != ExistingWorktreeBehavior::Error =>
+                                {
+                                    let existing = existing.clone();
+                                    state.close_input_dialog();
+                                    handle_existing_worktree(state, mux, config, worktree_manager, event_tx, &existing)?;
                                 }
-                            }
+                                _ => {
+                                    if let Some(ref mut dialog) = state.input_dialog {
+                                        dialog.set_error(format!("Failed: {}", e));
+                                    }
+                                }
+                            },
                         }
                     } else if let Some(ref mut dialog) = state.input_dialog {
                         dialog.set_error("No repository selected".to_string());
                     }
                 }
-                Some(InputDialogKind::DeleteWorktree { .. }) => {
+                Some(InputDialogKind::DeleteWorktree { .. } | InputDialogKind::ConfirmQuit { .. }) => {
                     // 'y'で確認する
                 }
                 Some(InputDialogKind::FilterBranches) => {
@@ -959,16 +1981,36 @@ fn handle_input_event(
                     state.close_input_dialog();
                     state.rebuild_tree_with_manager(Some(worktree_manager));
                     if filter.is_empty() {
-                        state.status_message = Some("Filter cleared".to_string());
+                        state.set_status("Filter cleared".to_string());
+                    } else {
+                        state.set_status(format!("Filter: {}", filter));
+                    }
+                }
+                Some(InputDialogKind::FilterTree) => {
+                    let filter = dialog_input.unwrap_or_default().trim().to_string();
+                    state.tree_filter = if filter.is_empty() { None } else { Some(filter.clone()) };
+                    state.close_input_dialog();
+                    state.rebuild_tree_with_manager(Some(worktree_manager));
+                    if filter.is_empty() {
+                        state.set_status("Tree filter cleared".to_string());
                     } else {
-                        state.status_message = Some(format!("Filter: {}", filter));
+                        state.set_status(format!("Tree filter: {}", filter));
                     }
                 }
                 None => {}
             }
         }
-        KeyCode::Char('y') => {
-            if let Some(InputDialogKind::DeleteWorktree { path, force }) = dialog_kind {
+        KeyCode::Char(c @ ('y' | 'b'))
+            if matches!(dialog_kind, Some(InputDialogKind::DeleteWorktree { .. })) =>
+        {
+            if let Some(InputDialogKind::DeleteWorktree { path, force, branch }) = dialog_kind {
+                // b はブランチも削除（ブランチが分からないときは何もしない）
+                // ダイアログを開く d の連打で消えないよう、別のキーにしている
+                let delete_branch = match (c, branch) {
+                    ('b', Some(branch)) => Some(branch),
+                    ('b', None) => return Ok(()),
+                    _ => None,
+                };
                 if let Some(ref rp) = repo_path {
                     // チルダを展開
                     let expanded_path = if path.starts_with("~/") {
@@ -981,13 +2023,25 @@ fn handle_input_event(
                         std::path::PathBuf::from(&path)
                     };
 
-                    match worktree_manager.remove_worktree(
-                        Path::new(rp),
-                        &expanded_path,
-                        force,
-                    ) {
+                    let result = match delete_branch {
+                        Some(ref branch) => worktree_manager.remove_worktree_and_branch(
+                            Path::new(rp),
+                            &expanded_path,
+                            branch,
+                            force,
+                        ),
+                        None => worktree_manager.remove_worktree(
+                            Path::new(rp),
+                            &expanded_path,
+                            force,
+                        ),
+                    };
+                    match result {
                         Ok(()) => {
-                            state.status_message = Some(format!("Deleted worktree: {}", path));
+                            match delete_branch {
+                                Some(branch) => state.set_status(format!("Deleted worktree: {} and branch: {}", path, branch)),
+                                None => state.set_status(format!("Deleted worktree: {}", path)),
+                            }
                             state.close_input_dialog();
                             state.scan_workspaces();
                         }
@@ -998,19 +2052,25 @@ fn handle_input_event(
                         }
                     }
                 }
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(InputDialogKind::ConfirmQuit { .. }) = dialog_kind {
+                state.close_input_dialog();
+                state.should_quit = true;
             } else if let Some(ref mut dialog) = state.input_dialog {
                 dialog.insert_char('y');
             }
         }
         KeyCode::Char('n') => {
-            if matches!(dialog_kind, Some(InputDialogKind::DeleteWorktree { .. })) {
+            if dialog_kind.as_ref().is_some_and(|k| k.is_confirmation()) {
                 state.close_input_dialog();
             } else if let Some(ref mut dialog) = state.input_dialog {
                 dialog.insert_char('n');
             }
         }
         KeyCode::Char(c) => {
-            if !matches!(dialog_kind, Some(InputDialogKind::DeleteWorktree { .. })) {
+            if !dialog_kind.as_ref().is_some_and(|k| k.is_confirmation()) {
                 if let Some(ref mut dialog) = state.input_dialog {
                     dialog.insert_char(c);
                 }
@@ -1047,11 +2107,21 @@ fn handle_selection_event(
     key: KeyEvent,
     mux: &mut dyn Multiplexer,
     config: &mut Config,
+    worktree_manager: &WorktreeManager,
+    event_tx: &EventSender,
 ) -> Result<()> {
     match key.code {
         KeyCode::Esc => {
             state.close_selection_dialog();
         }
+        KeyCode::Enter if matches!(state.selection_dialog_kind(), Some(SelectionDialogKind::ContextMenu(_))) => {
+            // コンテキストメニュー: 閉じてから選ばれたアクションを実行
+            let action = state.selection_dialog.as_ref().and_then(|d| d.selected_action());
+            state.close_selection_dialog();
+            if let Some(action) = action {
+                handle_action(state, mux, config, worktree_manager, event_tx, action)?;
+            }
+        }
         KeyCode::Up | KeyCode::Char('k') => {
             state.selection_move_up();
         }
@@ -1070,7 +2140,7 @@ fn handle_selection_event(
                         mux.set_session_name(selected_item.clone());
                         // 設定ファイルに保存
                         if let Err(e) = config.save_session(selected_item.clone()) {
-                            state.status_message = Some(format!("Warning: Failed to save config: {}", e));
+                            state.set_warning(format!("Warning: Failed to save config: {}", e));
                         }
                         state.close_selection_dialog();
 
@@ -1081,18 +2151,18 @@ fn handle_selection_event(
 
                         match mux.open_workspace_window(&tab_name, cwd, layout.as_deref()) {
                             Ok(WindowActionResult::SwitchedToExisting(name)) => {
-                                state.status_message = Some(format!("Switched to tab: {}", name));
+                                state.set_status(format!("Switched to tab: {}", name));
                                 run_post_select_command(config);
                             }
                             Ok(WindowActionResult::CreatedNew(name)) => {
-                                state.status_message = Some(format!("Created tab: {}", name));
+                                state.set_status(format!("Created tab: {}", name));
                                 run_post_select_command(config);
                             }
                             Ok(WindowActionResult::SessionNotFound(session)) => {
-                                state.status_message = Some(format!("Session '{}' not found", session));
+                                state.set_error(format!("Session '{}' not found", session));
                             }
                             Err(e) => {
-                                state.status_message = Some(format!("Error: {}", e));
+                                state.set_error(format!("Error: {}", e));
                             }
                         }
                     }
@@ -1111,27 +2181,28 @@ fn handle_selection_event(
                         // デフォルトレイアウトとして保存
                         if let Some(ref path) = layout_path {
                             if let Err(e) = config.save_layout(path.clone()) {
-                                state.status_message = Some(format!("Warning: Failed to save config: {}", e));
+                                state.set_warning(format!("Warning: Failed to save config: {}", e));
                             }
                         }
 
                         match mux.open_workspace_window(&tab_name, cwd, layout) {
                             Ok(WindowActionResult::SwitchedToExisting(name)) => {
-                                state.status_message = Some(format!("Switched to tab: {}", name));
+                                state.set_status(format!("Switched to tab: {}", name));
                                 run_post_select_command(config);
                             }
                             Ok(WindowActionResult::CreatedNew(name)) => {
-                                state.status_message = Some(format!("Created tab: {} (layout: {})", name, selected_item));
+                                state.set_status(format!("Created tab: {} (layout: {})", name, selected_item));
                                 run_post_select_command(config);
                             }
                             Ok(WindowActionResult::SessionNotFound(session)) => {
-                                state.status_message = Some(format!("Session '{}' not found", session));
+                                state.set_error(format!("Session '{}' not found", session));
                             }
                             Err(e) => {
-                                state.status_message = Some(format!("Error: {}", e));
+                                state.set_error(format!("Error: {}", e));
                             }
                         }
                     }
+                    SelectionDialogKind::ContextMenu(_) => {}
                 }
             }
         }
@@ -1156,15 +2227,15 @@ fn handle_notify_event(state: &mut AppState, event: AppEvent, worktree_manager:
                 tool
             );
             // Register the session
-            if let Some(session_index) = state.register_session(
+            if let Some(session_id) = state.register_session(
                 external_id.clone(),
                 &project_path,
                 tool,
                 pane_id,
             ) {
                 tracing::info!(
-                    "Session registered at index {}: {}",
-                    session_index,
+                    "Session registered as {}: {}",
+                    session_id,
                     external_id
                 );
                 // Rebuild tree to show the new session
@@ -1176,6 +2247,7 @@ fn handle_notify_event(state: &mut AppState, event: AppEvent, worktree_manager:
                     AiTool::Kiro => "kiro",
                     AiTool::OpenCode => "opencode",
                     AiTool::Codex => "codex",
+                    AiTool::Other => "",
                 };
                 let ws_idx_for_pane = state.find_workspace_by_path(&project_path);
                 if let Some(ws_idx) = ws_idx_for_pane {
@@ -1208,13 +2280,23 @@ fn handle_notify_event(state: &mut AppState, event: AppEvent, worktree_manager:
                 external_id,
                 status
             );
+            let was_idle = state.is_session_idle(&external_id);
             state.update_session_status(&external_id, status, message);
+            // Idle 非表示中は Idle への出入りで行が増減する
+            if state.hide_idle_sessions && was_idle != state.is_session_idle(&external_id) {
+                state.rebuild_tree_with_manager(Some(worktree_manager));
+            }
         }
         AppEvent::SessionUnregister { external_id } => {
             tracing::info!("Session unregistered: external_id={}", external_id);
             state.remove_session(&external_id);
             state.rebuild_tree_with_manager(Some(worktree_manager));
         }
+        AppEvent::SessionHeartbeat { external_id } => {
+            if !state.heartbeat_session(&external_id) {
+                tracing::debug!("Heartbeat for unknown session: {}", external_id);
+            }
+        }
         AppEvent::TabFocusChanged { tab_name } => {
             tracing::info!("Tab focus changed: {}", tab_name);
         }
@@ -1234,11 +2316,7 @@ fn handle_notify_event(state: &mut AppState, event: AppEvent, worktree_manager:
             let is_new_session = state.get_session_by_external_id(&external_id).is_none();
             if is_new_session {
                 // Determine tool from external_id prefix
-                let tool = if external_id.starts_with("kiro:") {
-                    AiTool::Kiro
-                } else {
-                    AiTool::Claude
-                };
+                let (tool, _) = parse_external_id(&external_id);
 
                 if let Some(_) = state.register_session(
                     external_id.clone(),
@@ -1253,19 +2331,44 @@ fn handle_notify_event(state: &mut AppState, event: AppEvent, worktree_manager:
             }
 
             // Update session with AI analysis status
+            let was_idle = state.is_session_idle(&external_id);
+            let limits = state.text_limits;
+            let error_confirm_polls = state.error_confirm_polls;
+            let mut api_error = None;
             if let Some(session) = state.get_session_by_external_id_mut(&external_id) {
-                session.update_from_logwatch_status(&status);
+                let was_api_error = session.is_api_error();
+                // 一度きりの Error（レート制限など）は次のポーリングで続くまで表示しない
+                if !session.confirm_error(status.status == StatusState::Error, error_confirm_polls) {
+                    tracing::debug!("Error for {} not confirmed yet, keeping {}", external_id, session.status);
+                    session.heartbeat();
+                    return;
+                }
+                session.update_from_logwatch_status(&status, limits);
                 tracing::debug!(
                     "Updated session {} with AI status: {:?}",
                     external_id,
                     session.summary
                 );
+                // API エラーになった時だけステータスバーに出す（続報では繰り返さない）
+                if session.is_api_error() && !was_api_error {
+                    let detail = session.error.clone().or_else(|| session.summary.clone());
+                    api_error = Some(match detail {
+                        Some(detail) => format!("{} API error: {}", session.tool.name(), detail),
+                        None => format!("{} API error", session.tool.name()),
+                    });
+                }
+            }
+            if let Some(message) = api_error {
+                state.set_warning(message);
+            }
+            if state.hide_idle_sessions && was_idle != state.is_session_idle(&external_id) {
+                state.rebuild_tree_with_manager(Some(worktree_manager));
             }
 
             // Also update matching pane AI session
             state.update_pane_ai_session_by_external_id(&external_id, |ai| {
-                ai.summary = status.display_summary();
-                ai.current_task = status.current_task.clone();
+                ai.summary = status.display_summary(limits.summary);
+                ai.current_task = status.display_task(limits.task);
                 ai.state_detail = Some(status.state_detail.label().to_string());
                 ai.status = match status.status {
                     StatusState::Working => SessionStatus::Working,
@@ -1286,6 +2389,31 @@ fn handle_notify_event(state: &mut AppState, event: AppEvent, worktree_manager:
                 }
             });
         }
+        AppEvent::KiroDbError { message } => {
+            state.set_warning(format!("Kiro DB unreadable: {}", message));
+        }
+        AppEvent::PostCreateHookFinished { path, result } => {
+            match result {
+                Ok(()) => state.set_status(format!("post_create_command finished: {}", state.display_path(&path))),
+                Err(e) => {
+                    tracing::warn!("post_create_command failed for {}: {}", path, e);
+                    state.set_error(format!("post_create_command failed: {}", e));
+                }
+            }
+        }
+        AppEvent::RecentCommitsCounted(counts) => state.set_recent_commits(&counts),
+        AppEvent::FetchFinished { repo_path, result } => match result {
+            Ok(()) => {
+                state.set_status(format!("Fetched: {}", state.display_path(&repo_path)));
+                // 新しいリモートブランチと ahead/behind をツリーに反映
+                state.invalidate_branch_stats();
+                state.rebuild_tree_with_manager(Some(worktree_manager));
+            }
+            Err(e) => {
+                tracing::warn!("git fetch failed for {}: {}", repo_path, e);
+                state.set_error(format!("Fetch failed: {}", e));
+            }
+        },
         _ => {}
     }
 }
@@ -1294,12 +2422,18 @@ fn handle_action(
     state: &mut AppState,
     mux: &mut dyn Multiplexer,
     config: &mut Config,
-    _worktree_manager: &WorktreeManager,
+    worktree_manager: &WorktreeManager,
+    event_tx: &EventSender,
     action: Action,
 ) -> Result<()> {
     match action {
         Action::Quit => {
-            state.should_quit = true;
+            let working = state.working_count();
+            if config.confirm_quit_when_working && working > 0 {
+                state.open_confirm_quit_dialog(working);
+            } else {
+                state.should_quit = true;
+            }
         }
         Action::MoveUp => {
             if state.focused_pane == FocusedPane::GitLog && state.show_git_log {
@@ -1337,9 +2471,10 @@ fn handle_action(
             }
         }
         Action::Refresh => {
-            state.status_message = Some("Scanning workspaces...".to_string());
+            state.set_status("Scanning workspaces...".to_string());
+            state.invalidate_branch_stats();
             state.scan_workspaces();
-            state.rebuild_tree_with_manager(Some(_worktree_manager));
+            state.rebuild_tree_with_manager(Some(worktree_manager));
         }
         Action::Select => {
             // ペインが選択されている場合: タブ切替 + ペインフォーカス
@@ -1351,16 +2486,16 @@ fn handle_action(
 
                     // ウィンドウ切替
                     if let Err(e) = mux.go_to_window(&session, &window_index.to_string()) {
-                        state.status_message = Some(format!("Failed to switch window: {}", e));
+                        state.set_error(format!("Failed to switch window: {}", e));
                     } else {
                         // ペインフォーカス (pane_id is like "%12")
                         let pane_id_num: Option<u32> = pane_id.strip_prefix('%')
                             .and_then(|s| s.parse().ok());
                         if let Some(id) = pane_id_num {
                             if let Err(e) = mux.focus_pane(id) {
-                                state.status_message = Some(format!("Failed to focus pane: {}", e));
+                                state.set_error(format!("Failed to focus pane: {}", e));
                             } else {
-                                state.status_message = Some(format!("Focused pane {}", pane_id));
+                                state.set_status(format!("Focused pane {}", pane_id));
                                 run_post_select_command(config);
                             }
                         }
@@ -1373,16 +2508,16 @@ fn handle_action(
                     let pane_id = workspace_index.and_then(|idx| {
                         state.sessions_for_workspace(idx)
                             .first()
-                            .and_then(|&si| state.sessions.get(si))
+                            .and_then(|si| state.sessions.get(si))
                             .and_then(|s| s.pane_id)
                     });
 
                     if let Some(pane_id) = pane_id {
                         if let Err(e) = mux.focus_pane(pane_id) {
-                            state.status_message = Some(format!("Failed to focus pane: {}", e));
+                            state.set_error(format!("Failed to focus pane: {}", e));
                         }
                     } else {
-                        state.view_mode = ViewMode::Detail;
+                        state.open_detail_view();
                     }
                 } else if mux.is_available() {
                     // External mode: タブ/ウィンドウを開く
@@ -1398,10 +2533,10 @@ fn handle_action(
                                 state.open_session_select_dialog(sessions, context);
                             }
                             Ok(_) => {
-                                state.status_message = Some("No sessions found".to_string());
+                                state.set_warning("No sessions found".to_string());
                             }
                             Err(e) => {
-                                state.status_message = Some(format!("Failed to list sessions: {}", e));
+                                state.set_error(format!("Failed to list sessions: {}", e));
                             }
                         }
                     } else {
@@ -1411,23 +2546,23 @@ fn handle_action(
 
                         match mux.open_workspace_window(&tab_name, cwd, layout.as_deref()) {
                             Ok(WindowActionResult::SwitchedToExisting(name)) => {
-                                state.status_message = Some(format!("Switched to tab: {}", name));
+                                state.set_status(format!("Switched to tab: {}", name));
                                 run_post_select_command(config);
                             }
                             Ok(WindowActionResult::CreatedNew(name)) => {
-                                state.status_message = Some(format!("Created tab: {}", name));
+                                state.set_status(format!("Created tab: {}", name));
                                 run_post_select_command(config);
                             }
                             Ok(WindowActionResult::SessionNotFound(session)) => {
-                                state.status_message = Some(format!("Session '{}' not found", session));
+                                state.set_error(format!("Session '{}' not found", session));
                             }
                             Err(e) => {
-                                state.status_message = Some(format!("Error: {}", e));
+                                state.set_error(format!("Error: {}", e));
                             }
                         }
                     }
                 } else {
-                    state.view_mode = ViewMode::Detail;
+                    state.open_detail_view();
                 }
             }
         }
@@ -1450,29 +2585,34 @@ fn handle_action(
         }
         Action::ToggleExpand => {
             state.toggle_expand();
-            state.rebuild_tree_with_manager(Some(_worktree_manager));
+            state.rebuild_tree_with_manager(Some(worktree_manager));
         }
         Action::Expand => {
             state.expand();
-            state.rebuild_tree_with_manager(Some(_worktree_manager));
+            state.rebuild_tree_with_manager(Some(worktree_manager));
         }
         Action::Collapse => {
             state.collapse();
-            state.rebuild_tree_with_manager(Some(_worktree_manager));
+            state.rebuild_tree_with_manager(Some(worktree_manager));
         }
         Action::ToggleDisplayMode => {
             state.toggle_display_mode();
-            state.rebuild_tree_with_manager(Some(_worktree_manager));
-            state.status_message = Some(format!("View: {}", state.list_display_mode.label()));
+            state.rebuild_tree_with_manager(Some(worktree_manager));
+            state.set_status(format!("View: {}", state.list_display_mode.label()));
         }
         Action::FilterBranches => {
             state.input_dialog = Some(InputDialog::new_filter_branches(state.branch_filter.clone()));
             state.view_mode = ViewMode::Input;
         }
+        Action::FilterTree => {
+            state.input_dialog = Some(InputDialog::new_filter_tree(state.tree_filter.clone()));
+            state.view_mode = ViewMode::Input;
+        }
         Action::ClearFilter => {
             state.branch_filter = None;
-            state.rebuild_tree_with_manager(Some(_worktree_manager));
-            state.status_message = Some("Filter cleared".to_string());
+            state.tree_filter = None;
+            state.rebuild_tree_with_manager(Some(worktree_manager));
+            state.set_status("Filter cleared".to_string());
         }
         Action::ToggleFavorite => {
             if let Some(repo_key) = state.selected_repo_key() {
@@ -1482,9 +2622,9 @@ fn handle_action(
                 if let Err(e) = config.save() {
                     tracing::warn!("Failed to save favorites: {}", e);
                 }
-                state.rebuild_tree_with_manager(Some(_worktree_manager));
+                state.rebuild_tree_with_manager(Some(worktree_manager));
                 let is_fav = state.favorite_repos.contains(&repo_key);
-                state.status_message = Some(if is_fav {
+                state.set_status(if is_fav {
                     format!("★ {} added to favorites", repo_key)
                 } else {
                     format!("☆ {} removed from favorites", repo_key)
@@ -1493,7 +2633,29 @@ fn handle_action(
         }
         Action::CollapseNonFavorites => {
             state.toggle_collapse_non_favorites();
-            state.rebuild_tree_with_manager(Some(_worktree_manager));
+            state.rebuild_tree_with_manager(Some(worktree_manager));
+        }
+        Action::CollapseAll => {
+            let current_group = state.collapse_all();
+            state.rebuild_tree_with_manager(Some(worktree_manager));
+            if let Some(key) = current_group {
+                state.select_repo_group(&key);
+            }
+        }
+        Action::ExpandAll => {
+            state.expand_all();
+            state.rebuild_tree_with_manager(Some(worktree_manager));
+        }
+        Action::KeyPrefix(prefix) => {
+            state.pending_key = Some(prefix);
+        }
+        Action::JumpToRepo(letter) => {
+            if state.jump_to_repo(letter) {
+                state.user_selected = true;
+                state.invalidate_git_log();
+            } else {
+                state.set_warning(format!("No repository starting with '{}'", letter));
+            }
         }
         Action::ToggleGitLog => {
             state.show_git_log = !state.show_git_log;
@@ -1506,6 +2668,25 @@ fn handle_action(
                 state.focused_pane = FocusedPane::WorkspaceList;
             }
         }
+        Action::FilterByTool => {
+            state.cycle_tool_filter();
+            state.rebuild_tree_with_manager(Some(worktree_manager));
+        }
+        Action::ToggleIdleSessions => {
+            state.toggle_hide_idle_sessions();
+            state.rebuild_tree_with_manager(Some(worktree_manager));
+        }
+        Action::AcknowledgeSession => match state.toggle_acknowledge_selected() {
+            Some(true) => state.set_status("Acknowledged session"),
+            Some(false) => state.set_status("Cleared acknowledgement"),
+            None => state.set_warning("Select a session that needs input or has failed"),
+        },
+        Action::ToggleDetailPane => {
+            state.show_detail_pane = !state.show_detail_pane;
+        }
+        Action::CycleDetailPane => {
+            state.cycle_detail_pane(config.detail_pane_percent.clamp(20, 80));
+        }
         Action::GitLogScrollUp => {
             if state.show_git_log {
                 for _ in 0..5 {
@@ -1525,23 +2706,28 @@ fn handle_action(
             if let Some((branch_name, _is_local, repo_path)) = state.selected_branch_info() {
                 let branch_name = branch_name.to_string();
                 let repo_path = repo_path.to_string();
-                match _worktree_manager.create_worktree(
+                match worktree_manager.create_worktree(
                     Path::new(&repo_path),
                     &branch_name,
                     false, // 既存ブランチなのでcreate_branch=false
                     None,  // 既存ブランチなのでstart_point不要
                 ) {
                     Ok(path) => {
-                        state.status_message = Some(format!(
+                        state.set_status(format!(
                             "Created worktree: {}",
-                            path.display()
+                            state.display_path(&path.to_string_lossy())
                         ));
+                        spawn_post_create_hook(state, worktree_manager, event_tx, Path::new(&repo_path), &path, &branch_name);
                         state.scan_workspaces();
-                        state.rebuild_tree_with_manager(Some(_worktree_manager));
-                    }
-                    Err(e) => {
-                        state.status_message = Some(format!("Failed: {}", e));
+                        state.rebuild_tree_with_manager(Some(worktree_manager));
                     }
+                    Err(e) => match e.downcast_ref::<BranchCheckedOut>() {
+                        Some(existing) => {
+                            let existing = existing.clone();
+                            handle_existing_worktree(state, mux, config, worktree_manager, event_tx, &existing)?;
+                        }
+                        None => state.set_error(format!("Failed: {}", e)),
+                    },
                 }
             } else {
                 // Worktreeまたはグループ選択時は既存のダイアログを開く
@@ -1556,26 +2742,112 @@ fn handle_action(
         }
         Action::OpenInEditor => {
             if let Some(ws) = state.selected_workspace() {
-                let path = &ws.project_path;
-                match std::process::Command::new(&config.editor)
-                    .arg(path)
-                    .spawn()
-                {
-                    Ok(_) => {
-                        state.status_message = Some(format!("Opened in {}: {}", config.editor, path));
+                let path = ws.project_path.clone();
+                let shown = state.display_path(&ws.project_path);
+                match open_path_in_editor(mux, config, Path::new(&path)) {
+                    Ok(editor) if editor == config.editor.trim() => {
+                        state.set_status(format!("Opened in {}: {}", editor, shown));
+                    }
+                    Ok(editor) => {
+                        state.set_warning(format!("Opened in {} ({} not found): {}", editor, config.editor, shown));
+                    }
+                    Err(e) => {
+                        state.set_error(format!("Failed to open editor: {}", e));
+                    }
+                }
+            }
+        }
+        Action::OpenFile => {
+            if state.selected_workspace().is_some() {
+                match state.file_to_open(config.default_open_file.as_deref()) {
+                    Some(file) => match open_path_in_editor(mux, config, &file) {
+                        Ok(editor) => state.set_status(format!("Opened in {}: {}", editor, state.display_path(&file.to_string_lossy()))),
+                        Err(e) => state.set_error(format!("Failed to open editor: {}", e)),
+                    },
+                    None => state.set_warning("No recent file or README to open"),
+                }
+            }
+        }
+        Action::OpenInTerminal => {
+            if let Some(ws) = state.selected_workspace() {
+                let path = Path::new(&ws.project_path);
+                let ai_command = config
+                    .terminal
+                    .run_ai_command
+                    .then(|| config.effective_multiplexer_config().ai_command);
+                match terminal::open_terminal_window(&config.terminal, path, ai_command.as_deref()) {
+                    Ok(()) => {
+                        state.set_status(format!("Opened terminal: {}", state.display_path(&ws.project_path)));
                     }
                     Err(e) => {
-                        state.status_message = Some(format!("Failed to open editor: {}", e));
+                        state.set_error(format!("Failed to open terminal: {}", e));
                     }
                 }
             }
         }
+        Action::ShowDiff => {
+            if let Some(ws) = state.selected_workspace() {
+                let project_path = ws.project_path.clone();
+                let path = Path::new(&project_path);
+                let Some(base) = worktree_manager.default_branch(path) else {
+                    state.set_warning("Default branch not found".to_string());
+                    return Ok(());
+                };
+                let range = format!("{}...HEAD", base);
+
+                let result = if let Some(ref template) = config.diff_command {
+                    let cmd = terminal::expand_quoted(template, &[("{base}", &base), ("{path}", &project_path)]);
+                    std::process::Command::new("sh")
+                        .args(["-c", &cmd])
+                        .current_dir(path)
+                        .stdout(std::process::Stdio::null())
+                        .stderr(std::process::Stdio::null())
+                        .spawn()
+                        .map(|mut child| {
+                            std::thread::spawn(move || child.wait());
+                        })
+                        .map_err(anyhow::Error::from)
+                } else if mux.is_available() {
+                    // LESS=R: 1画面に収まっても即終了しないようにする（git既定のFXを外す）
+                    mux.launch_command(path, &["env", "LESS=R", "git", "-c", "color.ui=always", "diff", &range])
+                } else {
+                    Err(anyhow::anyhow!("Multiplexer not available"))
+                };
+
+                match result {
+                    Ok(()) => state.set_status(format!("Diff: {}", range)),
+                    Err(e) => state.set_error(format!("Failed to show diff: {}", e)),
+                }
+            }
+        }
+        Action::ToggleLogView => {
+            state.toggle_log_view();
+        }
+        Action::ShowMessages => {
+            state.toggle_message_history();
+        }
+        Action::OpenLogFile => {
+            if let Some(log_path) = state.log_file_path.clone() {
+                let log_str = log_path.to_string_lossy();
+                // マルチプレクサがあればペインで追従表示、なければエディタで開く
+                let result = if mux.is_available() {
+                    let cwd = log_path.parent().unwrap_or(Path::new("/"));
+                    mux.launch_command(cwd, &["less", "+F", &log_str])
+                } else {
+                    open_path_in_editor(mux, config, &log_path).map(|_| ())
+                };
+                match result {
+                    Ok(()) => state.set_status(format!("Opened log: {}", state.display_path(&log_str))),
+                    Err(e) => state.set_error(format!("Failed to open log: {}", e)),
+                }
+            }
+        }
         Action::LaunchLazygit => {
             if let Some(ws) = state.selected_workspace() {
                 if mux.is_available() {
                     let path = Path::new(&ws.project_path);
                     if let Err(e) = mux.launch_command(path, &["lazygit"]) {
-                        state.status_message = Some(format!("Failed to launch lazygit: {}", e));
+                        state.set_error(format!("Failed to launch lazygit: {}", e));
                     }
                 }
             }
@@ -1585,7 +2857,7 @@ fn handle_action(
                 if mux.is_available() {
                     let path = Path::new(&ws.project_path);
                     if let Err(e) = mux.launch_command(path, &["zsh"]) {
-                        state.status_message = Some(format!("Failed to launch shell: {}", e));
+                        state.set_error(format!("Failed to launch shell: {}", e));
                     }
                 }
             }
@@ -1595,7 +2867,7 @@ fn handle_action(
                 if mux.is_available() {
                     let path = Path::new(&ws.project_path);
                     if let Err(e) = mux.launch_command(path, &["yazi"]) {
-                        state.status_message = Some(format!("Failed to launch yazi: {}", e));
+                        state.set_error(format!("Failed to launch yazi: {}", e));
                     }
                 }
             }
@@ -1605,7 +2877,34 @@ fn handle_action(
                 if mux.is_available() {
                     let path = Path::new(&ws.project_path);
                     if let Err(e) = mux.launch_command(path, &["claude"]) {
-                        state.status_message = Some(format!("Failed to launch Claude: {}", e));
+                        state.set_error(format!("Failed to launch Claude: {}", e));
+                    }
+                }
+            }
+        }
+        Action::ExportSnapshot => {
+            let dir = workspace_manager::app::export::export_dir(config.export_dir.as_deref());
+            match workspace_manager::app::export::write_snapshot(state, config.export_format, &dir) {
+                Ok(path) => state.set_status(format!("Exported snapshot: {}", state.display_path(&path.to_string_lossy()))),
+                Err(e) => state.set_error(format!("Failed to export snapshot: {}", e)),
+            }
+        }
+        Action::RelaunchSession => {
+            if let Some(session) = state.selected_session() {
+                if !session.can_relaunch() {
+                    state.set_warning(format!("{} session is still running", session.tool.name()));
+                } else if !mux.is_available() {
+                    state.set_warning(format!("Relaunching {} needs a multiplexer", session.tool.name()));
+                } else {
+                    let tool = session.tool.name();
+                    let command = session.relaunch_command(&config.effective_multiplexer_config().ai_command);
+                    let path = state.workspaces.get(session.workspace_index).map(|ws| ws.project_path.clone());
+                    if let Some(path) = path {
+                        let args: Vec<&str> = command.iter().map(String::as_str).collect();
+                        match mux.launch_command(Path::new(&path), &args) {
+                            Ok(()) => state.set_status(format!("Relaunched {}: {}", tool, command.join(" "))),
+                            Err(e) => state.set_error(format!("Failed to relaunch {}: {}", tool, e)),
+                        }
                     }
                 }
             }
@@ -1618,11 +2917,11 @@ fn handle_action(
                         let cwd = Path::new(&ws.project_path);
                         match mux.new_pane(&session, cwd) {
                             Ok(()) => {
-                                state.status_message = Some(format!("Added pane: {}", ws.project_path));
+                                state.set_status(format!("Added pane: {}", state.display_path(&ws.project_path)));
                                 run_post_select_command(config);
                             }
                             Err(e) => {
-                                state.status_message = Some(format!("Failed to add pane: {}", e));
+                                state.set_error(format!("Failed to add pane: {}", e));
                             }
                         }
                     } else {
@@ -1637,17 +2936,17 @@ fn handle_action(
                                 state.open_session_select_dialog(sessions, context);
                             }
                             Ok(_) => {
-                                state.status_message = Some("No sessions found".to_string());
+                                state.set_warning("No sessions found".to_string());
                             }
                             Err(e) => {
-                                state.status_message = Some(format!("Failed to list sessions: {}", e));
+                                state.set_error(format!("Failed to list sessions: {}", e));
                             }
                         }
                     }
                 } else if mux.is_internal() {
-                    state.status_message = Some("Add pane is for external mode only".to_string());
+                    state.set_warning("Add pane is for external mode only".to_string());
                 } else {
-                    state.status_message = Some("Multiplexer integration disabled".to_string());
+                    state.set_warning("Multiplexer integration disabled".to_string());
                 }
             }
         }
@@ -1658,13 +2957,13 @@ fn handle_action(
                     let pane_id = workspace_index.and_then(|idx| {
                         state.sessions_for_workspace(idx)
                             .first()
-                            .and_then(|&si| state.sessions.get(si))
+                            .and_then(|si| state.sessions.get(si))
                             .and_then(|s| s.pane_id)
                     });
 
                     if let Some(pane_id) = pane_id {
                         if let Err(e) = mux.close_pane(pane_id) {
-                            state.status_message = Some(format!("Failed to close pane: {}", e));
+                            state.set_error(format!("Failed to close pane: {}", e));
                         }
                     }
                 } else if mux.is_available() {
@@ -1673,17 +2972,17 @@ fn handle_action(
                         let session = session.to_string();
                         match mux.close_window(&session, &tab_name) {
                             Ok(()) => {
-                                state.status_message = Some(format!("Closed tab: {}", tab_name));
+                                state.set_status(format!("Closed tab: {}", tab_name));
                             }
                             Err(e) => {
-                                state.status_message = Some(format!("Failed to close tab: {}", e));
+                                state.set_error(format!("Failed to close tab: {}", e));
                             }
                         }
                     } else {
-                        state.status_message = Some("No session configured".to_string());
+                        state.set_warning("No session configured".to_string());
                     }
                 } else {
-                    state.status_message = Some("Multiplexer integration disabled".to_string());
+                    state.set_warning("Multiplexer integration disabled".to_string());
                 }
             }
         }
@@ -1693,12 +2992,120 @@ fn handle_action(
                 state.set_selected_index(index);
                 state.user_selected = true;
                 state.invalidate_git_log();
+                // グループ行クリックで展開/折りたたみ
+                if matches!(
+                    state.tree_items.get(index),
+                    Some(TreeItem::RepoGroup { .. } | TreeItem::RemoteBranchGroup { .. })
+                ) {
+                    state.toggle_expand();
+                    state.rebuild_tree_with_manager(Some(worktree_manager));
+                }
                 // ペイン行クリックでそのペインにフォーカス
                 if state.selected_pane().is_some() {
-                    handle_action(state, mux, config, _worktree_manager, Action::Select)?;
+                    handle_action(state, mux, config, worktree_manager, event_tx, Action::Select)?;
+                }
+            }
+        }
+        Action::MouseHover(row) => {
+            state.hover_index = row
+                .map(|row| row as usize + state.table_state.offset())
+                .filter(|&index| index < state.tree_item_count());
+        }
+        Action::MouseRightClick(row) => {
+            let index = row as usize + state.table_state.offset();
+            if index < state.tree_item_count() {
+                state.set_selected_index(index);
+                state.user_selected = true;
+                state.invalidate_git_log();
+                handle_action(state, mux, config, worktree_manager, event_tx, Action::ContextMenu)?;
+            }
+        }
+        Action::ReloadConfig => {
+            state.reload_config_requested = true;
+        }
+        Action::RefreshSelected => {
+            if let Some(ws) = state.selected_workspace() {
+                let path = ws.project_path.clone();
+                state.set_status(format!("Refreshing status: {}", ws.branch));
+                state.refresh_request = Some(path);
+            }
+        }
+        Action::ContextMenu => {
+            state.open_context_menu();
+        }
+        Action::OpenWithLayout => {
+            if let Some(ws) = state.selected_workspace() {
+                if mux.is_internal() || !mux.is_available() {
+                    state.set_warning("Open with layout is for external mode only".to_string());
+                } else {
+                    let context = SelectionContext {
+                        workspace_path: ws.project_path.clone(),
+                        repo_name: ws.repo_name.clone(),
+                        branch_name: ws.branch.clone(),
+                    };
+                    let layouts = config.effective_layout_dir()
+                        .map(|dir| list_layouts(&dir))
+                        .unwrap_or_default();
+                    if layouts.is_empty() {
+                        state.set_warning("No layouts found".to_string());
+                    } else {
+                        state.open_layout_select_dialog(layouts, context);
+                    }
+                }
+            }
+        }
+        Action::OpenInBrowser => {
+            if let Some(ws) = state.selected_workspace() {
+                let (level, message) = match ws.branch_web_url() {
+                    Some(url) => match terminal::open_in_browser(&url) {
+                        Ok(()) => (MessageLevel::Info, format!("Opened: {}", url)),
+                        Err(e) => (MessageLevel::Error, format!("Failed to open browser: {}", e)),
+                    },
+                    None => (MessageLevel::Warn, "No recognizable origin remote".to_string()),
+                };
+                state.set_status_with_level(level, message);
+            }
+        }
+        Action::CreatePullRequest => {
+            if let Some(ws) = state.selected_workspace() {
+                let command = ws.forge().map(|forge| forge.create_pr_command());
+                let (level, message) = match command {
+                    None => (MessageLevel::Warn, "No recognizable origin remote".to_string()),
+                    Some(command) if !workspace_manager::workspace::forge::command_available(command[0]) => {
+                        (MessageLevel::Warn, format!("{} is not installed", command[0]))
+                    }
+                    Some(_) if !mux.is_available() => {
+                        (MessageLevel::Warn, "Multiplexer integration disabled".to_string())
+                    }
+                    Some(command) => match mux.launch_command(Path::new(&ws.project_path), command) {
+                        Ok(()) => (MessageLevel::Info, format!("Running: {}", command.join(" "))),
+                        Err(e) => (MessageLevel::Error, format!("Failed to run {}: {}", command.join(" "), e)),
+                    },
+                };
+                state.set_status_with_level(level, message);
+            }
+        }
+        Action::CopyPath => {
+            if let Some(ws) = state.selected_workspace() {
+                let path = ws.project_path.clone();
+                match terminal::copy_to_clipboard(&path) {
+                    Ok(()) => state.set_status(format!("Copied: {}", state.display_path(&path))),
+                    Err(e) => state.set_error(format!("Failed to copy path: {}", e)),
                 }
             }
         }
+        Action::FetchRepo => {
+            if let Some(repo_path) = state.selected_repo_path() {
+                state.set_status(format!("Fetching {}...", repo_path));
+                let tx = event_tx.clone();
+                let remote = worktree_manager.config().default_remote.clone();
+                std::thread::spawn(move || {
+                    let result = WorktreeManager::fetch_remote(Path::new(&repo_path), &remote)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.blocking_send(AppEvent::FetchFinished { repo_path, result });
+                });
+            }
+        }
         Action::MouseDoubleClick(row) => {
             let index = row as usize + state.table_state.offset();
             if index < state.tree_item_count() {
@@ -1706,14 +3113,16 @@ fn handle_action(
                 state.user_selected = true;
                 state.invalidate_git_log();
             }
-            handle_action(state, mux, config, _worktree_manager, Action::Select)?;
+            let action = config.mouse_double_click_action();
+            handle_action(state, mux, config, worktree_manager, event_tx, action)?;
         }
         Action::MouseMiddleClick(row) => {
             let index = row as usize + state.table_state.offset();
             if index < state.tree_item_count() {
                 state.set_selected_index(index);
             }
-            handle_action(state, mux, config, _worktree_manager, Action::CloseWorkspace)?;
+            let action = config.mouse_middle_click_action();
+            handle_action(state, mux, config, worktree_manager, event_tx, action)?;
         }
         Action::ScrollUp => {
             state.move_up();
@@ -1728,3 +3137,82 @@ fn handle_action(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod notify_event_tests {
+    use super::*;
+    use workspace_manager::app::config::WorktreeConfig;
+    use workspace_manager::workspace::Workspace;
+
+    #[test]
+    fn test_notify_result_json() {
+        use anyhow::Context as _;
+
+        let sent = notify_result_json("s1", &Ok(()));
+        assert_eq!(sent, serde_json::json!({"ok": true, "session_id": "s1", "delivered": true}));
+
+        // No TUI running: still ok, but nothing was delivered
+        let socket_path = std::path::Path::new("/nonexistent/workspace-manager.sock");
+        let missing = notify::send_notification(socket_path, &NotifyMessage::Heartbeat { session_id: "s1".into() });
+        let no_tui = notify_result_json("s1", &missing);
+        assert_eq!(no_tui, serde_json::json!({"ok": true, "session_id": "s1", "delivered": false}));
+
+        let refused: Result<()> = Err(anyhow::Error::from(std::io::Error::from(std::io::ErrorKind::ConnectionRefused)))
+            .context("Failed to connect to socket");
+        assert_eq!(notify_result_json("s1", &refused)["delivered"], false);
+
+        let failed = notify_result_json("s1", &Err(anyhow::anyhow!("Failed to write message")));
+        assert_eq!(failed["ok"], false);
+        assert_eq!(failed["error"], "Failed to write message");
+    }
+
+    #[test]
+    fn test_auto_registered_session_tool_follows_external_id_prefix() {
+        let manager = WorktreeManager::new(WorktreeConfig::default());
+        let mut state = AppState::new();
+        state.workspaces = vec![Workspace::new(
+            "/nonexistent/app".to_string(),
+            "app".to_string(),
+            "main".to_string(),
+        )];
+
+        let cases = [
+            ("claude:s1", AiTool::Claude),
+            ("kiro:/nonexistent/app:c1", AiTool::Kiro),
+            ("opencode:s1", AiTool::OpenCode),
+            ("codex:s1", AiTool::Codex),
+            ("tmux:main:1", AiTool::Other),
+        ];
+        for (external_id, expected) in cases {
+            let event = AppEvent::SessionStatusAnalyzed {
+                external_id: external_id.to_string(),
+                project_path: "/nonexistent/app".to_string(),
+                status: workspace_manager::logwatch::SessionStatus::default(),
+            };
+            handle_notify_event(&mut state, event, &manager);
+
+            let session = state.get_session_by_external_id(external_id).unwrap();
+            assert_eq!(session.tool, expected, "{}", external_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod complete_tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<std::ffi::OsString> {
+        line.split_whitespace().map(Into::into).collect()
+    }
+
+    #[test]
+    fn test_completion_repo() {
+        assert_eq!(completion_repo(&args("wm -- wm open app fe"), None), Some("app".to_string()));
+        assert_eq!(completion_repo(&args("wm -- wm -l debug path app x"), Some(5)), Some("app".to_string()));
+        // 補完中の語がリポジトリ名なら、それより前にリポジトリはない
+        assert_eq!(completion_repo(&args("wm -- wm open app"), Some(2)), None);
+        // サブコマンドは語ではなく clap の解析で判定する
+        assert_eq!(completion_repo(&args("wm -- wm notify register --project-path path x"), None), None);
+        assert_eq!(completion_repo(&args("wm open app"), None), None);
+    }
+}
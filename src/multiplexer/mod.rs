@@ -1,8 +1,11 @@
 pub mod tmux;
+pub mod tmux_layout;
 pub mod zellij;
 
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// ペイン情報（マルチプレクサから取得）
@@ -149,7 +152,7 @@ pub trait Multiplexer {
 }
 
 /// マルチプレクサ設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MultiplexerConfig {
     /// バックエンド選択: auto | zellij | tmux | none
     #[serde(default = "default_mux_backend")]
@@ -171,6 +174,76 @@ pub struct MultiplexerConfig {
     /// タブ切り替え後に実行するコマンド
     #[serde(default)]
     pub post_select_command: Option<String>,
+    /// tmux でツール（lazygit, yazi 等）を起動する際の配置
+    #[serde(default)]
+    pub tmux_launch: TmuxLaunchConfig,
+}
+
+/// tmux でのツール起動方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TmuxLaunchMode {
+    /// 現在のウィンドウを分割（split-window）
+    #[default]
+    Split,
+    /// 新規ウィンドウ（new-window）
+    Window,
+    /// フローティングポップアップ（display-popup、tmux 3.2+）
+    Popup,
+}
+
+/// split-window の分割方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitDirection {
+    /// 上下に分割（-v、tmux のデフォルト）
+    #[default]
+    Vertical,
+    /// 左右に分割（-h）
+    Horizontal,
+}
+
+/// tmux でのツール起動スタイル
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TmuxLaunchStyle {
+    /// 起動方法: split | window | popup
+    #[serde(default)]
+    pub mode: TmuxLaunchMode,
+    /// 分割方向（mode = "split" 時のみ）: vertical | horizontal
+    #[serde(default)]
+    pub direction: SplitDirection,
+    /// ペイン/ポップアップのサイズ（%）。未設定時は tmux のデフォルト
+    #[serde(default)]
+    pub size_percent: Option<u8>,
+}
+
+/// tmux でのツール起動設定
+///
+/// ```toml
+/// [multiplexer.tmux_launch]
+/// mode = "split"
+/// direction = "horizontal"
+/// size_percent = 40
+///
+/// [multiplexer.tmux_launch.commands.lazygit]
+/// mode = "popup"
+/// size_percent = 90
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TmuxLaunchConfig {
+    /// 全コマンド共通のデフォルトスタイル
+    #[serde(flatten)]
+    pub default: TmuxLaunchStyle,
+    /// コマンド名（プログラム名）ごとの上書き
+    #[serde(default)]
+    pub commands: HashMap<String, TmuxLaunchStyle>,
+}
+
+impl TmuxLaunchConfig {
+    /// コマンド名に対応するスタイルを取得（上書きがなければデフォルト）
+    pub fn style_for(&self, program: &str) -> &TmuxLaunchStyle {
+        self.commands.get(program).unwrap_or(&self.default)
+    }
 }
 
 fn default_mux_backend() -> String {
@@ -195,6 +268,7 @@ impl Default for MultiplexerConfig {
             default_layout: None,
             layout_dir: None,
             post_select_command: None,
+            tmux_launch: TmuxLaunchConfig::default(),
         }
     }
 }
@@ -223,6 +297,7 @@ pub fn multiplexer_config_from_zellij(zellij: &crate::app::ZellijConfig) -> Mult
         default_layout: zellij.default_layout.clone(),
         layout_dir: zellij.layout_dir.clone(),
         post_select_command: zellij.post_select_command.clone(),
+        tmux_launch: TmuxLaunchConfig::default(),
     }
 }
 
@@ -239,22 +314,31 @@ pub fn create_multiplexer(
         .and_then(|c| c.session_name.clone())
         .or_else(|| zellij_config.session_name.clone());
 
+    let tmux_launch = mux_config
+        .map(|c| c.tmux_launch.clone())
+        .unwrap_or_default();
+
     match backend_str {
         "zellij" => Box::new(zellij::ZellijMultiplexer::auto_detect(session_name)),
-        "tmux" => Box::new(tmux::TmuxMultiplexer::auto_detect(session_name)),
+        "tmux" => Box::new(
+            tmux::TmuxMultiplexer::auto_detect(session_name).with_launch_config(tmux_launch),
+        ),
         "none" => Box::new(zellij::ZellijMultiplexer::new_disabled()),
         _ => {
             // auto: 環境変数で自動検出
             if std::env::var("ZELLIJ").is_ok() {
                 Box::new(zellij::ZellijMultiplexer::new_internal())
             } else if std::env::var("TMUX").is_ok() {
-                Box::new(tmux::TmuxMultiplexer::new_internal())
+                Box::new(tmux::TmuxMultiplexer::new_internal().with_launch_config(tmux_launch))
             } else if let Some(session) = session_name {
                 // 外部モード: zellij を優先（後方互換）
                 if zellij_config.enabled {
                     Box::new(zellij::ZellijMultiplexer::new_external(session))
                 } else {
-                    Box::new(tmux::TmuxMultiplexer::new_external(session))
+                    Box::new(
+                        tmux::TmuxMultiplexer::new_external(session)
+                            .with_launch_config(tmux_launch),
+                    )
                 }
             } else {
                 Box::new(zellij::ZellijMultiplexer::new_disabled())
@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
-use super::{Multiplexer, MultiplexerBackend, WindowActionResult};
+use super::{
+    tmux_layout, Multiplexer, MultiplexerBackend, SplitDirection, TmuxLaunchConfig, TmuxLaunchMode,
+    TmuxLaunchStyle, WindowActionResult,
+};
 
 /// tmux動作モード
 #[derive(Debug, Clone)]
@@ -18,6 +21,7 @@ pub enum TmuxMode {
 pub struct TmuxMultiplexer {
     mode: TmuxMode,
     session_name: String,
+    launch_config: TmuxLaunchConfig,
 }
 
 impl TmuxMultiplexer {
@@ -34,6 +38,7 @@ impl TmuxMultiplexer {
         Self {
             mode: TmuxMode::Internal,
             session_name,
+            launch_config: TmuxLaunchConfig::default(),
         }
     }
 
@@ -41,6 +46,7 @@ impl TmuxMultiplexer {
         Self {
             mode: TmuxMode::External,
             session_name,
+            launch_config: TmuxLaunchConfig::default(),
         }
     }
 
@@ -53,10 +59,17 @@ impl TmuxMultiplexer {
             Self {
                 mode: TmuxMode::External,
                 session_name: String::new(),
+                launch_config: TmuxLaunchConfig::default(),
             }
         }
     }
 
+    /// ツール起動時の配置設定を適用
+    pub fn with_launch_config(mut self, launch_config: TmuxLaunchConfig) -> Self {
+        self.launch_config = launch_config;
+        self
+    }
+
     /// 操作対象のセッション名を解決
     fn resolve_session(&self) -> Result<String> {
         if !self.session_name.is_empty() {
@@ -288,22 +301,33 @@ impl Multiplexer for TmuxMultiplexer {
         session: &str,
         name: &str,
         cwd: &Path,
-        _layout: Option<&Path>,
+        layout: Option<&Path>,
     ) -> Result<()> {
+        // 解釈できないレイアウトは従来どおり単一ペインのウィンドウにする
+        let layout = layout.and_then(|path| match tmux_layout::parse_layout_file(path) {
+            Ok(pane) => Some(pane),
+            Err(e) => {
+                tracing::warn!("Ignoring layout {} for tmux: {:#}", path.display(), e);
+                None
+            }
+        });
+
         let cwd_str = cwd.to_string_lossy();
-        let status = Command::new("tmux")
+        let output = Command::new("tmux")
             .args([
                 "new-window",
                 "-t", session,
                 "-n", name,
                 "-c", &cwd_str,
+                "-P", "-F", "#{pane_id}",
             ])
-            .status()
+            .output()
             .context("Failed to create new window")?;
 
-        if !status.success() {
+        if !output.status.success() {
             anyhow::bail!("Failed to create window: {}", name);
         }
+        let pane_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
 
         // ワークスペース名をユーザーオプションに保存（ウィンドウ検索用）
         let target = format!("{}:{}", session, name);
@@ -315,6 +339,12 @@ impl Multiplexer for TmuxMultiplexer {
             .args(["set-window-option", "-t", &target, "automatic-rename", "off"])
             .status();
 
+        if let Some(layout) = layout {
+            if let Err(e) = tmux_layout::apply_layout(&layout, &pane_id, cwd) {
+                tracing::warn!("Failed to apply layout to {}: {:#}", name, e);
+            }
+        }
+
         Ok(())
     }
 
@@ -415,14 +445,11 @@ impl Multiplexer for TmuxMultiplexer {
         let cwd_str = cwd.to_string_lossy();
         let cmd_str = command.join(" ");
         let session = self.resolve_session()?;
+        let program = command.first().copied().unwrap_or_default();
+        let style = self.launch_config.style_for(program);
 
         let status = Command::new("tmux")
-            .args([
-                "split-window",
-                "-t", &session,
-                "-c", &cwd_str,
-                &cmd_str,
-            ])
+            .args(build_launch_args(style, &session, &cwd_str, &cmd_str))
             .status()
             .context("Failed to launch command in tmux")?;
 
@@ -534,3 +561,97 @@ impl Multiplexer for TmuxMultiplexer {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 }
+
+/// 起動スタイルに応じた tmux 引数を組み立てる
+fn build_launch_args(style: &TmuxLaunchStyle, session: &str, cwd: &str, cmd: &str) -> Vec<String> {
+    let mut args: Vec<String> = Vec::new();
+    match style.mode {
+        TmuxLaunchMode::Split => {
+            args.push("split-window".into());
+            args.push(match style.direction {
+                SplitDirection::Vertical => "-v".into(),
+                SplitDirection::Horizontal => "-h".into(),
+            });
+            if let Some(size) = style.size_percent {
+                args.extend(["-l".into(), format!("{}%", size.min(100))]);
+            }
+            args.extend(["-t".into(), session.into(), "-c".into(), cwd.into()]);
+        }
+        TmuxLaunchMode::Window => {
+            args.extend([
+                "new-window".into(),
+                "-t".into(),
+                session.into(),
+                "-c".into(),
+                cwd.into(),
+            ]);
+        }
+        TmuxLaunchMode::Popup => {
+            args.extend(["display-popup".into(), "-E".into()]);
+            if let Some(size) = style.size_percent {
+                let size = format!("{}%", size.min(100));
+                args.extend(["-w".into(), size.clone(), "-h".into(), size]);
+            }
+            args.extend(["-t".into(), session.into(), "-d".into(), cwd.into()]);
+        }
+    }
+    args.push(cmd.into());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_launch_args_default_split() {
+        let args = build_launch_args(&TmuxLaunchStyle::default(), "main", "/tmp", "yazi");
+        assert_eq!(args, ["split-window", "-v", "-t", "main", "-c", "/tmp", "yazi"]);
+    }
+
+    #[test]
+    fn test_build_launch_args_horizontal_with_size() {
+        let style = TmuxLaunchStyle {
+            mode: TmuxLaunchMode::Split,
+            direction: SplitDirection::Horizontal,
+            size_percent: Some(40),
+        };
+        let args = build_launch_args(&style, "main", "/tmp", "yazi");
+        assert_eq!(
+            args,
+            ["split-window", "-h", "-l", "40%", "-t", "main", "-c", "/tmp", "yazi"]
+        );
+    }
+
+    #[test]
+    fn test_build_launch_args_popup() {
+        let style = TmuxLaunchStyle {
+            mode: TmuxLaunchMode::Popup,
+            direction: SplitDirection::Vertical,
+            size_percent: Some(90),
+        };
+        let args = build_launch_args(&style, "main", "/tmp", "lazygit");
+        assert_eq!(
+            args,
+            ["display-popup", "-E", "-w", "90%", "-h", "90%", "-t", "main", "-d", "/tmp", "lazygit"]
+        );
+    }
+
+    #[test]
+    fn test_launch_config_per_command_override() {
+        let config: TmuxLaunchConfig = toml::from_str(
+            r#"
+            mode = "split"
+            direction = "horizontal"
+
+            [commands.lazygit]
+            mode = "popup"
+            size_percent = 90
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.style_for("yazi").direction, SplitDirection::Horizontal);
+        assert_eq!(config.style_for("lazygit").mode, TmuxLaunchMode::Popup);
+        assert_eq!(config.style_for("lazygit").size_percent, Some(90));
+    }
+}
@@ -0,0 +1,285 @@
+//! Zellij の KDL レイアウトを tmux の split-window / send-keys で再現する
+//!
+//! 対応するのは pane の入れ子・split_direction・size（%）・command/args/cwd のみ。
+//! plugin ペイン（tab-bar / status-bar など）は tmux に相当するものがないので無視する。
+
+use anyhow::{Context, Result};
+use kdl::{KdlDocument, KdlNode};
+use std::path::Path;
+use std::process::Command;
+
+use super::SplitDirection;
+use crate::app::terminal::shell_quote;
+
+/// レイアウト中のペイン
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutPane {
+    /// コマンド（なければシェル）を動かす1枚のペイン
+    Leaf {
+        command: Option<String>,
+        args: Vec<String>,
+        cwd: Option<String>,
+    },
+    /// 子ペインへの分割（方向は tmux の意味: Horizontal = 左右に並ぶ）
+    Split {
+        direction: SplitDirection,
+        children: Vec<(Option<u16>, LayoutPane)>,
+    },
+}
+
+/// レイアウトファイルを読んでペイン構成にする
+pub fn parse_layout_file(path: &Path) -> Result<LayoutPane> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read layout {}", path.display()))?;
+    parse_layout(&content)
+}
+
+/// KDL 文字列をペイン構成にする（トップレベルの `layout` ノードが必要）
+pub fn parse_layout(content: &str) -> Result<LayoutPane> {
+    let doc: KdlDocument = content.parse().context("Invalid KDL")?;
+    let layout = doc.get("layout").context("No `layout` node")?;
+    pane_from_children(layout, SplitDirection::Vertical)
+        .context("Layout has no non-plugin panes")
+}
+
+/// ノードの子 pane をまとめる（plugin だけのペインは除く）
+///
+/// zellij の既定の split_direction は horizontal（上下に並ぶ）なので tmux の Vertical が既定。
+fn pane_from_children(node: &KdlNode, default_direction: SplitDirection) -> Option<LayoutPane> {
+    let direction = match string_prop(node, "split_direction").as_deref() {
+        Some("vertical") => SplitDirection::Horizontal,
+        Some("horizontal") => SplitDirection::Vertical,
+        _ => default_direction,
+    };
+    let mut children: Vec<(Option<u16>, LayoutPane)> = node
+        .children()
+        .map(|doc| doc.nodes())
+        .unwrap_or_default()
+        .iter()
+        .filter(|child| child.name().value() == "pane")
+        .filter_map(|child| parse_pane(child).map(|pane| (percent_size(child), pane)))
+        .collect();
+    match children.len() {
+        0 => None,
+        1 => children.pop().map(|(_, pane)| pane),
+        _ => Some(LayoutPane::Split { direction, children }),
+    }
+}
+
+/// `pane` ノード1つを解釈する
+fn parse_pane(node: &KdlNode) -> Option<LayoutPane> {
+    let child_nodes = node.children().map(|doc| doc.nodes()).unwrap_or_default();
+    if child_nodes.iter().any(|n| n.name().value() == "plugin") {
+        return None;
+    }
+    if child_nodes.iter().any(|n| n.name().value() == "pane") {
+        return pane_from_children(node, SplitDirection::Vertical);
+    }
+
+    let child_arg = |name: &str| {
+        child_nodes
+            .iter()
+            .find(|n| n.name().value() == name)
+            .and_then(|n| n.entries().first())
+            .and_then(|e| e.value().as_string())
+            .map(str::to_string)
+    };
+    let args = child_nodes
+        .iter()
+        .find(|n| n.name().value() == "args")
+        .map(|n| {
+            n.entries()
+                .iter()
+                .filter(|e| e.name().is_none())
+                .filter_map(|e| e.value().as_string().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(LayoutPane::Leaf {
+        command: string_prop(node, "command").or_else(|| child_arg("command")),
+        args,
+        cwd: string_prop(node, "cwd").or_else(|| child_arg("cwd")),
+    })
+}
+
+fn string_prop(node: &KdlNode, key: &str) -> Option<String> {
+    node.get(key)
+        .and_then(|e| e.value().as_string())
+        .map(str::to_string)
+}
+
+/// `size="70%"` を 70 にする（行数指定の整数は tmux では再現しない）
+fn percent_size(node: &KdlNode) -> Option<u16> {
+    node.get("size")
+        .and_then(|e| e.value().as_string())
+        .and_then(|s| s.trim().strip_suffix('%'))
+        .and_then(|s| s.trim().parse().ok())
+        .filter(|&p| p > 0 && p < 100)
+}
+
+/// 子の割合を決める（未指定の子は残りを均等に分ける）
+fn resolve_sizes(sizes: &[Option<u16>]) -> Vec<u32> {
+    let fixed: u32 = sizes.iter().flatten().map(|&s| u32::from(s)).sum();
+    let unset = sizes.iter().filter(|s| s.is_none()).count() as u32;
+    let share = 100u32.saturating_sub(fixed).max(unset).checked_div(unset).unwrap_or(0);
+    sizes
+        .iter()
+        .map(|s| s.map(u32::from).unwrap_or(share).max(1))
+        .collect()
+}
+
+/// 順に split-window するときの各新ペインの大きさ（分割元ペインに対する %）
+///
+/// i 番目の子は、i-1 番目以降の領域から i 番目以降の分を切り出して作る。
+pub fn split_percents(sizes: &[Option<u16>]) -> Vec<u32> {
+    let sizes = resolve_sizes(sizes);
+    (1..sizes.len())
+        .map(|i| {
+            let rest: u32 = sizes[i..].iter().sum();
+            let with_prev: u32 = sizes[i - 1..].iter().sum();
+            (rest * 100 / with_prev).clamp(1, 99)
+        })
+        .collect()
+}
+
+/// 作成済みウィンドウの最初のペイン `pane_id` にレイアウトを再現する
+pub fn apply_layout(pane: &LayoutPane, pane_id: &str, cwd: &Path) -> Result<()> {
+    match pane {
+        LayoutPane::Leaf { .. } => {
+            if let Some(line) = leaf_command_line(pane, cwd) {
+                run_tmux(&["send-keys", "-t", pane_id, &line, "Enter"])?;
+            }
+            Ok(())
+        }
+        LayoutPane::Split { direction, children } => {
+            let flag = match direction {
+                SplitDirection::Vertical => "-v",
+                SplitDirection::Horizontal => "-h",
+            };
+            let sizes: Vec<Option<u16>> = children.iter().map(|(size, _)| *size).collect();
+            let cwd_str = cwd.to_string_lossy();
+            // この階層の分割を先に済ませてから各子を再帰的に処理する
+            let mut pane_ids = vec![pane_id.to_string()];
+            for percent in split_percents(&sizes) {
+                let target = pane_ids.last().cloned().unwrap_or_default();
+                let length = format!("{}%", percent);
+                let output = Command::new("tmux")
+                    .args(["split-window", flag, "-l", &length, "-t", &target, "-c", &cwd_str])
+                    .args(["-P", "-F", "#{pane_id}"])
+                    .output()
+                    .context("Failed to split window")?;
+                if !output.status.success() {
+                    anyhow::bail!("tmux split-window failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+                }
+                pane_ids.push(String::from_utf8_lossy(&output.stdout).trim().to_string());
+            }
+            for ((_, child), id) in children.iter().zip(&pane_ids) {
+                apply_layout(child, id, cwd)?;
+            }
+            // 最初のペイン（メインの作業ペイン）を選択しておく
+            run_tmux(&["select-pane", "-t", pane_id])
+        }
+    }
+}
+
+/// Leaf ペインに送るコマンドライン（cwd への cd とコマンド）
+///
+/// Zellij と同じく command はシェル断片ではなく実行ファイル名として扱い、args と一緒にクォートする。
+fn leaf_command_line(pane: &LayoutPane, cwd: &Path) -> Option<String> {
+    let LayoutPane::Leaf { command, args, cwd: pane_cwd } = pane else {
+        return None;
+    };
+    let mut line = Vec::new();
+    if let Some(dir) = pane_cwd {
+        line.push(format!("cd {}", shell_quote(&cwd.join(dir).to_string_lossy())));
+    }
+    if let Some(command) = command {
+        let mut words = vec![shell_quote(command)];
+        words.extend(args.iter().map(|a| shell_quote(a)));
+        line.push(words.join(" "));
+    }
+    (!line.is_empty()).then(|| line.join(" && "))
+}
+
+fn run_tmux(args: &[&str]) -> Result<()> {
+    let status = Command::new("tmux").args(args).status().context("Failed to run tmux")?;
+    if !status.success() {
+        anyhow::bail!("tmux {} failed", args.first().copied().unwrap_or_default());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(command: Option<&str>) -> LayoutPane {
+        LayoutPane::Leaf { command: command.map(str::to_string), args: vec![], cwd: None }
+    }
+
+    fn builtin(template: &str) -> LayoutPane {
+        parse_layout(&template.replace("{{AI_COMMAND}}", "claude")).unwrap()
+    }
+
+    #[test]
+    fn test_parse_builtin_layouts() {
+        assert_eq!(builtin(include_str!("../../layouts/simple.kdl.template")), leaf(Some("claude")));
+        assert_eq!(
+            builtin(include_str!("../../layouts/with-shell.kdl.template")),
+            LayoutPane::Split {
+                direction: SplitDirection::Horizontal,
+                children: vec![(Some(70), leaf(Some("claude"))), (Some(30), leaf(None))],
+            }
+        );
+        assert_eq!(
+            builtin(include_str!("../../layouts/dev.kdl.template")),
+            LayoutPane::Split {
+                direction: SplitDirection::Horizontal,
+                children: vec![
+                    (
+                        Some(25),
+                        LayoutPane::Split {
+                            direction: SplitDirection::Vertical,
+                            children: vec![(Some(60), leaf(Some("yazi"))), (Some(40), leaf(Some("lazygit")))],
+                        },
+                    ),
+                    (
+                        Some(75),
+                        LayoutPane::Split {
+                            direction: SplitDirection::Vertical,
+                            children: vec![(Some(70), leaf(Some("claude"))), (Some(30), leaf(None))],
+                        },
+                    ),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_command_props_and_invalid_layouts() {
+        let pane = parse_layout(r#"layout { pane command="htop" cwd="src" { args "-d" "5"; }; }"#).unwrap();
+        assert_eq!(
+            pane,
+            LayoutPane::Leaf {
+                command: Some("htop".to_string()),
+                args: vec!["-d".to_string(), "5".to_string()],
+                cwd: Some("src".to_string()),
+            }
+        );
+        assert_eq!(
+            leaf_command_line(&pane, Path::new("/work/my repo")).as_deref(),
+            Some("cd '/work/my repo/src' && 'htop' '-d' '5'")
+        );
+        assert_eq!(leaf_command_line(&leaf(None), Path::new("/work")), None);
+
+        assert!(parse_layout("layout {").is_err());
+        assert!(parse_layout(r#"layout { pane { plugin location="tab-bar"; }; }"#).is_err());
+    }
+
+    #[test]
+    fn test_split_percents() {
+        assert_eq!(split_percents(&[Some(70), Some(30)]), vec![30]);
+        assert_eq!(split_percents(&[None, None, None]), vec![66, 50]);
+        assert_eq!(split_percents(&[Some(50), None, None]), vec![50, 50]);
+    }
+}
@@ -31,6 +31,11 @@ pub enum NotifyMessage {
         /// Session ID from the AI CLI tool
         session_id: String,
     },
+    /// Keepalive for a session that is alive but has nothing new to report
+    Heartbeat {
+        /// Session ID from the AI CLI tool
+        session_id: String,
+    },
     /// Tab focus changed (from Zellij plugin)
     TabFocus {
         /// Tab name that received focus
@@ -45,6 +50,7 @@ impl NotifyMessage {
             NotifyMessage::Register { session_id, .. } => session_id,
             NotifyMessage::Status { session_id, .. } => session_id,
             NotifyMessage::Unregister { session_id } => session_id,
+            NotifyMessage::Heartbeat { session_id } => session_id,
             NotifyMessage::TabFocus { tab_name } => tab_name,
         }
     }
@@ -78,6 +84,15 @@ mod tests {
         assert!(json.contains("\"status\":\"working\""));
     }
 
+    #[test]
+    fn test_heartbeat_roundtrip() {
+        let json = r#"{"type":"heartbeat","session_id":"abc123"}"#;
+        let msg: NotifyMessage = serde_json::from_str(json).unwrap();
+        assert!(matches!(msg, NotifyMessage::Heartbeat { .. }));
+        assert_eq!(msg.session_id(), "abc123");
+        assert_eq!(serde_json::to_string(&msg).unwrap(), json);
+    }
+
     #[test]
     fn test_deserialize_register() {
         let json = r#"{"type":"register","session_id":"test","project_path":"/tmp"}"#;
@@ -123,6 +123,10 @@ fn message_to_event(message: NotifyMessage) -> AppEvent {
             let external_id = claude_external_id(&session_id);
             AppEvent::SessionUnregister { external_id }
         }
+        NotifyMessage::Heartbeat { session_id } => {
+            let external_id = claude_external_id(&session_id);
+            AppEvent::SessionHeartbeat { external_id }
+        }
         NotifyMessage::TabFocus { tab_name } => AppEvent::TabFocusChanged { tab_name },
     }
 }
@@ -1,21 +1,108 @@
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
 use super::centered_rect;
+use crate::app::messages::MessageLevel;
 use crate::app::AppState;
+use crate::logwatch::{todo_progress, TodoState};
 use crate::workspace::{Session, SessionStatus, Workspace};
 
-/// 詳細ビューを描画
-pub fn render(frame: &mut Frame, area: Rect, workspace: &Workspace, state: &AppState) {
+/// 詳細ビューを描画（内容は折り返し、`state.detail_scroll` 行だけスクロール）
+///
+/// スクロールできる最大行数を返す（呼び出し側で `detail_scroll` を丸める）。
+pub fn render(frame: &mut Frame, area: Rect, workspace: &Workspace, state: &AppState) -> u16 {
     let popup_area = centered_rect(70, 60, area);
 
     frame.render_widget(Clear, popup_area);
 
+    let lines = detail_lines(workspace, state);
+
+    // ボーダー分を除いた表示領域に対する最大スクロール量
+    let inner_width = popup_area.width.saturating_sub(2);
+    let inner_height = popup_area.height.saturating_sub(2);
+    let total = u16::try_from(wrapped_line_count(&lines, inner_width)).unwrap_or(u16::MAX);
+    let max_scroll = total.saturating_sub(inner_height);
+    let scroll = state.detail_scroll.min(max_scroll);
+
+    let mut block = Block::default()
+        .title(format!(" {} ", workspace.repo_name))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    if max_scroll > 0 {
+        block = block.title_bottom(
+            Line::from(format!(" j/k:scroll ({}/{}) ", scroll, max_scroll)).centered(),
+        );
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: false })
+        .block(block)
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, popup_area);
+    max_scroll
+}
+
+/// 幅 `width` で単語単位に折り返したときの行数（`Wrap { trim: false }` の表示に合わせる）
+///
+/// 幅に収まらない単語は文字単位で分割される。
+fn wrapped_line_count(lines: &[Line], width: u16) -> usize {
+    let width = usize::from(width.max(1));
+    lines
+        .iter()
+        .map(|line| {
+            let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+            let mut rows = 1;
+            let mut used = 0;
+            for token in text.split_inclusive(' ') {
+                let word = Span::raw(token.trim_end_matches(' ')).width();
+                let spaces = Span::raw(token).width() - word;
+                if used > 0 && used + word > width {
+                    rows += 1;
+                    used = 0;
+                }
+                if word > width {
+                    rows += (word - 1) / width;
+                    used = word - (word - 1) / width * width;
+                } else {
+                    used += word;
+                }
+                used += spaces;
+            }
+            rows
+        })
+        .sum()
+}
+
+/// 詳細サイドパネルを描画（カーソル位置のワークスペースを常時表示）
+pub fn render_panel(frame: &mut Frame, area: Rect, state: &AppState) {
+    let block = Block::default()
+        .title(" Detail ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    let lines = match state.selected_workspace() {
+        Some(ws) => detail_lines(ws, state),
+        None => vec![Line::from(Span::styled(
+            "No workspace selected",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+
+    let panel = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(panel, area);
+}
+
+/// ワークスペースの詳細行（オーバーレイ・サイドパネル共通）
+fn detail_lines<'a>(workspace: &'a Workspace, state: &'a AppState) -> Vec<Line<'a>> {
     // このワークスペースのセッションを取得
     let workspace_index = state
         .workspaces
@@ -26,7 +113,7 @@ pub fn render(frame: &mut Frame, area: Rect, workspace: &Workspace, state: &AppS
             state
                 .sessions_for_workspace(idx)
                 .iter()
-                .filter_map(|&si| state.sessions.get(si))
+                .filter_map(|si| state.sessions.get(si))
                 .collect()
         })
         .unwrap_or_default();
@@ -36,20 +123,28 @@ pub fn render(frame: &mut Frame, area: Rect, workspace: &Workspace, state: &AppS
         .map(|idx| state.workspace_aggregate_status(idx))
         .unwrap_or(SessionStatus::Disconnected);
 
+    let mut branch_spans = vec![
+        Span::styled("Branch:     ", Style::default().fg(Color::Yellow)),
+        Span::styled(&workspace.branch, Style::default().fg(Color::Green)),
+    ];
+    if let Some(op) = workspace.git_operation {
+        branch_spans.push(Span::styled(
+            format!("  ⚠ {}", op.label()),
+            Style::default().fg(state.theme.message_color(MessageLevel::Warn)).add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let mut details = vec![
         Line::from(vec![
             Span::styled("Repository: ", Style::default().fg(Color::Yellow)),
             Span::raw(&workspace.repo_name),
         ]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Branch:     ", Style::default().fg(Color::Yellow)),
-            Span::styled(&workspace.branch, Style::default().fg(Color::Green)),
-        ]),
+        Line::from(branch_spans),
         Line::from(""),
         Line::from(vec![
             Span::styled("Path:       ", Style::default().fg(Color::Yellow)),
-            Span::raw(workspace.display_path()),
+            Span::raw(state.display_path(&workspace.project_path)),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -86,15 +181,27 @@ pub fn render(frame: &mut Frame, area: Rect, workspace: &Workspace, state: &AppS
                     Style::default().fg(session.tool.color()),
                 ),
                 Span::styled(
-                    format!("{} ", session.status.icon()),
-                    Style::default().fg(session.status.color()),
+                    format!("{} ", session.status_icon()),
+                    Style::default().fg(session.status_color()),
                 ),
                 Span::styled(
-                    format!("{:?}", session.status),
-                    Style::default().fg(session.status.color()),
+                    if session.is_api_error() {
+                        "API error".to_string()
+                    } else {
+                        format!("{:?}", session.status)
+                    },
+                    Style::default().fg(session.status_color()),
                 ),
             ]));
 
+            // エラー内容（レート制限・過負荷など）
+            if let Some(ref error) = session.error {
+                details.push(Line::from(vec![
+                    Span::styled("    Error: ", Style::default().fg(Color::DarkGray)),
+                    Span::styled(error, Style::default().fg(session.status_color())),
+                ]));
+            }
+
             // セッションID
             details.push(Line::from(vec![
                 Span::styled("    ID: ", Style::default().fg(Color::DarkGray)),
@@ -109,6 +216,73 @@ pub fn render(frame: &mut Frame, area: Rect, workspace: &Workspace, state: &AppS
                 ]));
             }
 
+            // モデルとコンテキスト消費量
+            if let Some(ref context) = session.context {
+                if let Some(ref model) = context.model {
+                    details.push(Line::from(vec![
+                        Span::styled("    Model: ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(model),
+                    ]));
+                }
+                let tokens = context.context_tokens.or(context.tokens_used);
+                if let Some(tokens) = tokens {
+                    let mut spans = vec![
+                        Span::styled("    Context: ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!("{} tokens", format_tokens(tokens))),
+                    ];
+                    if let Some(output) = context.output_tokens {
+                        spans.push(Span::styled(
+                            format!(" (last output {})", format_tokens(output)),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                    details.push(Line::from(spans));
+                }
+            }
+
+            // 解析で報告された進捗
+            if let Some(progress) = &session.progress {
+                if let Some((done, total)) = progress.counts() {
+                    details.push(Line::from(vec![
+                        Span::styled("    Progress: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(
+                            super::progress_bar(done, total, 20, '█', '░'),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                        Span::raw(format!(" {}/{}", done, total)),
+                    ]));
+                }
+                if let Some(ref step) = progress.current_step {
+                    details.push(Line::from(vec![
+                        Span::styled("    Step: ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(step),
+                    ]));
+                }
+            }
+
+            // Todo リスト（エージェントの計画）
+            if let Some((done, total)) = todo_progress(&session.todos) {
+                details.push(Line::from(Span::styled(
+                    format!("    Todos: {}/{}", done, total),
+                    Style::default().fg(Color::DarkGray),
+                )));
+                for todo in &session.todos {
+                    let (mark, style) = match todo.status {
+                        TodoState::Completed => ("✓", Style::default().fg(Color::Green)),
+                        TodoState::InProgress => ("▶", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                        TodoState::Pending => ("○", Style::default().fg(Color::DarkGray)),
+                    };
+                    let text = match (todo.status, &todo.active_form) {
+                        (TodoState::InProgress, Some(active)) => active,
+                        _ => &todo.content,
+                    };
+                    details.push(Line::from(vec![
+                        Span::styled(format!("      {} ", mark), style),
+                        Span::styled(text.as_str(), style),
+                    ]));
+                }
+            }
+
             // Pane ID (if available)
             if let Some(pane_id) = session.pane_id {
                 details.push(Line::from(vec![
@@ -121,15 +295,65 @@ pub fn render(frame: &mut Frame, area: Rect, workspace: &Workspace, state: &AppS
         }
     }
 
-    let detail = Paragraph::new(details)
-        .block(
-            Block::default()
-                .title(format!(" {} ", workspace.repo_name))
-                .title_alignment(Alignment::Center)
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
-        )
-        .alignment(Alignment::Left);
-
-    frame.render_widget(detail, popup_area);
+    details
+}
+
+/// トークン数を短く表示（1234 → "1.2k"、1234567 → "1.2M"）
+fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::AiTool;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_render_wraps_and_reports_max_scroll() {
+        let mut state = AppState::new();
+        state.workspaces = vec![Workspace::new(
+            "/nonexistent/app".to_string(),
+            "app".to_string(),
+            "main".to_string(),
+        )];
+        let id = state.register_session("claude:s1".to_string(), "/nonexistent/app", AiTool::Claude, None).unwrap();
+        state.sessions.get_mut(&id).unwrap().summary = Some("word ".repeat(200));
+        let workspace = state.workspaces[0].clone();
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 30)).unwrap();
+        let mut max_scroll = 0;
+        terminal
+            .draw(|frame| max_scroll = render(frame, frame.area(), &workspace, &state))
+            .unwrap();
+        // 長い概要は折り返されて表示領域を超える
+        assert!(max_scroll > 0);
+
+        // 範囲外のスクロール量でも描画できる（最大値で表示）
+        state.detail_scroll = u16::MAX;
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &workspace, &state);
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_wrapped_line_count() {
+        let lines = vec![Line::from("aaa bbb ccc"), Line::from(""), Line::from("x".repeat(25))];
+        // "aaa bbb " / "ccc"、空行、8 文字ずつに分割された長い単語
+        assert_eq!(wrapped_line_count(&lines, 8), 2 + 1 + 4);
+        assert_eq!(wrapped_line_count(&lines, 80), 3);
+    }
+
+    #[test]
+    fn test_format_tokens() {
+        assert_eq!(format_tokens(950), "950");
+        assert_eq!(format_tokens(20_104), "20.1k");
+        assert_eq!(format_tokens(1_250_000), "1.2M");
+    }
 }
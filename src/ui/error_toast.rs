@@ -0,0 +1,41 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use super::theme::Theme;
+use crate::app::messages::{MessageLevel, StatusMessage};
+
+/// 確認待ちのエラーを中央に描画
+pub fn render(frame: &mut Frame, area: Rect, toast: &StatusMessage, theme: &Theme) {
+    let popup_area = centered_rect(60, 30, area);
+    frame.render_widget(Clear, popup_area);
+
+    let color = theme.message_color(MessageLevel::Error);
+    let text = vec![
+        Line::from(Span::styled(
+            toast.time.format("%H:%M:%S").to_string(),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            toast.text.clone(),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )),
+    ];
+
+    let view = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Error ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Enter/Esc:dismiss ").centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color)),
+    );
+
+    frame.render_widget(view, popup_area);
+}
@@ -102,7 +102,7 @@ fn render_log_list(frame: &mut Frame, area: Rect, state: &AppState) {
             let rel = relative_time(entry.timestamp);
             let is_selected = state.git_log_selected == Some(i);
             let style = if is_selected {
-                Style::default().bg(Color::DarkGray)
+                Style::default().bg(state.theme.selection.bg.unwrap_or(Color::DarkGray))
             } else {
                 Style::default()
             };
@@ -132,11 +132,7 @@ fn render_log_list(frame: &mut Frame, area: Rect, state: &AppState) {
         String::new()
     };
 
-    let border_color = if state.focused_pane == FocusedPane::GitLog {
-        Color::Cyan
-    } else {
-        Color::DarkGray
-    };
+    let border_color = state.theme.border(state.focused_pane == FocusedPane::GitLog);
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .title(title)
@@ -37,10 +37,26 @@ pub fn render(frame: &mut Frame, area: Rect) {
             Span::styled("  Space", Style::default().fg(Color::Yellow)),
             Span::raw("  Expand/collapse repo group"),
         ]),
+        Line::from(vec![
+            Span::styled("  zM/zR", Style::default().fg(Color::Yellow)),
+            Span::raw("  Collapse/expand all repo groups"),
+        ]),
+        Line::from(vec![
+            Span::styled("  'a-z ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Jump to next repo starting with the letter"),
+        ]),
         Line::from(vec![
             Span::styled("  r    ", Style::default().fg(Color::Yellow)),
             Span::raw("  Refresh workspace list"),
         ]),
+        Line::from(vec![
+            Span::styled("  R    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Refresh selected workspace's status now"),
+        ]),
+        Line::from(vec![
+            Span::styled("  C-r  ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Reload config.toml"),
+        ]),
         Line::from(vec![
             Span::styled("  v    ", Style::default().fg(Color::Yellow)),
             Span::raw("  Toggle view mode (Worktrees/+Branches/Running)"),
@@ -49,6 +65,10 @@ pub fn render(frame: &mut Frame, area: Rect) {
             Span::styled("  /    ", Style::default().fg(Color::Yellow)),
             Span::raw("  Filter branches"),
         ]),
+        Line::from(vec![
+            Span::styled("  s    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Filter tree by repo / branch / path / summary"),
+        ]),
         Line::from(vec![
             Span::styled("  f    ", Style::default().fg(Color::Yellow)),
             Span::raw("  Toggle favorite repo"),
@@ -64,7 +84,7 @@ pub fn render(frame: &mut Frame, area: Rect) {
         ]),
         Line::from(vec![
             Span::styled("  d    ", Style::default().fg(Color::Yellow)),
-            Span::raw("  Delete worktree"),
+            Span::raw("  Delete worktree (then y, or b to also delete the branch with -d; -D when forced)"),
         ]),
         Line::from(vec![
             Span::styled("  D    ", Style::default().fg(Color::Yellow)),
@@ -91,6 +111,14 @@ pub fn render(frame: &mut Frame, area: Rect) {
             Span::styled("  n    ", Style::default().fg(Color::Yellow)),
             Span::raw("  New Claude Code session"),
         ]),
+        Line::from(vec![
+            Span::styled("  N    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Relaunch stopped session (Claude: --resume)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  E    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Export workspace/session snapshot"),
+        ]),
         Line::from(vec![
             Span::styled("  p    ", Style::default().fg(Color::Yellow)),
             Span::raw("  Add pane to current tab"),
@@ -108,10 +136,62 @@ pub fn render(frame: &mut Frame, area: Rect) {
             Span::styled("  e    ", Style::default().fg(Color::Yellow)),
             Span::raw("  Open in editor"),
         ]),
+        Line::from(vec![
+            Span::styled("  w    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Open the session's last file (or README) in editor"),
+        ]),
+        Line::from(vec![
+            Span::styled("  t    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Open in new terminal window"),
+        ]),
+        Line::from(vec![
+            Span::styled("  b    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Open branch on the forge in browser"),
+        ]),
+        Line::from(vec![
+            Span::styled("  P    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Create PR/MR (gh / glab) in a new pane"),
+        ]),
+        Line::from(vec![
+            Span::styled("  V    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Show diff against default branch"),
+        ]),
+        Line::from(vec![
+            Span::styled("  O    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Open log file"),
+        ]),
+        Line::from(vec![
+            Span::styled("  o    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Toggle log viewer"),
+        ]),
+        Line::from(vec![
+            Span::styled("  M    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Message history"),
+        ]),
         Line::from(vec![
             Span::styled("  L    ", Style::default().fg(Color::Yellow)),
             Span::raw("  Toggle git log pane"),
         ]),
+        Line::from(vec![
+            Span::styled("  h    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Hide/show idle sessions"),
+        ]),
+        Line::from(vec![
+            Span::styled("  A    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Acknowledge session (dim until its status changes)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  T    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Filter sessions by tool (All/Claude/Kiro/...)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  i    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Toggle detail side panel"),
+        ]),
+        Line::from(vec![
+            Span::styled("  I    ", Style::default().fg(Color::Yellow)),
+            Span::raw("  Cycle detail panel width"),
+        ]),
         Line::from(vec![
             Span::styled("  Tab  ", Style::default().fg(Color::Yellow)),
             Span::raw("  Switch focus (workspace / git log)"),
@@ -12,10 +12,21 @@ use super::centered_rect;
 pub enum InputDialogKind {
     /// 新規worktree作成（ブランチ名入力）
     CreateWorktree { base_branch: Option<String> },
-    /// worktree削除確認
-    DeleteWorktree { path: String, force: bool },
+    /// worktree削除確認（branch があれば `d` でブランチごと削除できる）
+    DeleteWorktree { path: String, force: bool, branch: Option<String> },
     /// ブランチフィルター
     FilterBranches,
+    /// ツリー全体のフィルター
+    FilterTree,
+    /// 作業中のセッションがあるときの終了確認
+    ConfirmQuit { working: usize },
+}
+
+impl InputDialogKind {
+    /// 文字入力を受け付けず y/n で答える確認ダイアログか
+    pub fn is_confirmation(&self) -> bool {
+        matches!(self, Self::DeleteWorktree { .. } | Self::ConfirmQuit { .. })
+    }
 }
 
 /// 入力ダイアログの状態
@@ -37,9 +48,18 @@ impl InputDialog {
         }
     }
 
-    pub fn new_delete_worktree(path: String, force: bool) -> Self {
+    pub fn new_delete_worktree(path: String, force: bool, branch: Option<String>) -> Self {
+        Self {
+            kind: InputDialogKind::DeleteWorktree { path, force, branch },
+            input: String::new(),
+            cursor_position: 0,
+            error_message: None,
+        }
+    }
+
+    pub fn new_confirm_quit(working: usize) -> Self {
         Self {
-            kind: InputDialogKind::DeleteWorktree { path, force },
+            kind: InputDialogKind::ConfirmQuit { working },
             input: String::new(),
             cursor_position: 0,
             error_message: None,
@@ -57,6 +77,17 @@ impl InputDialog {
         }
     }
 
+    pub fn new_filter_tree(current_filter: Option<String>) -> Self {
+        let input = current_filter.unwrap_or_default();
+        let cursor_position = input.len();
+        Self {
+            kind: InputDialogKind::FilterTree,
+            input,
+            cursor_position,
+            error_message: None,
+        }
+    }
+
     /// 文字を入力
     pub fn insert_char(&mut self, c: char) {
         self.input.insert(self.cursor_position, c);
@@ -115,16 +146,41 @@ pub fn render(frame: &mut Frame, area: Rect, dialog: &InputDialog) {
             },
             "Enter: create | Esc: cancel".to_string(),
         ),
-        InputDialogKind::DeleteWorktree { path, force } => (
+        InputDialogKind::DeleteWorktree { path, force, branch } => (
             if *force { " Force Delete Worktree " } else { " Delete Worktree " }.to_string(),
-            format!("{}Delete {}?", if *force { "[FORCE] " } else { "" }, path),
-            "y: confirm | n/Esc: cancel".to_string(),
+            match branch {
+                Some(branch) => format!(
+                    "{}Delete {}?\nBranch: {}",
+                    if *force { "[FORCE] " } else { "" },
+                    path,
+                    branch
+                ),
+                None => format!("{}Delete {}?", if *force { "[FORCE] " } else { "" }, path),
+            },
+            match branch {
+                Some(branch) => format!("y: worktree only | b: also delete branch '{}' | n/Esc: cancel", branch),
+                None => "y: confirm | n/Esc: cancel".to_string(),
+            },
         ),
         InputDialogKind::FilterBranches => (
             " Filter Branches ".to_string(),
             "Filter:".to_string(),
             "Enter: apply | Esc: clear & close".to_string(),
         ),
+        InputDialogKind::FilterTree => (
+            " Filter Tree ".to_string(),
+            "Repo / branch / path:".to_string(),
+            "Enter: apply | Esc: clear & close".to_string(),
+        ),
+        InputDialogKind::ConfirmQuit { working } => (
+            " Quit ".to_string(),
+            format!(
+                "{} agent{} working — quit anyway?",
+                working,
+                if *working == 1 { "" } else { "s" }
+            ),
+            "y: quit | n/Esc: cancel".to_string(),
+        ),
     };
 
     let inner_area = popup_area.inner(ratatui::layout::Margin {
@@ -0,0 +1,57 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state::log_line_level;
+use crate::app::AppState;
+
+/// ログビューアオーバーレイを描画
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let popup_area = centered_rect(90, 85, area);
+    frame.render_widget(Clear, popup_area);
+
+    let lines = state.filtered_log_lines();
+    // ボーダー分を除いた表示行数
+    let height = popup_area.height.saturating_sub(2) as usize;
+    let end = lines.len().saturating_sub(state.log_scroll);
+    let start = end.saturating_sub(height);
+
+    let text: Vec<Line> = lines[start..end]
+        .iter()
+        .map(|line| {
+            let color = match log_line_level(line) {
+                Some("ERROR") => Color::Red,
+                Some("WARN") => Color::Yellow,
+                Some("INFO") => Color::Green,
+                Some("DEBUG") | Some("TRACE") => Color::DarkGray,
+                _ => Color::Gray,
+            };
+            Line::from(Span::styled(line.to_string(), Style::default().fg(color)))
+        })
+        .collect();
+
+    let follow = if state.log_scroll == 0 { "follow" } else { "paused" };
+    let title = format!(
+        " Logs [{}] {} ({}/{}) ",
+        state.log_level_filter.label(),
+        follow,
+        end,
+        lines.len()
+    );
+
+    let view = Paragraph::new(text).block(
+        Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" j/k:scroll  g/G:top/bottom  f:level  Esc:close ").centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(view, popup_area);
+}
@@ -0,0 +1,52 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::AppState;
+
+/// ステータスメッセージ履歴オーバーレイを描画（新しい順）
+pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
+    let popup_area = centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup_area);
+
+    let text: Vec<Line> = if state.messages.is_empty() {
+        vec![Line::from(Span::styled(
+            "No messages yet",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        state
+            .messages
+            .newest_first()
+            .map(|msg| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", msg.time.format("%H:%M:%S")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        format!("{:<5} ", msg.level.label()),
+                        Style::default().fg(state.theme.message_color(msg.level)),
+                    ),
+                    Span::raw(msg.text.clone()),
+                ])
+            })
+            .collect()
+    };
+
+    let view = Paragraph::new(text).block(
+        Block::default()
+            .title(format!(" Messages ({}) ", state.messages.len()))
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Esc:close ").centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(view, popup_area);
+}
@@ -1,9 +1,13 @@
 pub mod detail_view;
+pub mod error_toast;
 pub mod git_log;
 pub mod help_view;
 pub mod input_dialog;
+pub mod log_view;
+pub mod message_view;
 pub mod selection_dialog;
 pub mod status_bar;
+pub mod theme;
 pub mod workspace_list;
 
 pub use input_dialog::InputDialog;
@@ -16,6 +20,9 @@ use ratatui::{
 
 use crate::app::{AppState, ViewMode};
 
+/// 詳細サイドパネルを横に並べる最小の画面幅（これ未満はオーバーレイ表示）
+const DETAIL_PANE_MIN_WIDTH: u16 = 80;
+
 /// 中央配置用のRect計算（共通ユーティリティ）
 pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let popup_layout = Layout::vertical([
@@ -33,9 +40,37 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     .split(popup_layout[1])[1]
 }
 
+/// `done / total` を `width` 文字の横棒にする（例: "▰▰▰▱▱"）
+pub fn progress_bar(done: usize, total: usize, width: usize, filled: char, empty: char) -> String {
+    let filled_len = (done.min(total) * width + total / 2).checked_div(total).unwrap_or(0);
+    std::iter::repeat_n(filled, filled_len)
+        .chain(std::iter::repeat_n(empty, width - filled_len))
+        .collect()
+}
+
 /// メインUIを描画
 pub fn render(frame: &mut Frame, state: &mut AppState) {
-    let area = frame.area();
+    let [main_area, status_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let area = main_area;
+
+    // 詳細サイドパネル: 右側にカーソル位置の詳細を常時表示
+    // 狭い端末では分割せず、オーバーレイ表示にフォールバック
+    let detail_as_overlay = state.show_detail_pane && area.width < DETAIL_PANE_MIN_WIDTH;
+    let list_area = if state.show_detail_pane && !detail_as_overlay {
+        let percent = state.detail_pane_percent;
+        let [list_area, detail_area] = Layout::horizontal([
+            Constraint::Percentage(100 - percent),
+            Constraint::Percentage(percent),
+        ])
+        .areas(area);
+        state.detail_pane_area = Some(detail_area);
+        detail_view::render_panel(frame, detail_area, state);
+        list_area
+    } else {
+        state.detail_pane_area = None;
+        area
+    };
 
     if state.show_git_log {
         let top_pct = (state.git_log_split_ratio * 100.0) as u16;
@@ -43,7 +78,7 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
             Constraint::Percentage(top_pct),
             Constraint::Percentage(100 - top_pct),
         ])
-        .split(area);
+        .split(list_area);
 
         workspace_list::render(frame, chunks[0], state);
         state.git_log_area = Some(chunks[1]);
@@ -51,9 +86,11 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
     } else {
         // 全面表示
         state.git_log_area = None;
-        workspace_list::render(frame, area, state);
+        workspace_list::render(frame, list_area, state);
     }
 
+    status_bar::render(frame, status_area, state);
+
     // オーバーレイ（全画面に対して表示）
     match &state.view_mode {
         ViewMode::Help => {
@@ -61,7 +98,8 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
         }
         ViewMode::Detail => {
             if let Some(ws) = state.selected_workspace() {
-                detail_view::render(frame, area, ws, state);
+                let max_scroll = detail_view::render(frame, area, ws, state);
+                state.detail_scroll = state.detail_scroll.min(max_scroll);
             }
         }
         ViewMode::Input => {
@@ -74,6 +112,36 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
                 selection_dialog::render(frame, area, dialog);
             }
         }
-        ViewMode::List => {}
+        ViewMode::Logs => {
+            log_view::render(frame, area, state);
+        }
+        ViewMode::Messages => {
+            message_view::render(frame, area, state);
+        }
+        ViewMode::List => {
+            if detail_as_overlay {
+                if let Some(ws) = state.selected_workspace() {
+                    detail_view::render(frame, area, ws, state);
+                }
+            }
+        }
+    }
+
+    // エラートーストは他のオーバーレイより手前に出す
+    if let Some(ref toast) = state.error_toast {
+        error_toast::render(frame, area, toast, &state.theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(2, 4, 4, '#', '-'), "##--");
+        assert_eq!(progress_bar(1, 3, 5, '#', '-'), "##---");
+        assert_eq!(progress_bar(9, 3, 3, '#', '-'), "###");
+        assert_eq!(progress_bar(0, 0, 2, '#', '-'), "--");
     }
 }
@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use super::centered_rect;
+use crate::app::Action;
 
 /// 選択ダイアログの種類
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,6 +16,8 @@ pub enum SelectionDialogKind {
     SelectSession,
     /// レイアウト選択
     SelectLayout,
+    /// 右クリックのコンテキストメニュー（items と同じ順のアクション）
+    ContextMenu(Vec<Action>),
 }
 
 /// 選択ダイアログの状態
@@ -62,6 +65,21 @@ impl SelectionDialog {
         }
     }
 
+    /// コンテキストメニューを作成
+    pub fn new_context_menu(title: &str, entries: Vec<(&str, Action)>) -> Self {
+        let (items, actions): (Vec<String>, Vec<Action>) = entries
+            .into_iter()
+            .map(|(label, action)| (label.to_string(), action))
+            .unzip();
+        Self {
+            kind: SelectionDialogKind::ContextMenu(actions),
+            items,
+            selected_index: 0,
+            title: format!(" {} ", title),
+            context: None,
+        }
+    }
+
     /// 選択を上に移動
     pub fn move_up(&mut self) {
         if !self.items.is_empty() && self.selected_index > 0 {
@@ -80,6 +98,14 @@ impl SelectionDialog {
     pub fn selected_item(&self) -> Option<&str> {
         self.items.get(self.selected_index).map(|s| s.as_str())
     }
+
+    /// コンテキストメニューで選択中のアクションを取得
+    pub fn selected_action(&self) -> Option<Action> {
+        match &self.kind {
+            SelectionDialogKind::ContextMenu(actions) => actions.get(self.selected_index).copied(),
+            _ => None,
+        }
+    }
 }
 
 /// 選択ダイアログを描画
@@ -90,6 +116,7 @@ pub fn render(frame: &mut Frame, area: Rect, dialog: &SelectionDialog) {
     let hint = match dialog.kind {
         SelectionDialogKind::SelectSession => "j/k: move | Enter: select | Esc: cancel",
         SelectionDialogKind::SelectLayout => "j/k: move | Enter: select | Esc: cancel",
+        SelectionDialogKind::ContextMenu(_) => "j/k: move | Enter: run | Esc: cancel",
     };
 
     let inner_area = popup_area.inner(ratatui::layout::Margin {
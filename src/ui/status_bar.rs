@@ -8,24 +8,66 @@ use ratatui::{
 
 use crate::app::AppState;
 
+/// 最終ポーリングがこれより古ければ停止とみなして警告色にする
+const POLL_STALE_SECS: u64 = 60;
+
+/// 現在時刻と最終ポーリングからの経過時間
+fn clock_spans(state: &AppState) -> Vec<Span<'static>> {
+    let now = chrono::Local::now().format("%H:%M:%S").to_string();
+    let poll = match state.last_poll.elapsed() {
+        Some(elapsed) => {
+            let secs = elapsed.as_secs();
+            let color = if secs >= POLL_STALE_SECS { Color::Red } else { Color::DarkGray };
+            Span::styled(format!("poll {}s ago ", secs), Style::default().fg(color))
+        }
+        None => Span::styled("poll -- ", Style::default().fg(Color::DarkGray)),
+    };
+    vec![
+        Span::styled("│ ", Style::default().fg(Color::DarkGray)),
+        poll,
+        Span::styled(format!("{} ", now), Style::default().fg(Color::Gray)),
+    ]
+}
+
+/// 全ワークスペースの集計（件数が0でない入力待ち・エラーは色付け）
+fn summary_spans(state: &AppState) -> Vec<Span<'static>> {
+    let gray = Style::default().fg(Color::Gray);
+    let highlight = |count: usize, color: Color| {
+        if count > 0 {
+            Style::default().fg(color).add_modifier(Modifier::BOLD)
+        } else {
+            gray
+        }
+    };
+    let needs_input = state.needs_input_count();
+    let errors = state.error_count();
+    vec![
+        Span::styled(
+            format!(
+                " {} repos | {} worktrees | {} active | {} working | ",
+                state.repo_count,
+                state.workspaces.len(),
+                state.active_count(),
+                state.working_count()
+            ),
+            gray,
+        ),
+        Span::styled(format!("{} input", needs_input), highlight(needs_input, Color::Yellow)),
+        Span::styled(" | ", gray),
+        Span::styled(format!("{} error ", errors), highlight(errors, Color::Red)),
+    ]
+}
+
 /// ステータスバーを描画
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
-    let active = state.active_count();
-    let working = state.working_count();
-    let total = state.workspaces.len();
     let mode_label = state.list_display_mode.label();
 
-    let left_content = if let Some(ref msg) = state.status_message {
+    let left_content = if let Some(msg) = state.current_status_message() {
         Line::from(vec![
-            Span::styled(msg.clone(), Style::default().fg(Color::Cyan)),
+            Span::styled(msg.text.clone(), Style::default().fg(state.theme.message_color(msg.level))),
         ])
     } else {
-        Line::from(vec![
-            Span::styled(
-                format!(" {} workspaces | {} active | {} working ", total, active, working),
-                Style::default().fg(Color::Gray),
-            ),
-        ])
+        Line::from(summary_spans(state))
     };
 
     // フィルター状態を表示
@@ -41,6 +83,20 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
 
     // 表示モードと'v'キーのヒント、ヘルプヒントを右側に
     let mut right_spans = filter_spans;
+    if let Some(ref filter) = state.tree_filter {
+        right_spans.extend(vec![
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("s:{}", filter), Style::default().fg(Color::Magenta)),
+            Span::styled("] ", Style::default().fg(Color::DarkGray)),
+        ]);
+    }
+    if let Some(tool) = state.tool_filter {
+        right_spans.extend(vec![
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled(tool.name(), Style::default().fg(tool.color())),
+            Span::styled(" only] ", Style::default().fg(Color::DarkGray)),
+        ]);
+    }
     right_spans.extend(vec![
         Span::styled("[", Style::default().fg(Color::DarkGray)),
         Span::styled(mode_label, Style::default().fg(Color::Yellow)),
@@ -48,15 +104,16 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         Span::styled(" v:view /:filter ", Style::default().fg(Color::DarkGray)),
         Span::styled("?:help ", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)),
     ]);
+    right_spans.extend(clock_spans(state));
     let right_content = Line::from(right_spans);
 
+    // 右側の幅は内容に合わせる
+    let right_width = right_content.width() as u16;
+
     // 左右に分けて表示
     let left = Paragraph::new(left_content);
     let right = Paragraph::new(right_content);
 
-    // 右側の幅を計算（フィルターがある場合は広めに）
-    let right_width = if state.branch_filter.is_some() { 50 } else { 40 };
-
     // 左側
     let left_area = Rect {
         x: area.x,
@@ -73,6 +130,15 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         height: area.height,
     };
 
+    // 遷移直後は遷移先ステータスの色で背景を点滅させる
+    let (left, right) = match state.active_status_flash() {
+        Some(status) => {
+            let flash = Style::default().bg(status.color());
+            (left.style(flash), right.style(flash))
+        }
+        None => (left, right),
+    };
+
     frame.render_widget(left, left_area);
     frame.render_widget(right, right_area);
 }
@@ -0,0 +1,137 @@
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::app::config::ThemeConfig;
+use crate::app::messages::MessageLevel;
+
+/// 設定から解決済みの配色
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// 選択行のハイライト
+    pub selection: Style,
+    /// フォーカス中ペインの枠線色
+    pub focus_border: Color,
+    /// マウスカーソル下の行
+    pub hover: Style,
+    /// ステータスメッセージの文字色（通常 / 警告 / エラー）
+    pub message_info: Color,
+    pub message_warn: Color,
+    pub message_error: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_config(&ThemeConfig::default())
+    }
+}
+
+impl Theme {
+    /// 設定値を解決（解釈できない値は警告して既定値を使う）
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let mut selection = Style::default().bg(parse_color(&config.selection_bg, Color::DarkGray));
+        if let Some(fg) = &config.selection_fg {
+            selection = selection.fg(parse_color(fg, Color::White));
+        }
+        for name in &config.selection_modifiers {
+            match parse_modifier(name) {
+                Some(modifier) => selection = selection.add_modifier(modifier),
+                None => tracing::warn!("Unknown theme modifier: {}", name),
+            }
+        }
+
+        Self {
+            selection,
+            focus_border: parse_color(&config.focus_border, Color::Cyan),
+            hover: Style::default().bg(parse_color(&config.hover_bg, Color::Rgb(0x30, 0x30, 0x30))),
+            message_info: parse_color(&config.message_info, Color::Cyan),
+            message_warn: parse_color(&config.message_warn, Color::Yellow),
+            message_error: parse_color(&config.message_error, Color::Red),
+        }
+    }
+
+    /// ステータスメッセージの重要度ごとの文字色
+    pub fn message_color(&self, level: MessageLevel) -> Color {
+        match level {
+            MessageLevel::Info => self.message_info,
+            MessageLevel::Warn => self.message_warn,
+            MessageLevel::Error => self.message_error,
+        }
+    }
+
+    /// 行内の暗色スパン用の文字色（選択行では背景に埋もれないよう明るくする）
+    pub fn dim_fg(&self, is_selected: bool) -> Color {
+        if is_selected {
+            Color::Gray
+        } else {
+            Color::DarkGray
+        }
+    }
+
+    /// ペインの枠線色
+    pub fn border(&self, focused: bool) -> Color {
+        if focused {
+            self.focus_border
+        } else {
+            Color::DarkGray
+        }
+    }
+}
+
+/// 色名（"darkgray", "light-blue"）、"#rrggbb"、256色インデックスを解釈
+fn parse_color(value: &str, fallback: Color) -> Color {
+    match value.trim().parse::<Color>() {
+        Ok(color) => color,
+        Err(_) => {
+            tracing::warn!("Unknown theme color: {}", value);
+            fallback
+        }
+    }
+}
+
+fn parse_modifier(name: &str) -> Option<Modifier> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "bold" => Some(Modifier::BOLD),
+        "dim" => Some(Modifier::DIM),
+        "italic" => Some(Modifier::ITALIC),
+        "underlined" | "underline" => Some(Modifier::UNDERLINED),
+        "reversed" | "reverse" => Some(Modifier::REVERSED),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_theme_matches_previous_look() {
+        let theme = Theme::default();
+        assert_eq!(theme.selection, Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+        assert_eq!(theme.focus_border, Color::Cyan);
+    }
+
+    #[test]
+    fn test_from_config_parses_colors_and_modifiers() {
+        let config = ThemeConfig {
+            selection_bg: "#203040".to_string(),
+            selection_fg: Some("yellow".to_string()),
+            selection_modifiers: vec!["underlined".to_string(), "bogus".to_string()],
+            focus_border: "not-a-color".to_string(),
+            hover_bg: "238".to_string(),
+            message_info: "green".to_string(),
+            message_warn: "yellow".to_string(),
+            message_error: "bogus".to_string(),
+        };
+        let theme = Theme::from_config(&config);
+        assert_eq!(
+            theme.selection,
+            Style::default()
+                .bg(Color::Rgb(0x20, 0x30, 0x40))
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::UNDERLINED)
+        );
+        assert_eq!(theme.focus_border, Color::Cyan);
+        assert_eq!(theme.hover, Style::default().bg(Color::Indexed(238)));
+        assert_eq!(theme.message_color(MessageLevel::Info), Color::Green);
+        assert_eq!(theme.message_color(MessageLevel::Error), Color::Red);
+    }
+}
@@ -6,8 +6,14 @@ use ratatui::{
     Frame,
 };
 
+use chrono::{DateTime, Utc};
+
+use crate::app::messages::MessageLevel;
 use crate::app::{AppState, FocusedPane, ListDisplayMode, TreeItem};
 
+/// この日数より古いブランチは放置されているとみなして強調する
+const STALE_BRANCH_DAYS: i64 = 30;
+
 /// ワークスペース一覧をツリー形式で描画
 pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
     // RunningOnly モードで表示するワークスペースがない場合のメッセージ
@@ -38,16 +44,21 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
         .tree_items
         .iter()
         .enumerate()
-        .map(|(idx, item)| create_tree_row(item, state, idx == state.selected_index))
+        .map(|(idx, item)| {
+            let is_selected = idx == state.selected_index;
+            let row = create_tree_row(item, state, is_selected);
+            // ホバー行は選択行と区別できる背景にする（選択行は選択ハイライトを優先）
+            if !is_selected && state.hover_index == Some(idx) {
+                row.style(state.theme.hover)
+            } else {
+                row
+            }
+        })
         .collect();
 
     let widths = [Constraint::Min(10)];
 
-    let border_color = if state.focused_pane == FocusedPane::WorkspaceList {
-        Color::Cyan
-    } else {
-        Color::DarkGray
-    };
+    let border_color = state.theme.border(state.focused_pane == FocusedPane::WorkspaceList);
     let table = Table::new(rows, widths)
         .block(
             Block::default()
@@ -55,33 +66,68 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color)),
         )
-        .row_highlight_style(
-            Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
-        );
+        .row_highlight_style(state.theme.selection);
 
     frame.render_stateful_widget(table, area, &mut state.table_state);
 }
 
+/// クリック位置がセッション行のステータスアイコン上かどうか
+///
+/// `column` はリスト領域左端からの列。行の内容は左枠線の1列後ろから始まり、
+/// `create_tree_row` のセッション行と同じく「罫線 → ツールアイコン → ステータスアイコン」の順に並ぶ。
+pub fn is_session_status_icon(state: &AppState, index: usize, column: u16) -> bool {
+    let Some(TreeItem::Session { session_id, .. }) = state.tree_items.get(index) else {
+        return false;
+    };
+    let Some(session) = state.sessions.get(session_id) else {
+        return false;
+    };
+
+    let branch_width = Span::raw("├ ").width();
+    let tool_width = Span::raw(format!("{} ", session.tool.icon(state.use_nerd_font))).width();
+    let start = 1 + branch_width + tool_width;
+    let end = start + Span::raw(session.status_icon()).width();
+    (start..end).contains(&(column as usize))
+}
+
+/// 直近のコミット数からヒートグリフと色を決定（0件は非表示）
+fn activity_heat(commits: usize) -> Option<(&'static str, Color)> {
+    match commits {
+        0 => None,
+        1..=2 => Some(("▁", Color::DarkGray)),
+        3..=5 => Some(("▃", Color::Yellow)),
+        6..=10 => Some(("▅", Color::LightRed)),
+        _ => Some(("▇", Color::Red)),
+    }
+}
+
 fn create_tree_row(item: &TreeItem, state: &AppState, is_selected: bool) -> Row<'static> {
     match item {
         TreeItem::RepoGroup {
             name,
             path,
+            expanded,
+            host,
             ..
         } => {
-            // リポジトリグループ行
+            // リポジトリグループ行（先頭の ▼/▶ はクリックでも展開/折りたたみ可能）
             let name_style = Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD);
             let is_favorite = state.favorite_repos.contains(path);
+            let expand_icon = if *expanded { "▼ " } else { "▶ " };
 
-            let mut spans = Vec::new();
+            let mut spans = vec![Span::styled(expand_icon, Style::default().fg(Color::DarkGray))];
             if is_favorite {
                 spans.push(Span::styled("★ ", Style::default().fg(Color::Yellow)));
             }
             spans.push(Span::styled(name.clone(), name_style));
+            if let Some(host) = host.as_ref().filter(|_| state.show_remote_host) {
+                spans.push(Span::styled(
+                    format!(" [{}]", host),
+                    Style::default().fg(state.theme.dim_fg(is_selected)),
+                ));
+            }
 
             Row::new(vec![Line::from(spans)]).height(1)
         }
@@ -105,10 +151,25 @@ fn create_tree_row(item: &TreeItem, state: &AppState, is_selected: bool) -> Row<
                     (false, false) => Style::default(),
                 };
 
-                let spans = vec![
+                let mut spans = vec![
                     Span::styled(" ", Style::default()),
                     Span::styled(format!("({})", ws.branch), name_style),
                 ];
+                if ws.transient {
+                    spans.push(Span::styled(
+                        " (unscanned)",
+                        Style::default().fg(state.theme.dim_fg(is_selected)),
+                    ));
+                }
+                if let Some(op) = ws.git_operation {
+                    spans.push(Span::styled(
+                        format!(" ⚠ {}", op.label()),
+                        Style::default().fg(state.theme.message_color(MessageLevel::Warn)).add_modifier(Modifier::BOLD),
+                    ));
+                }
+                if let Some((glyph, color)) = activity_heat(ws.recent_commits) {
+                    spans.push(Span::styled(format!(" {}", glyph), Style::default().fg(color)));
+                }
 
                 Row::new(vec![Line::from(spans)]).height(1)
             } else {
@@ -116,19 +177,24 @@ fn create_tree_row(item: &TreeItem, state: &AppState, is_selected: bool) -> Row<
             }
         }
         TreeItem::Session {
-            session_index,
+            session_id,
             is_last,
             ..
         } => {
             // セッション行: ツールアイコンとステータスを表示
-            if let Some(session) = state.sessions.get(*session_index) {
+            if let Some(session) = state.sessions.get(session_id) {
                 let branch_char = if *is_last { "└ " } else { "├ " };
 
                 // ツールアイコンとステータス
                 let tool_icon = session.tool.icon(state.use_nerd_font);
                 let tool_color = session.tool.color();
-                let status_color = session.status.color();
-                let status_icon = session.status.icon();
+                // 確認済みのセッションは注意表示を控えめにする（API エラーはツールのエラーと別表示）
+                let status_color = if session.acknowledged {
+                    Color::DarkGray
+                } else {
+                    session.status_color()
+                };
+                let status_icon = session.status_icon();
 
                 // セッション情報
                 let info = session.display_info();
@@ -139,12 +205,19 @@ fn create_tree_row(item: &TreeItem, state: &AppState, is_selected: bool) -> Row<
                     Style::default()
                 };
 
-                let spans = vec![
+                let mut spans = vec![
                     Span::styled(branch_char, Style::default().fg(Color::DarkGray)),
                     Span::styled(format!("{} ", tool_icon), Style::default().fg(tool_color)),
                     Span::styled(format!("{} ", status_icon), Style::default().fg(status_color)),
-                    Span::styled(info, name_style.fg(Color::DarkGray)),
+                    Span::styled(info, name_style.fg(state.theme.dim_fg(is_selected))),
                 ];
+                // 解析で進捗が分かっていれば小さな棒と件数
+                if let Some((done, total)) = session.progress.as_ref().and_then(|p| p.counts()) {
+                    spans.push(Span::styled(
+                        format!(" {} {}/{}", super::progress_bar(done, total, 5, '▰', '▱'), done, total),
+                        Style::default().fg(Color::Cyan),
+                    ));
+                }
 
                 Row::new(vec![Line::from(spans)]).height(1)
             } else {
@@ -171,6 +244,10 @@ fn create_tree_row(item: &TreeItem, state: &AppState, is_selected: bool) -> Row<
         TreeItem::Branch {
             name,
             is_local,
+            ahead,
+            behind,
+            last_commit,
+            worktree_path,
             ..
         } => {
             // ブランチ行（worktree未作成）- 控えめな暗い色で表示
@@ -184,20 +261,57 @@ fn create_tree_row(item: &TreeItem, state: &AppState, is_selected: bool) -> Row<
                 format!("origin/{}", name)
             };
 
-            let name_style = if is_selected {
+            let mut name_style = if is_selected {
                 Style::default()
-                    .fg(Color::DarkGray)
+                    .fg(state.theme.dim_fg(true))
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default().fg(Color::DarkGray)
             };
+            // 別の場所の worktree でチェックアウト中のブランチはさらに薄く表示
+            if worktree_path.is_some() {
+                name_style = name_style.add_modifier(Modifier::DIM | Modifier::ITALIC);
+            }
 
-            Row::new(vec![Line::from(vec![
+            let mut spans = vec![
                 Span::styled(indent, Style::default()),
                 Span::styled("  ", Style::default()), // アイコン分のスペース
                 Span::styled(display_name, name_style),
-            ])])
-            .height(1)
+            ];
+
+            // デフォルトブランチとの差分: 先行コミットがなければマージ済み
+            match (ahead, behind) {
+                (Some(0), Some(behind)) if *behind > 0 => {
+                    spans.push(Span::styled(" merged", Style::default().fg(Color::Green)));
+                }
+                (Some(ahead), Some(behind)) if *ahead > 0 || *behind > 0 => {
+                    spans.push(Span::styled(
+                        format!(" ↑{} ↓{}", ahead, behind),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                _ => {}
+            }
+            if let Some(time) = last_commit {
+                let now = Utc::now();
+                let age_color = if (now - *time).num_days() >= STALE_BRANCH_DAYS {
+                    Color::Yellow
+                } else {
+                    Color::DarkGray
+                };
+                spans.push(Span::styled(
+                    format!(" {}", short_age(*time, now)),
+                    Style::default().fg(age_color),
+                ));
+            }
+            if let Some(path) = worktree_path {
+                spans.push(Span::styled(
+                    format!(" (worktree: {})", state.display_path(path)),
+                    Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+                ));
+            }
+
+            Row::new(vec![Line::from(spans)]).height(1)
         }
         TreeItem::Pane {
             pane_index,
@@ -260,3 +374,51 @@ fn create_tree_row(item: &TreeItem, state: &AppState, is_selected: bool) -> Row<
         }
     }
 }
+
+/// 経過時間を短い表記にする（例: "5m", "3h", "12d", "4mo", "2y"）
+fn short_age(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - time).num_seconds().max(0);
+    match secs {
+        0..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        86400..=2591999 => format!("{}d", secs / 86400),
+        2592000..=31535999 => format!("{}mo", secs / 2592000),
+        _ => format!("{}y", secs / 31536000),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{AiTool, Session};
+
+    #[test]
+    fn test_is_session_status_icon() {
+        let mut state = AppState::new();
+        state.use_nerd_font = false;
+        let id = state.add_session(Session::new("kiro:/work:c1".to_string(), 0, AiTool::Kiro));
+        state.tree_items = vec![TreeItem::Session {
+            session_id: id,
+            is_last: true,
+            parent_is_last: true,
+        }];
+
+        // 枠線(1) + "└ "(2) + "[K] "(4) の直後がステータスアイコン
+        assert!(!is_session_status_icon(&state, 0, 6));
+        assert!(is_session_status_icon(&state, 0, 7));
+        assert!(!is_session_status_icon(&state, 0, 8));
+        // 存在しない行
+        assert!(!is_session_status_icon(&state, 1, 7));
+    }
+
+    #[test]
+    fn test_short_age() {
+        let now = Utc::now();
+        assert_eq!(short_age(now, now), "0m");
+        assert_eq!(short_age(now - chrono::Duration::hours(5), now), "5h");
+        assert_eq!(short_age(now - chrono::Duration::days(12), now), "12d");
+        assert_eq!(short_age(now - chrono::Duration::days(400), now), "1y");
+        // 未来の日時（時計のずれ）は 0 扱い
+        assert_eq!(short_age(now + chrono::Duration::hours(1), now), "0m");
+    }
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use crate::app::config::parse_git_url;
+
+/// リモートのホスティングサービス
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+}
+
+impl Forge {
+    /// ホスト名から推定（"gitlab" を含めば GitLab、それ以外は GitHub 互換とみなす）
+    pub fn from_host(host: &str) -> Self {
+        if host.contains("gitlab") {
+            Forge::GitLab
+        } else {
+            Forge::GitHub
+        }
+    }
+
+    /// ブランチページのURL（ブランチ名は `/` 区切りのまま各部分をパーセントエンコード）
+    pub fn branch_url(&self, host: &str, owner: &str, repo: &str, branch: &str) -> String {
+        let branch = branch.split('/').map(percent_encode).collect::<Vec<_>>().join("/");
+        match self {
+            Forge::GitHub => format!("https://{}/{}/{}/tree/{}", host, owner, repo, branch),
+            Forge::GitLab => format!("https://{}/{}/{}/-/tree/{}", host, owner, repo, branch),
+        }
+    }
+
+    /// PR / MR を作成するCLIコマンド
+    pub fn create_pr_command(&self) -> &'static [&'static str] {
+        match self {
+            Forge::GitHub => &["gh", "pr", "create"],
+            Forge::GitLab => &["glab", "mr", "create"],
+        }
+    }
+}
+
+/// リモートURLからフォージと (host, owner, repo) を解決
+pub fn resolve_remote(remote_url: &str) -> Option<(Forge, String, String, String)> {
+    let (host, owner, repo) = parse_git_url(remote_url)?;
+    Some((Forge::from_host(&host), host, owner, repo))
+}
+
+/// URL のパスの一部としてパーセントエンコード（英数字と `-._~` 以外）
+fn percent_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// コマンドが PATH 上にあるか
+pub fn command_available(program: &str) -> bool {
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&paths).any(|dir| Path::new(&dir).join(program).is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_remote() {
+        let (forge, host, owner, repo) = resolve_remote("git@gitlab.com:team/app.git").unwrap();
+        assert_eq!(forge, Forge::GitLab);
+        assert_eq!(forge.branch_url(&host, &owner, &repo, "main"), "https://gitlab.com/team/app/-/tree/main");
+        assert_eq!(forge.create_pr_command(), &["glab", "mr", "create"]);
+
+        let (forge, ..) = resolve_remote("https://github.com/owner/repo.git").unwrap();
+        assert_eq!(forge, Forge::GitHub);
+        assert!(resolve_remote("/local/path").is_none());
+    }
+
+    #[test]
+    fn test_branch_url_encodes_segments() {
+        assert_eq!(
+            Forge::GitHub.branch_url("github.com", "owner", "repo", "feature/fix #1+ü"),
+            "https://github.com/owner/repo/tree/feature/fix%20%231%2B%C3%BC"
+        );
+    }
+}
@@ -1,9 +1,41 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use git2::{Repository, BranchType};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::app::config::WorktreeConfig;
+use crate::app::terminal::expand_quoted;
+
+/// ブランチの鮮度情報（デフォルトブランチとの差分と最終コミット日時）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchStats {
+    /// デフォルトブランチより先行しているコミット数
+    pub ahead: Option<usize>,
+    /// デフォルトブランチより遅れているコミット数
+    pub behind: Option<usize>,
+    /// ブランチ先端のコミット日時
+    pub last_commit: Option<DateTime<Utc>>,
+}
+
+/// 作成しようとしたブランチが既に別の worktree にチェックアウトされている
+///
+/// `create_worktree` のエラーから `downcast_ref` で取り出せる。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCheckedOut {
+    pub branch: String,
+    /// チェックアウトしている worktree のパス
+    pub path: PathBuf,
+}
+
+impl std::fmt::Display for BranchCheckedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Branch '{}' is already checked out at {}", self.branch, self.path.display())
+    }
+}
+
+impl std::error::Error for BranchCheckedOut {}
 
 /// Worktree管理
 pub struct WorktreeManager {
@@ -31,6 +63,11 @@ impl WorktreeManager {
         let repo = Repository::open(repo_path)
             .context("Failed to open repository")?;
 
+        // 既にどこかでチェックアウトされているブランチは git worktree add が失敗するので先に検出
+        if let Some(path) = self.find_worktree_for_branch(repo_path, branch_name) {
+            return Err(BranchCheckedOut { branch: branch_name.to_string(), path }.into());
+        }
+
         // リモートURLを取得
         let remote_url = repo
             .find_remote(&self.config.default_remote)
@@ -79,6 +116,18 @@ impl WorktreeManager {
         }
 
         info!("Created worktree at: {}", worktree_path.display());
+
+        // gitignoreされた設定ファイル等をコピー（失敗してもworktree作成自体は成功扱い）
+        if !self.config.copy_into_worktree.is_empty() {
+            match copy_matching_files(repo_path, &worktree_path, &self.config.copy_into_worktree) {
+                Ok(copied) if copied > 0 => {
+                    info!("Copied {} file(s) into worktree", copied);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to copy files into worktree: {}", e),
+            }
+        }
+
         Ok(worktree_path)
     }
 
@@ -142,6 +191,40 @@ impl WorktreeManager {
         Ok(())
     }
 
+    /// worktree作成後フックのコマンドを解決（{path}/{branch}をシェルクォートして置換済み）
+    pub fn post_create_command(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch_name: &str,
+    ) -> Option<String> {
+        self.config.post_create_command_for(repo_path).map(|template| {
+            expand_quoted(
+                template,
+                &[("{path}", &worktree_path.to_string_lossy()), ("{branch}", branch_name)],
+            )
+        })
+    }
+
+    /// post_create_command を worktree ディレクトリで実行（完了まで待機）
+    pub fn run_post_create_command(command: &str, worktree_path: &Path) -> Result<()> {
+        let output = std::process::Command::new("sh")
+            .args(["-c", command])
+            .current_dir(worktree_path)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .context("Failed to execute post_create_command")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let last_line = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("");
+            anyhow::bail!("exit {}: {}", output.status.code().unwrap_or(-1), last_line.trim());
+        }
+
+        info!("post_create_command finished: {}", worktree_path.display());
+        Ok(())
+    }
+
     /// worktreeを削除
     pub fn remove_worktree(&self, repo_path: &Path, worktree_path: &Path, force: bool) -> Result<()> {
         let mut cmd = std::process::Command::new("git");
@@ -165,8 +248,81 @@ impl WorktreeManager {
         Ok(())
     }
 
+    /// worktreeを削除し、そのブランチも削除
+    ///
+    /// ブランチは通常 `git branch -d`（未マージなら失敗）、`force` のときだけ `git branch -D` で消す。
+    /// デフォルトブランチやメインの worktree でチェックアウト中のブランチは削除しない
+    /// （worktree も消さずにエラーを返す）。
+    pub fn remove_worktree_and_branch(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        force: bool,
+    ) -> Result<()> {
+        // `git worktree list` は常にメインの worktree を最初に出力する
+        let main_branch = self
+            .list_worktrees(repo_path)?
+            .into_iter()
+            .next()
+            .and_then(|wt| wt.branch);
+        if main_branch.as_deref() == Some(branch) {
+            anyhow::bail!("Refusing to delete branch '{}': it is checked out in the main worktree", branch);
+        }
+        let default_branch = self.default_branch(repo_path).map(|name| {
+            let remote_prefix = format!("{}/", self.config.default_remote);
+            name.strip_prefix(&remote_prefix).map(str::to_string).unwrap_or(name)
+        });
+        if default_branch.as_deref() == Some(branch) {
+            anyhow::bail!("Refusing to delete the default branch '{}'", branch);
+        }
+
+        self.remove_worktree(repo_path, worktree_path, force)?;
+
+        let delete_flag = if force { "-D" } else { "-d" };
+        let output = std::process::Command::new("git")
+            .current_dir(repo_path)
+            .args(["branch", delete_flag, branch])
+            .output()
+            .with_context(|| format!("Failed to execute git branch {}", delete_flag))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Worktree removed, but git branch {} failed: {}", delete_flag, stderr);
+        }
+
+        info!("Deleted branch: {}", branch);
+        Ok(())
+    }
+
+    /// リモートから fetch（`git fetch --prune <remote>`）
+    ///
+    /// ネットワークを待つため UI スレッドからは別スレッドで呼ぶ。
+    /// 認証情報やパスフレーズの入力待ちで TUI が止まらないよう、プロンプトは無効にして
+    /// 認証エラーとして返す。
+    pub fn fetch_remote(repo_path: &Path, remote: &str) -> Result<()> {
+        let ssh_command = match std::env::var("GIT_SSH_COMMAND") {
+            Ok(command) if !command.trim().is_empty() => format!("{} -oBatchMode=yes", command),
+            _ => "ssh -oBatchMode=yes".to_string(),
+        };
+        let output = std::process::Command::new("git")
+            .current_dir(repo_path)
+            .args(["fetch", "--prune", remote])
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("GIT_SSH_COMMAND", ssh_command)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .context("Failed to execute git fetch")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git fetch failed: {}", stderr.trim());
+        }
+
+        info!("Fetched {} in {}", remote, repo_path.display());
+        Ok(())
+    }
+
     /// リポジトリのworktree一覧を取得
-    #[allow(dead_code)]
     pub fn list_worktrees(&self, repo_path: &Path) -> Result<Vec<WorktreeListInfo>> {
         let output = std::process::Command::new("git")
             .current_dir(repo_path)
@@ -219,8 +375,24 @@ impl WorktreeManager {
         Ok(worktrees)
     }
 
-    /// リモートブランチ一覧を取得
-    pub fn list_remote_branches(&self, repo_path: &Path) -> Result<Vec<String>> {
+    /// ブランチをチェックアウトしている worktree（メインのリポジトリを含む）のパス
+    pub fn find_worktree_for_branch(&self, repo_path: &Path, branch: &str) -> Option<PathBuf> {
+        self.checked_out_branches(repo_path).remove(branch)
+    }
+
+    /// チェックアウト中のブランチ -> worktree のパス（`git worktree list --porcelain` から）
+    pub fn checked_out_branches(&self, repo_path: &Path) -> HashMap<String, PathBuf> {
+        self.list_worktrees(repo_path)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|wt| Some((wt.branch?, wt.path)))
+            .collect()
+    }
+
+    /// リモートブランチ一覧を (リモート名, ブランチ名) で取得
+    ///
+    /// 同名のブランチが複数のリモートにある場合は `default_remote` のものを優先して1つにまとめる。
+    pub fn list_remote_branches(&self, repo_path: &Path) -> Result<Vec<(String, String)>> {
         let repo = Repository::open(repo_path)?;
         let mut branches = Vec::new();
 
@@ -229,23 +401,46 @@ impl WorktreeManager {
             if let Some(name) = branch.name()? {
                 // origin/HEAD などを除外
                 if !name.ends_with("/HEAD") {
-                    // origin/ プレフィックスを除去（スラッシュを含むブランチ名に対応）
-                    // "origin/claude/feature" -> "claude/feature"
-                    if let Some(idx) = name.find('/') {
-                        let short_name = &name[idx + 1..];
+                    // リモート名とブランチ名に分ける（スラッシュを含むブランチ名に対応）
+                    // "origin/claude/feature" -> ("origin", "claude/feature")
+                    if let Some((remote, short_name)) = name.split_once('/') {
                         if !short_name.is_empty() {
-                            branches.push(short_name.to_string());
+                            branches.push((remote.to_string(), short_name.to_string()));
                         }
                     }
                 }
             }
         }
 
-        branches.sort();
-        branches.dedup();
+        let default_remote = self.config.default_remote.as_str();
+        branches.sort_by(|(ra, a), (rb, b)| {
+            a.cmp(b)
+                .then_with(|| (ra != default_remote).cmp(&(rb != default_remote)))
+                .then_with(|| ra.cmp(rb))
+        });
+        branches.dedup_by(|(_, a), (_, b)| a == b);
         Ok(branches)
     }
 
+    /// デフォルトブランチを取得（`<remote>/HEAD` の参照先、なければ main / master）
+    pub fn default_branch(&self, repo_path: &Path) -> Option<String> {
+        let repo = Repository::open(repo_path).ok()?;
+
+        let remote_head = format!("refs/remotes/{}/HEAD", self.config.default_remote);
+        if let Some(target) = repo
+            .find_reference(&remote_head)
+            .ok()
+            .and_then(|r| r.symbolic_target().map(|t| t.to_string()))
+        {
+            return target.strip_prefix("refs/remotes/").map(|s| s.to_string());
+        }
+
+        ["main", "master"]
+            .into_iter()
+            .find(|name| repo.find_branch(name, BranchType::Local).is_ok())
+            .map(|name| name.to_string())
+    }
+
     /// ローカルブランチ一覧を取得
     pub fn list_local_branches(&self, repo_path: &Path) -> Result<Vec<String>> {
         let repo = Repository::open(repo_path)?;
@@ -261,6 +456,129 @@ impl WorktreeManager {
         branches.sort();
         Ok(branches)
     }
+
+    /// ブランチのデフォルトブランチに対する ahead/behind と最終コミット日時を取得
+    ///
+    /// リモートブランチは `<remote>/<branch>` の形で渡す。取得できない値は None。
+    pub fn branch_stats(&self, repo_path: &Path, branch: &str, is_local: bool) -> BranchStats {
+        let Ok(repo) = Repository::open(repo_path) else {
+            return BranchStats::default();
+        };
+        let branch_type = if is_local { BranchType::Local } else { BranchType::Remote };
+        let found = repo.find_branch(branch, branch_type);
+        let Some(commit) = found.ok().and_then(|b| b.get().peel_to_commit().ok()) else {
+            return BranchStats::default();
+        };
+
+        let last_commit = DateTime::from_timestamp(commit.time().seconds(), 0);
+        let base = self
+            .default_branch(repo_path)
+            .and_then(|name| repo.revparse_single(&name).ok())
+            .and_then(|obj| obj.peel_to_commit().ok());
+        let (ahead, behind) = match base {
+            Some(base) => match repo.graph_ahead_behind(commit.id(), base.id()) {
+                Ok((ahead, behind)) => (Some(ahead), Some(behind)),
+                Err(_) => (None, None),
+            },
+            None => (None, None),
+        };
+        BranchStats { ahead, behind, last_commit }
+    }
+}
+
+/// `patterns` に一致するファイル/ディレクトリを `src_root` から `dest_root` にコピー
+///
+/// パターンは `/` 区切りの相対パスで、各要素に `*` / `?` を使える。
+/// コピー先に既に存在するもの（追跡ファイル等）は上書きしない。コピーした数を返す。
+fn copy_matching_files(src_root: &Path, dest_root: &Path, patterns: &[String]) -> Result<usize> {
+    let mut copied = 0;
+    for pattern in patterns {
+        let components: Vec<&str> = pattern.split('/').filter(|c| !c.is_empty()).collect();
+        for rel in expand_glob(src_root, &components)? {
+            let dest = dest_root.join(&rel);
+            if dest.exists() {
+                continue;
+            }
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            copy_recursive(&src_root.join(&rel), &dest)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// globパターン要素を展開し、一致した相対パスを返す
+fn expand_glob(root: &Path, components: &[&str]) -> Result<Vec<PathBuf>> {
+    let mut matches = vec![PathBuf::new()];
+    for component in components {
+        let mut next = Vec::new();
+        for rel in &matches {
+            let dir = root.join(rel);
+            if !component.contains(['*', '?']) {
+                if dir.join(component).exists() {
+                    next.push(rel.join(component));
+                }
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                // .git は対象外
+                if name != ".git" && glob_match(component, &name) {
+                    next.push(rel.join(name.as_ref()));
+                }
+            }
+        }
+        matches = next;
+    }
+    matches.retain(|p| !p.as_os_str().is_empty());
+    matches.sort();
+    Ok(matches)
+}
+
+/// `*`（任意の文字列）と `?`（任意の1文字）のみをサポートする簡易globマッチ
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    let (mut pi, mut ni) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// ファイルまたはディレクトリを再帰的にコピー
+fn copy_recursive(src: &Path, dest: &Path) -> Result<()> {
+    if src.is_dir() {
+        std::fs::create_dir_all(dest)?;
+        for entry in std::fs::read_dir(src)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+    } else {
+        std::fs::copy(src, dest)
+            .with_context(|| format!("Failed to copy {}", src.display()))?;
+    }
+    Ok(())
 }
 
 /// Worktree一覧情報（list_worktrees用）
@@ -279,3 +597,144 @@ impl Default for WorktreeManager {
         Self::new(WorktreeConfig::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(".env*", ".env"));
+        assert!(glob_match(".env*", ".env.local"));
+        assert!(glob_match("*.yml", "local.yml"));
+        assert!(glob_match("?.txt", "a.txt"));
+        assert!(!glob_match(".env*", "env"));
+        assert!(!glob_match("*.yml", "local.yaml"));
+    }
+
+    #[test]
+    fn test_copy_matching_files() {
+        let src = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join(".env"), "A=1").unwrap();
+        std::fs::write(src.path().join(".env.local"), "B=2").unwrap();
+        std::fs::write(src.path().join("README.md"), "readme").unwrap();
+        std::fs::create_dir(src.path().join("config")).unwrap();
+        std::fs::write(src.path().join("config/local.yml"), "x: 1").unwrap();
+        // 既存ファイルは上書きしない
+        std::fs::write(dest.path().join(".env.local"), "keep").unwrap();
+
+        let patterns = vec![".env*".to_string(), "config/*.yml".to_string()];
+        let copied = copy_matching_files(src.path(), dest.path(), &patterns).unwrap();
+
+        assert_eq!(copied, 2);
+        assert_eq!(std::fs::read_to_string(dest.path().join(".env")).unwrap(), "A=1");
+        assert_eq!(std::fs::read_to_string(dest.path().join(".env.local")).unwrap(), "keep");
+        assert!(dest.path().join("config/local.yml").exists());
+        assert!(!dest.path().join("README.md").exists());
+    }
+
+    #[test]
+    fn test_post_create_command_quotes_values() {
+        let config = WorktreeConfig {
+            post_create_command: Some("cd {path} && echo {branch}".to_string()),
+            ..WorktreeConfig::default()
+        };
+        let manager = WorktreeManager::new(config);
+        let command = manager.post_create_command(Path::new("/src/app"), Path::new("/src/app wt"), "feat;x");
+        assert_eq!(command.as_deref(), Some("cd '/src/app wt' && echo 'feat;x'"));
+    }
+
+    #[test]
+    fn test_branch_stats_against_default_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::new("test", "test@example.com", &git2::Time::new(1_700_000_000, 0)).unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit = |refname: &str, parent: &git2::Commit, msg: &str| {
+            let oid = repo.commit(Some(refname), &sig, &sig, msg, &tree, &[parent]).unwrap();
+            repo.find_commit(oid).unwrap()
+        };
+
+        let root = repo.commit(None, &sig, &sig, "root", &tree, &[]).unwrap();
+        let root = repo.find_commit(root).unwrap();
+        repo.branch("main", &root, true).unwrap();
+        repo.branch("feature", &root, true).unwrap();
+        repo.branch("merged", &root, true).unwrap();
+        let feature = commit("refs/heads/feature", &root, "feature work");
+        commit("refs/heads/main", &root, "main 1");
+
+        let manager = WorktreeManager::default();
+        let stats = manager.branch_stats(dir.path(), "feature", true);
+        assert_eq!((stats.ahead, stats.behind), (Some(1), Some(1)));
+        assert_eq!(stats.last_commit.map(|t| t.timestamp()), Some(1_700_000_000));
+
+        let stats = manager.branch_stats(dir.path(), "merged", true);
+        assert_eq!((stats.ahead, stats.behind), (Some(0), Some(1)));
+
+        assert_eq!(manager.branch_stats(dir.path(), "missing", true), BranchStats::default());
+
+        // origin 以外のリモートのブランチもリモート名付きで参照する
+        repo.reference("refs/remotes/upstream/feature", feature.id(), true, "test").unwrap();
+        repo.reference("refs/remotes/upstream/only-upstream", root.id(), true, "test").unwrap();
+        repo.reference("refs/remotes/origin/feature", root.id(), true, "test").unwrap();
+        assert_eq!(
+            manager.list_remote_branches(dir.path()).unwrap(),
+            vec![
+                ("origin".to_string(), "feature".to_string()),
+                ("upstream".to_string(), "only-upstream".to_string()),
+            ]
+        );
+        let stats = manager.branch_stats(dir.path(), "upstream/feature", false);
+        assert_eq!((stats.ahead, stats.behind), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn test_create_worktree_for_checked_out_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_path = dir.path().join("repo");
+        let repo = Repository::init(&repo_path).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("refs/heads/main"), &sig, &sig, "root", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+
+        let manager = WorktreeManager::default();
+        let err = manager.create_worktree(&repo_path, "main", false, None).unwrap_err();
+        let existing = err.downcast_ref::<BranchCheckedOut>().expect("BranchCheckedOut");
+        assert_eq!(existing.branch, "main");
+        assert_eq!(existing.path.canonicalize().unwrap(), repo_path.canonicalize().unwrap());
+        assert_eq!(manager.find_worktree_for_branch(&repo_path, "other"), None);
+    }
+
+    #[test]
+    fn test_remove_worktree_and_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_path = dir.path().join("repo");
+        let repo = Repository::init(&repo_path).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("refs/heads/main"), &sig, &sig, "root", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+
+        let manager = WorktreeManager::default();
+        let worktree_path = manager.create_worktree(&repo_path, "feature", true, None).unwrap();
+        assert_eq!(
+            manager.find_worktree_for_branch(&repo_path, "feature").map(|p| p.canonicalize().unwrap()),
+            Some(worktree_path.canonicalize().unwrap())
+        );
+
+        // メインの worktree のブランチは拒否し、worktree も残す
+        let err = manager
+            .remove_worktree_and_branch(&repo_path, &repo_path, "main", false)
+            .unwrap_err();
+        assert!(err.to_string().contains("main worktree"));
+
+        manager
+            .remove_worktree_and_branch(&repo_path, &worktree_path, "feature", false)
+            .unwrap();
+        assert!(!worktree_path.exists());
+        assert!(repo.find_branch("feature", BranchType::Local).is_err());
+    }
+}
@@ -1,14 +1,19 @@
+pub mod forge;
 pub mod manager;
 pub mod pane;
 pub mod session;
 pub mod state;
 pub mod worktree;
 
-pub use manager::WorktreeManager;
+pub use forge::Forge;
+pub use manager::{BranchCheckedOut, BranchStats, WorktreeManager};
 pub use pane::{AiSessionInfo, Pane};
 pub use session::{
-    AiTool, Session, SessionId, SessionStatus, claude_external_id, kiro_external_id,
-    parse_external_id,
+    AiTool, Session, SessionId, SessionStatus, claude_external_id, codex_external_id, kiro_external_id,
+    opencode_external_id, parse_external_id, session_key, tmux_external_id,
+};
+pub use state::{abbreviate_home, GitOperation, Workspace};
+pub use worktree::{
+    count_recent_commits, detect_worktrees, get_default_search_paths, scan_for_repositories,
+    workspace_for_path, worktree_root, WorktreeInfo,
 };
-pub use state::Workspace;
-pub use worktree::{detect_worktrees, get_default_search_paths, scan_for_repositories, WorktreeInfo};
@@ -7,8 +7,10 @@ use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 use uuid::Uuid;
 
+use crate::logwatch::process::normalize_path;
+
 /// Unique identifier for a session
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SessionId(Uuid);
 
 impl SessionId {
@@ -53,9 +55,14 @@ pub enum AiTool {
     OpenCode,
     /// Codex (OpenAI)
     Codex,
+    /// Any other CLI, known only from a captured tmux window
+    Other,
 }
 
 impl AiTool {
+    /// All supported tools, in display order (`Other` is not a tool that can be tracked or filtered)
+    pub const ALL: [AiTool; 4] = [AiTool::Claude, AiTool::Kiro, AiTool::OpenCode, AiTool::Codex];
+
     /// Parse from string
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
@@ -63,6 +70,7 @@ impl AiTool {
             "kiro" => AiTool::Kiro,
             "opencode" => AiTool::OpenCode,
             "codex" => AiTool::Codex,
+            "other" => AiTool::Other,
             _ => AiTool::Claude,
         }
     }
@@ -74,6 +82,18 @@ impl AiTool {
             AiTool::Kiro => "Kiro",
             AiTool::OpenCode => "OpenCode",
             AiTool::Codex => "Codex",
+            AiTool::Other => "Other",
+        }
+    }
+
+    /// Command that starts the tool (a plain shell for unknown CLIs)
+    pub fn command(&self) -> &'static str {
+        match self {
+            AiTool::Claude => "claude",
+            AiTool::Kiro => "kiro-cli",
+            AiTool::OpenCode => "opencode",
+            AiTool::Codex => "codex",
+            AiTool::Other => "sh",
         }
     }
 
@@ -90,6 +110,7 @@ impl AiTool {
             }
             AiTool::OpenCode => "[O]",
             AiTool::Codex => "[X]",
+            AiTool::Other => "[?]",
         }
     }
 
@@ -101,6 +122,7 @@ impl AiTool {
             AiTool::Kiro => Color::Rgb(153, 102, 204),   // Purple for Kiro
             AiTool::OpenCode => Color::Cyan,
             AiTool::Codex => Color::Green,
+            AiTool::Other => Color::Gray,
         }
     }
 }
@@ -201,7 +223,7 @@ pub struct Session {
     /// Detailed state information
     #[serde(default)]
     pub state_detail: Option<String>,
-    /// Brief summary of current work (max 50 chars)
+    /// Brief summary of current work (capped at `summary_max_len`)
     #[serde(default)]
     pub summary: Option<String>,
     /// Current task description
@@ -220,6 +242,30 @@ pub struct Session {
     pub created_at: SystemTime,
     /// Last update time
     pub updated_at: SystemTime,
+    /// Last time the tool reported in (register / status update)
+    #[serde(default = "SystemTime::now")]
+    pub last_heartbeat: SystemTime,
+    /// The user has seen the current NeedsInput / Error state (cleared on the next status change)
+    #[serde(default)]
+    pub acknowledged: bool,
+    /// File the most recent tool call worked on (from the tool input, if known)
+    #[serde(default)]
+    pub last_file: Option<String>,
+    /// The agent's todo list (Claude Code's TodoWrite)
+    #[serde(default)]
+    pub todos: Vec<crate::logwatch::TodoItem>,
+    /// Step progress reported by the status analysis
+    #[serde(default)]
+    pub progress: Option<crate::logwatch::AnalysisProgress>,
+    /// Model and token usage reported by the status analysis
+    #[serde(default)]
+    pub context: Option<crate::logwatch::AnalysisContext>,
+    /// Error message of the current Error state (cleared once the session recovers)
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Error reports received in a row that are not shown yet (see [`Session::confirm_error`])
+    #[serde(skip)]
+    pub pending_error_reports: u32,
 }
 
 impl Session {
@@ -240,39 +286,71 @@ impl Session {
             tab_name: None,
             created_at: now,
             updated_at: now,
+            last_heartbeat: now,
+            acknowledged: false,
+            last_file: None,
+            todos: Vec::new(),
+            progress: None,
+            context: None,
+            error: None,
+            pending_error_reports: 0,
         }
     }
 
     /// Update session status
     pub fn update_status(&mut self, status: SessionStatus, message: Option<String>) {
-        self.status = status;
+        // A detail from an earlier analysis (e.g. "API error") no longer describes the new status
+        if status != self.status {
+            self.state_detail = None;
+        }
+        self.set_status(status);
         if message.is_some() {
             self.summary = message;
         }
         self.updated_at = SystemTime::now();
         self.last_activity = Some(SystemTime::now());
+        self.heartbeat();
     }
 
     /// Update from SessionStatus (logwatch schema)
-    pub fn update_from_logwatch_status(&mut self, status: &crate::logwatch::SessionStatus) {
-        // Update summary (truncate to 50 chars)
-        self.summary = status.display_summary();
-
-        // Update current task
-        self.current_task = status.current_task.clone();
+    pub fn update_from_logwatch_status(
+        &mut self,
+        status: &crate::logwatch::SessionStatus,
+        limits: crate::logwatch::TextLimits,
+    ) {
+        // Update summary and current task (truncated to the configured lengths)
+        self.summary = status.display_summary(limits.summary);
+        self.current_task = status.display_task(limits.task);
 
         // Update state detail label
         self.state_detail = Some(status.state_detail.label().to_string());
 
+        // Keep the last known file, context and todo list when this update doesn't mention them
+        if let Some(file) = status.context.as_ref().and_then(|c| c.last_file.clone()) {
+            self.last_file = Some(file);
+        }
+        if status.context.is_some() {
+            self.context = status.context.clone();
+        }
+        if let Some(todos) = &status.todos {
+            self.todos = todos.clone();
+        }
+        self.progress = status.progress.clone();
+        // Only an Error state carries an error; any other report means the session recovered
+        self.error = match status.status {
+            crate::logwatch::StatusState::Error => status.error.clone(),
+            _ => None,
+        };
+
         // Convert StatusState to SessionStatus
-        self.status = match status.status {
+        self.set_status(match status.status {
             crate::logwatch::StatusState::Working => SessionStatus::Working,
             crate::logwatch::StatusState::Waiting => SessionStatus::NeedsInput,
             crate::logwatch::StatusState::Completed => SessionStatus::Success,
             crate::logwatch::StatusState::Error => SessionStatus::Error,
             crate::logwatch::StatusState::Idle => SessionStatus::Idle,
             crate::logwatch::StatusState::Disconnected => SessionStatus::Disconnected,
-        };
+        });
 
         // Update timestamps
         if let Some(activity) = status.last_activity {
@@ -286,6 +364,71 @@ impl Session {
         }
 
         self.updated_at = SystemTime::now();
+        self.heartbeat();
+    }
+
+    /// Count a polled status report, returning whether it should be applied
+    ///
+    /// An Error report is held back until `required` of them arrive in a row, so a
+    /// one-off blip (e.g. a single rate limit) never shows. Any other report resets
+    /// the count, and a session already in Error keeps taking updates.
+    pub fn confirm_error(&mut self, is_error: bool, required: u32) -> bool {
+        if !is_error {
+            self.pending_error_reports = 0;
+            return true;
+        }
+        self.pending_error_reports = self.pending_error_reports.saturating_add(1);
+        self.status == SessionStatus::Error || self.pending_error_reports >= required.max(1)
+    }
+
+    /// Change the status, clearing the acknowledgement when it actually changes
+    pub fn set_status(&mut self, status: SessionStatus) {
+        if self.status != status {
+            self.acknowledged = false;
+        }
+        if status != SessionStatus::Error {
+            self.error = None;
+        }
+        self.status = status;
+    }
+
+    /// Whether the session is in an Error state caused by the model API (rate limit, overload)
+    pub fn is_api_error(&self) -> bool {
+        self.status == SessionStatus::Error
+            && self.state_detail.as_deref() == Some(crate::logwatch::StatusDetail::ApiError.label())
+    }
+
+    /// Status icon, with API errors told apart from tool errors
+    pub fn status_icon(&self) -> &'static str {
+        if self.is_api_error() {
+            "⚠"
+        } else {
+            self.status.icon()
+        }
+    }
+
+    /// Status color, with API errors told apart from tool errors
+    pub fn status_color(&self) -> ratatui::style::Color {
+        if self.is_api_error() {
+            ratatui::style::Color::Magenta
+        } else {
+            self.status.color()
+        }
+    }
+
+    /// Record that the tool is still alive
+    pub fn heartbeat(&mut self) {
+        self.last_heartbeat = SystemTime::now();
+    }
+
+    /// Active session with no heartbeat for longer than `timeout`
+    pub fn is_stale(&self, timeout: std::time::Duration) -> bool {
+        self.is_active()
+            && self
+                .last_heartbeat
+                .elapsed()
+                .map(|elapsed| elapsed > timeout)
+                .unwrap_or(false)
     }
 
     /// Get time since last activity as human-readable string
@@ -308,7 +451,7 @@ impl Session {
 
     /// Mark session as disconnected
     pub fn disconnect(&mut self) {
-        self.status = SessionStatus::Disconnected;
+        self.set_status(SessionStatus::Disconnected);
         self.updated_at = SystemTime::now();
     }
 
@@ -317,6 +460,35 @@ impl Session {
         self.status != SessionStatus::Disconnected
     }
 
+    /// Whether the tool has stopped and can be relaunched (unknown CLIs can't be)
+    pub fn can_relaunch(&self) -> bool {
+        self.tool != AiTool::Other && matches!(self.status, SessionStatus::Disconnected | SessionStatus::Error)
+    }
+
+    /// Command that relaunches the tool (Claude resumes the same session when its ID is known)
+    ///
+    /// `ai_command` is the configured AI command; it is used (flags included) when it
+    /// runs this session's tool, otherwise the tool's plain command is.
+    pub fn relaunch_command(&self, ai_command: &str) -> Vec<String> {
+        let configured: Vec<String> = ai_command.split_whitespace().map(str::to_string).collect();
+        let runs_tool = configured
+            .first()
+            .and_then(|program| std::path::Path::new(program).file_name())
+            .is_some_and(|name| name == self.tool.command());
+        let mut command = if runs_tool {
+            configured
+        } else {
+            vec![self.tool.command().to_string()]
+        };
+        if self.tool == AiTool::Claude {
+            let (_, session_id) = parse_external_id(&self.external_id);
+            if !session_id.is_empty() {
+                command.extend(["--resume".to_string(), session_id.to_string()]);
+            }
+        }
+        command
+    }
+
     /// Get display summary with state detail
     pub fn display_info(&self) -> String {
         let mut parts = Vec::new();
@@ -329,6 +501,10 @@ impl Session {
             parts.push(summary.clone());
         }
 
+        if let Some((done, total)) = crate::logwatch::todo_progress(&self.todos) {
+            parts.push(format!("{}/{} todos", done, total));
+        }
+
         if let Some(time) = self.time_since_activity() {
             parts.push(format!("({})", time));
         }
@@ -342,14 +518,32 @@ pub fn claude_external_id(session_id: &str) -> String {
     format!("claude:{}", session_id)
 }
 
+/// Generate external session ID for OpenCode
+pub fn opencode_external_id(session_id: &str) -> String {
+    format!("opencode:{}", session_id)
+}
+
+/// Generate external session ID for Codex
+pub fn codex_external_id(session_id: &str) -> String {
+    format!("codex:{}", session_id)
+}
+
+/// Generate external session ID for a captured tmux window
+pub fn tmux_external_id(session: &str, window_index: &str) -> String {
+    format!("tmux:{}:{}", session, window_index)
+}
+
 /// Generate external session ID for Kiro (with conversation ID)
+///
+/// The project path is normalized (`~` expanded) so that the same workspace
+/// always yields the same ID, however the path was spelled.
 pub fn kiro_external_id(project_path: &str, conversation_id: &str) -> String {
-    format!("kiro:{}:{}", project_path, conversation_id)
+    format!("kiro:{}:{}", normalize_path(project_path), conversation_id)
 }
 
 /// Generate external session ID for Kiro (legacy - without conversation ID)
 pub fn kiro_external_id_legacy(project_path: &str) -> String {
-    format!("kiro:{}", project_path)
+    format!("kiro:{}", normalize_path(project_path))
 }
 
 /// Parse external session ID to get tool and original ID
@@ -363,12 +557,27 @@ pub fn parse_external_id(external_id: &str) -> (AiTool, &str) {
         (AiTool::OpenCode, id)
     } else if let Some(id) = external_id.strip_prefix("codex:") {
         (AiTool::Codex, id)
+    } else if let Some(id) = external_id.strip_prefix("tmux:") {
+        (AiTool::Other, id)
     } else {
         // Default to Claude for legacy compatibility
         (AiTool::Claude, external_id)
     }
 }
 
+/// Key identifying the underlying agent session of an external ID
+///
+/// IDs that differ only in case, surrounding whitespace or (for Kiro) the
+/// spelling of the project path map to the same key.
+pub fn session_key(external_id: &str) -> (AiTool, String) {
+    let (tool, raw_id) = parse_external_id(external_id);
+    let id = match tool {
+        AiTool::Kiro => parse_kiro_external_id(external_id).map_or(raw_id, |(_, conv)| conv),
+        _ => raw_id,
+    };
+    (tool, id.trim().to_ascii_lowercase())
+}
+
 /// Parse Kiro external ID to get project path and conversation ID
 /// Format: kiro:{project_path}:{conversation_id}
 pub fn parse_kiro_external_id(external_id: &str) -> Option<(&str, &str)> {
@@ -399,6 +608,35 @@ mod tests {
         assert_eq!(AiTool::from_str("unknown"), AiTool::Claude);
     }
 
+    #[test]
+    fn test_relaunch_command() {
+        let claude = Session::new(claude_external_id("abc-123"), 0, AiTool::Claude);
+        assert_eq!(claude.relaunch_command("claude"), vec!["claude", "--resume", "abc-123"]);
+        assert_eq!(
+            claude.relaunch_command("/opt/bin/claude --model opus"),
+            vec!["/opt/bin/claude", "--model", "opus", "--resume", "abc-123"]
+        );
+
+        let unknown = Session::new("claude:".to_string(), 0, AiTool::Claude);
+        assert_eq!(unknown.relaunch_command("claude"), vec!["claude"]);
+
+        // The configured command runs another tool: fall back to the session's own
+        let kiro = Session::new(kiro_external_id("/work/repo", "conv"), 0, AiTool::Kiro);
+        assert_eq!(kiro.relaunch_command("claude --model opus"), vec!["kiro-cli"]);
+        assert_eq!(kiro.relaunch_command("kiro-cli chat"), vec!["kiro-cli", "chat"]);
+    }
+
+    #[test]
+    fn test_other_tool_cannot_relaunch() {
+        let mut other = Session::new("tmux:main:2".to_string(), 0, AiTool::Other);
+        other.disconnect();
+        assert!(!other.can_relaunch());
+
+        let mut claude = Session::new(claude_external_id("abc-123"), 0, AiTool::Claude);
+        claude.disconnect();
+        assert!(claude.can_relaunch());
+    }
+
     #[test]
     fn test_session_status_parsing() {
         assert_eq!(SessionStatus::from_str("working"), SessionStatus::Working);
@@ -411,6 +649,16 @@ mod tests {
         assert_eq!(claude_external_id("abc-123"), "claude:abc-123");
         assert_eq!(kiro_external_id("/path/to/project", "conv-123"), "kiro:/path/to/project:conv-123");
         assert_eq!(kiro_external_id_legacy("/path/to/project"), "kiro:/path/to/project");
+        assert_eq!(opencode_external_id("ses_1"), "opencode:ses_1");
+        assert_eq!(codex_external_id("0199-abc"), "codex:0199-abc");
+
+        // ~ is expanded so both spellings of a workspace share one ID
+        if let Ok(home) = std::env::var("HOME") {
+            assert_eq!(
+                kiro_external_id("~/project", "conv-123"),
+                kiro_external_id(&format!("{}/project", home), "conv-123")
+            );
+        }
     }
 
     #[test]
@@ -424,12 +672,23 @@ mod tests {
         assert_eq!(tool, AiTool::Kiro);
         assert_eq!(id, "/path/to/project:conv-123");
 
+        assert_eq!(parse_external_id("opencode:s1").0, AiTool::OpenCode);
+        assert_eq!(parse_external_id("codex:s1").0, AiTool::Codex);
+        assert_eq!(parse_external_id("tmux:main:2"), (AiTool::Other, "main:2"));
+
         // Parse kiro external id to get project path and conversation id
         let (project_path, conv_id) = parse_kiro_external_id("kiro:/path/to/project:conv-123").unwrap();
         assert_eq!(project_path, "/path/to/project");
         assert_eq!(conv_id, "conv-123");
     }
 
+    #[test]
+    fn test_session_key_ignores_id_spelling() {
+        assert_eq!(session_key("claude:ABC-123"), session_key("claude:abc-123 "));
+        assert_eq!(session_key("kiro:~/repo:conv-1"), (AiTool::Kiro, "conv-1".to_string()));
+        assert_ne!(session_key("claude:abc"), session_key("codex:abc"));
+    }
+
     #[test]
     fn test_session_creation() {
         let session = Session::new("claude:test-123".to_string(), 0, AiTool::Claude);
@@ -440,6 +699,23 @@ mod tests {
         assert!(session.is_active());
     }
 
+    #[test]
+    fn test_session_stale_after_heartbeat_timeout() {
+        let mut session = Session::new("claude:test-123".to_string(), 0, AiTool::Claude);
+        session.last_heartbeat = SystemTime::now() - std::time::Duration::from_secs(120);
+        assert!(session.is_stale(std::time::Duration::from_secs(60)));
+        assert!(!session.is_stale(std::time::Duration::from_secs(300)));
+
+        // A status update refreshes the heartbeat
+        session.update_status(SessionStatus::Working, None);
+        assert!(!session.is_stale(std::time::Duration::from_secs(60)));
+
+        // Disconnected sessions are never reported as stale
+        session.last_heartbeat = SystemTime::now() - std::time::Duration::from_secs(120);
+        session.disconnect();
+        assert!(!session.is_stale(std::time::Duration::from_secs(60)));
+    }
+
     #[test]
     fn test_session_disconnect() {
         let mut session = Session::new("claude:test-123".to_string(), 0, AiTool::Claude);
@@ -449,4 +725,110 @@ mod tests {
         assert!(!session.is_active());
         assert_eq!(session.status, SessionStatus::Disconnected);
     }
+
+    #[test]
+    fn test_acknowledgement_cleared_on_status_change() {
+        let mut session = Session::new(claude_external_id("abc-123"), 0, AiTool::Claude);
+        session.update_status(SessionStatus::NeedsInput, None);
+        session.acknowledged = true;
+        // Re-reporting the same status keeps the acknowledgement
+        session.update_status(SessionStatus::NeedsInput, None);
+        assert!(session.acknowledged);
+
+        session.update_status(SessionStatus::Working, None);
+        session.update_status(SessionStatus::NeedsInput, None);
+        assert!(!session.acknowledged);
+    }
+
+    #[test]
+    fn test_error_needs_consecutive_reports() {
+        let mut session = Session::new(claude_external_id("abc-123"), 0, AiTool::Claude);
+        session.update_status(SessionStatus::Working, None);
+
+        // A single blip between successful reports is never applied
+        assert!(!session.confirm_error(true, 2));
+        assert!(session.confirm_error(false, 2));
+        assert!(!session.confirm_error(true, 2));
+
+        // The second error in a row is
+        assert!(session.confirm_error(true, 2));
+        session.update_status(SessionStatus::Error, None);
+        assert!(session.confirm_error(true, 2));
+
+        // 0 / 1 show errors right away
+        let mut session = Session::new(claude_external_id("def-456"), 0, AiTool::Claude);
+        assert!(session.confirm_error(true, 0));
+        assert!(session.confirm_error(true, 1));
+    }
+
+    #[test]
+    fn test_api_error_clears_on_recovery() {
+        use crate::logwatch::{StatusDetail, StatusState, TextLimits};
+
+        let mut session = Session::new(claude_external_id("abc-123"), 0, AiTool::Claude);
+        let api_error = crate::logwatch::SessionStatus {
+            status: StatusState::Error,
+            state_detail: StatusDetail::ApiError,
+            error: Some("529 overloaded".to_string()),
+            ..Default::default()
+        };
+        session.update_from_logwatch_status(&api_error, TextLimits::default());
+        assert!(session.is_api_error());
+        assert_eq!(session.error.as_deref(), Some("529 overloaded"));
+        assert_eq!(session.status_icon(), "⚠");
+
+        // Tool errors keep the regular error styling
+        let tool_error = crate::logwatch::SessionStatus {
+            status: StatusState::Error,
+            state_detail: StatusDetail::ToolError,
+            ..Default::default()
+        };
+        session.update_from_logwatch_status(&tool_error, TextLimits::default());
+        assert!(!session.is_api_error());
+        assert_eq!(session.status_icon(), SessionStatus::Error.icon());
+
+        // The next successful report clears the error
+        session.update_from_logwatch_status(&api_error, TextLimits::default());
+        let working = crate::logwatch::SessionStatus {
+            status: StatusState::Working,
+            state_detail: StatusDetail::Thinking,
+            ..Default::default()
+        };
+        session.update_from_logwatch_status(&working, TextLimits::default());
+        assert_eq!(session.status, SessionStatus::Working);
+        assert!(session.error.is_none());
+
+        // Also when the recovery arrives through a hook
+        session.update_from_logwatch_status(&api_error, TextLimits::default());
+        session.update_status(SessionStatus::Working, None);
+        assert!(!session.is_api_error());
+        assert!(session.error.is_none());
+        assert!(session.state_detail.is_none());
+    }
+
+    #[test]
+    fn test_todos_kept_until_cleared() {
+        use crate::logwatch::{TextLimits, TodoItem};
+
+        let mut session = Session::new(claude_external_id("abc-123"), 0, AiTool::Claude);
+        let with_todos = |todos: Option<Vec<TodoItem>>| crate::logwatch::SessionStatus {
+            todos,
+            ..Default::default()
+        };
+        let item = TodoItem {
+            content: "Write tests".to_string(),
+            status: Default::default(),
+            active_form: None,
+        };
+        session.update_from_logwatch_status(&with_todos(Some(vec![item])), TextLimits::default());
+        assert_eq!(session.todos.len(), 1);
+
+        // An update that didn't see a todo list keeps the last one
+        session.update_from_logwatch_status(&with_todos(None), TextLimits::default());
+        assert_eq!(session.todos.len(), 1);
+
+        // An empty list clears it
+        session.update_from_logwatch_status(&with_todos(Some(Vec::new())), TextLimits::default());
+        assert!(session.todos.is_empty());
+    }
 }
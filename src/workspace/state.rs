@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::forge::{resolve_remote, Forge};
+
 /// ワークスペース情報（Git worktree単位）
 ///
 /// Note: セッション情報（status, session_id等）は Session 構造体に移動しました。
@@ -17,6 +19,61 @@ pub struct Workspace {
     pub branch: String,
     /// 最終更新時刻
     pub updated_at: std::time::SystemTime,
+    /// 直近N日間のコミット数（スキャン時に計算）
+    #[serde(default)]
+    pub recent_commits: usize,
+    /// origin のURL（スキャン時に取得）
+    #[serde(default)]
+    pub remote_url: Option<String>,
+    /// origin のホスト名（github.com など）
+    #[serde(default)]
+    pub remote_host: Option<String>,
+    /// 進行中の git 操作（rebase / merge など、スキャン時に取得）
+    #[serde(default)]
+    pub git_operation: Option<GitOperation>,
+    /// スキャン対象外で、セッション登録時に追加された一時ワークスペース
+    #[serde(default)]
+    pub transient: bool,
+}
+
+/// worktree で進行中の git 操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitOperation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+    Bisect,
+    ApplyMailbox,
+}
+
+impl GitOperation {
+    /// リポジトリの状態から進行中の操作を判定（通常状態なら None）
+    pub fn from_repo_state(state: git2::RepositoryState) -> Option<Self> {
+        use git2::RepositoryState as S;
+        match state {
+            S::Clean => None,
+            S::Merge => Some(Self::Merge),
+            S::Rebase | S::RebaseInteractive | S::RebaseMerge => Some(Self::Rebase),
+            S::CherryPick | S::CherryPickSequence => Some(Self::CherryPick),
+            S::Revert | S::RevertSequence => Some(Self::Revert),
+            S::Bisect => Some(Self::Bisect),
+            S::ApplyMailbox | S::ApplyMailboxOrRebase => Some(Self::ApplyMailbox),
+        }
+    }
+
+    /// 一覧に出すラベル
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Merge => "MERGING",
+            Self::Rebase => "REBASING",
+            Self::CherryPick => "CHERRY-PICKING",
+            Self::Revert => "REVERTING",
+            Self::Bisect => "BISECTING",
+            Self::ApplyMailbox => "APPLYING",
+        }
+    }
 }
 
 impl Workspace {
@@ -28,18 +85,76 @@ impl Workspace {
             repo_name,
             branch,
             updated_at: std::time::SystemTime::now(),
+            recent_commits: 0,
+            remote_url: None,
+            remote_host: None,
+            git_operation: None,
+            transient: false,
         }
     }
 
+    /// origin のフォージ（GitHub / GitLab）
+    pub fn forge(&self) -> Option<Forge> {
+        resolve_remote(self.remote_url.as_deref()?).map(|(forge, ..)| forge)
+    }
+
+    /// 現在のブランチをフォージで表示するURL
+    pub fn branch_web_url(&self) -> Option<String> {
+        let (forge, host, owner, repo) = resolve_remote(self.remote_url.as_deref()?)?;
+        Some(forge.branch_url(&host, &owner, &repo, &self.branch))
+    }
+
     /// 表示用の短縮パスを返す
     pub fn display_path(&self) -> String {
-        if let Some(base_dirs) = directories::BaseDirs::new() {
-            let home = base_dirs.home_dir();
-            if let Some(stripped) = self.project_path.strip_prefix(home.to_string_lossy().as_ref())
-            {
-                return format!("~{}", stripped);
-            }
-        }
-        self.project_path.clone()
+        abbreviate_home(&self.project_path)
+    }
+}
+
+/// 表示用にホームディレクトリを `~` に短縮したパスを返す
+pub fn abbreviate_home(path: &str) -> String {
+    match directories::BaseDirs::new() {
+        Some(base_dirs) => abbreviate_home_with(path, &base_dirs.home_dir().to_string_lossy()),
+        None => path.to_string(),
+    }
+}
+
+/// `home` 直下のときだけ短縮する（`/home/al` が `/home/alice` に一致しないよう区切りを確認）
+fn abbreviate_home_with(path: &str, home: &str) -> String {
+    let home = home.trim_end_matches('/');
+    if home.is_empty() {
+        return path.to_string();
+    }
+    match path.strip_prefix(home) {
+        Some("") => "~".to_string(),
+        Some(rest) if rest.starts_with('/') => format!("~{}", rest),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abbreviate_home_only_at_path_boundary() {
+        assert_eq!(abbreviate_home_with("/home/alice/src/app", "/home/alice"), "~/src/app");
+        assert_eq!(abbreviate_home_with("/home/alice", "/home/alice/"), "~");
+        assert_eq!(abbreviate_home_with("/home/alice2/src", "/home/alice"), "/home/alice2/src");
+        assert_eq!(abbreviate_home_with("/srv/app", "/home/alice"), "/srv/app");
+    }
+
+    #[test]
+    fn test_branch_web_url() {
+        let mut ws = Workspace::new("/work/repo".to_string(), "repo".to_string(), "feat/x".to_string());
+        assert_eq!(ws.branch_web_url(), None);
+
+        ws.remote_url = Some("git@github.com:owner/repo.git".to_string());
+        assert_eq!(ws.branch_web_url().as_deref(), Some("https://github.com/owner/repo/tree/feat/x"));
+
+        ws.remote_url = Some("https://gitlab.example.com/team/repo.git".to_string());
+        assert_eq!(
+            ws.branch_web_url().as_deref(),
+            Some("https://gitlab.example.com/team/repo/-/tree/feat/x")
+        );
     }
 }
@@ -3,7 +3,8 @@ use git2::Repository;
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
-use super::state::Workspace;
+use super::state::{GitOperation, Workspace};
+use crate::app::config::parse_git_url;
 
 /// Git worktreeの情報
 #[derive(Debug, Clone)]
@@ -16,6 +17,10 @@ pub struct WorktreeInfo {
     pub branch: String,
     /// メインworktreeかどうか
     pub is_main: bool,
+    /// origin のURL
+    pub remote_url: Option<String>,
+    /// 進行中の git 操作
+    pub operation: Option<GitOperation>,
 }
 
 /// 指定ディレクトリからgit worktreeを検出
@@ -98,14 +103,43 @@ fn extract_worktree_info(path: &Path) -> Option<WorktreeInfo> {
     // ブランチ名を取得
     let branch = get_current_branch(&repo).unwrap_or_else(|| "detached".to_string());
 
+    let remote_url = repo
+        .find_remote("origin")
+        .ok()
+        .and_then(|r| r.url().map(|s| s.to_string()));
+
     Some(WorktreeInfo {
         path: path.to_path_buf(),
         repo_name,
         branch,
         is_main: false,
+        remote_url,
+        operation: GitOperation::from_repo_state(repo.state()),
     })
 }
 
+/// 任意のパスからワークスペースを作る（git 管理外ならディレクトリ名を使う）
+pub fn workspace_for_path(path: &Path) -> Workspace {
+    match extract_worktree_info(path) {
+        Some(info) => info.into(),
+        None => {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string());
+            Workspace::new(path.to_string_lossy().to_string(), name, "-".to_string())
+        }
+    }
+}
+
+/// `path` を含む git worktree のルート（git 管理外なら None）
+pub fn worktree_root(path: &Path) -> Option<PathBuf> {
+    let repo = Repository::discover(path).ok()?;
+    let workdir = repo.workdir()?;
+    // discover は末尾に / の付いたパスを返すので正規化して揃える
+    Some(std::fs::canonicalize(workdir).unwrap_or_else(|_| workdir.to_path_buf()))
+}
+
 /// 現在のブランチ名を取得
 fn get_current_branch(repo: &Repository) -> Option<String> {
     let head = repo.head().ok()?;
@@ -118,14 +152,46 @@ fn get_current_branch(repo: &Repository) -> Option<String> {
     }
 }
 
+/// HEADから辿って直近 `days` 日以内のコミット数を数える（取得できない場合は0）
+pub fn count_recent_commits(path: &Path, days: u32) -> usize {
+    if days == 0 {
+        return 0;
+    }
+    let Ok(repo) = Repository::open(path) else {
+        return 0;
+    };
+    let Ok(mut revwalk) = repo.revwalk() else {
+        return 0;
+    };
+    if revwalk.push_head().is_err() || revwalk.set_sorting(git2::Sort::TIME).is_err() {
+        return 0;
+    }
+
+    let cutoff = chrono::Utc::now().timestamp() - i64::from(days) * 24 * 60 * 60;
+    revwalk
+        .filter_map(|oid| oid.ok())
+        .filter_map(|oid| repo.find_commit(oid).ok())
+        // 時刻順に走査しているので、期間外に出たら打ち切る
+        .take_while(|commit| commit.time().seconds() >= cutoff)
+        .count()
+}
+
 /// WorktreeInfoからWorkspaceを生成
 impl From<WorktreeInfo> for Workspace {
     fn from(info: WorktreeInfo) -> Self {
-        Workspace::new(
+        let mut workspace = Workspace::new(
             info.path.to_string_lossy().to_string(),
             info.repo_name,
             info.branch,
-        )
+        );
+        workspace.remote_host = info
+            .remote_url
+            .as_deref()
+            .and_then(parse_git_url)
+            .map(|(host, _, _)| host);
+        workspace.remote_url = info.remote_url;
+        workspace.git_operation = info.operation;
+        workspace
     }
 }
 
@@ -203,6 +269,76 @@ fn scan_recursive(path: &Path, max_depth: usize, current_depth: usize, results:
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_count_recent_commits_non_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(count_recent_commits(dir.path(), 7), 0);
+    }
+
+    #[test]
+    fn test_count_recent_commits_stops_at_cutoff() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let now = chrono::Utc::now().timestamp();
+        let mut parents: Vec<git2::Commit> = Vec::new();
+        // 30日前に1件、直近に2件
+        for (i, age_days) in [30, 2, 0].into_iter().enumerate() {
+            let time = git2::Time::new(now - age_days * 24 * 60 * 60, 0);
+            let sig = git2::Signature::new("test", "test@example.com", &time).unwrap();
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+            let oid = repo
+                .commit(Some("HEAD"), &sig, &sig, &format!("commit {}", i), &tree, &parent_refs)
+                .unwrap();
+            parents = vec![repo.find_commit(oid).unwrap()];
+        }
+
+        assert_eq!(count_recent_commits(dir.path(), 7), 2);
+        assert_eq!(count_recent_commits(dir.path(), 60), 3);
+        assert_eq!(count_recent_commits(dir.path(), 0), 0);
+    }
+
+    #[test]
+    fn test_worktree_root_from_subdirectory() {
+        let dir = tempfile::tempdir().unwrap();
+        Repository::init(dir.path()).unwrap();
+        let sub = dir.path().join("src/app");
+        std::fs::create_dir_all(&sub).unwrap();
+
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        assert_eq!(worktree_root(&sub), Some(root.clone()));
+        assert_eq!(worktree_root(&root), Some(root));
+
+        let outside = tempfile::tempdir().unwrap();
+        assert_eq!(worktree_root(outside.path()), None);
+    }
+
+    #[test]
+    fn test_detects_merge_in_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        assert_eq!(extract_worktree_info(dir.path()).unwrap().operation, None);
+
+        std::fs::write(repo.path().join("MERGE_HEAD"), "0000000000000000000000000000000000000000\n").unwrap();
+        let workspace: Workspace = extract_worktree_info(dir.path()).unwrap().into();
+        assert_eq!(workspace.git_operation, Some(GitOperation::Merge));
+    }
+
+    #[test]
+    fn test_workspace_from_info_keeps_remote_host() {
+        let info = WorktreeInfo {
+            path: PathBuf::from("/work/repo"),
+            repo_name: "repo".to_string(),
+            branch: "main".to_string(),
+            is_main: true,
+            remote_url: Some("git@gitlab.example.com:team/repo.git".to_string()),
+            operation: None,
+        };
+        let ws = Workspace::from(info);
+        assert_eq!(ws.remote_host.as_deref(), Some("gitlab.example.com"));
+        assert_eq!(ws.remote_url.as_deref(), Some("git@gitlab.example.com:team/repo.git"));
+    }
+
     #[test]
     fn test_get_default_search_paths() {
         let paths = get_default_search_paths();